@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+/// Table every index in this module belongs to
+pub const VIDEOS_TABLE: &str = "videos";
+
+/// Outcome of ensuring a single index exists
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexOutcome {
+    pub name: String,
+    pub created: bool,
+}
+
+/// Index definitions the `videos` table is expected to carry, keyed by name
+///
+/// Shared by the migration that first creates them and the maintenance
+/// endpoint that restores any that have gone missing.
+pub fn video_indexes() -> Vec<(&'static str, IndexCreateStatement)> {
+    vec![
+        (
+            "idx_videos_youtube_id",
+            Index::create()
+                .name("idx_videos_youtube_id")
+                .table(Videos::Table)
+                .col(Videos::YoutubeId)
+                .to_owned(),
+        ),
+        (
+            "idx_videos_title",
+            Index::create()
+                .name("idx_videos_title")
+                .table(Videos::Table)
+                .col(Videos::Title)
+                .to_owned(),
+        ),
+        (
+            "idx_videos_created_at_id",
+            Index::create()
+                .name("idx_videos_created_at_id")
+                .table(Videos::Table)
+                .col(Videos::CreatedAt)
+                .col(Videos::Id)
+                .to_owned(),
+        ),
+    ]
+}
+
+/// Creates each index from `video_indexes` that doesn't exist yet
+///
+/// Existing indexes are left untouched, so this is safe to run repeatedly.
+pub async fn ensure_video_indexes(manager: &SchemaManager<'_>) -> Result<Vec<IndexOutcome>, DbErr> {
+    let mut outcomes = Vec::new();
+    for (name, mut index) in video_indexes() {
+        let created = !manager.has_index(VIDEOS_TABLE, name).await?;
+        if created {
+            manager.create_index(index.if_not_exists().to_owned()).await?;
+        }
+        outcomes.push(IndexOutcome { name: name.to_string(), created });
+    }
+    Ok(outcomes)
+}
+
+/// Name of the optional unique index over `videos.title_normalized`
+pub const TITLE_NORMALIZED_UNIQUE_INDEX: &str = "idx_videos_title_normalized_unique";
+
+/// Creates or drops the unique index over `title_normalized` to match `enabled`
+///
+/// Creating the index fails if existing rows already share a normalized
+/// title. Returns whether the schema was changed.
+pub async fn sync_title_normalized_unique(manager: &SchemaManager<'_>, enabled: bool) -> Result<bool, DbErr> {
+    let present = manager.has_index(VIDEOS_TABLE, TITLE_NORMALIZED_UNIQUE_INDEX).await?;
+    match (enabled, present) {
+        (true, false) => {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(TITLE_NORMALIZED_UNIQUE_INDEX)
+                        .table(Videos::Table)
+                        .col(Videos::TitleNormalized)
+                        .unique()
+                        .to_owned(),
+                )
+                .await?;
+            Ok(true)
+        }
+        (false, true) => {
+            manager
+                .drop_index(
+                    Index::drop()
+                        .name(TITLE_NORMALIZED_UNIQUE_INDEX)
+                        .table(Videos::Table)
+                        .to_owned(),
+                )
+                .await?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+#[derive(DeriveIden)]
+enum Videos {
+    Table,
+    Id,
+    Title,
+    TitleNormalized,
+    YoutubeId,
+    CreatedAt,
+}
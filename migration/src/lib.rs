@@ -1,6 +1,18 @@
 pub use sea_orm_migration::prelude::*;
 
+pub mod indexes;
+
 mod m20240101_000001_create_videos;
+mod m20240201_000001_backfill_updated_at;
+mod m20240301_000001_add_video_indexes;
+mod m20240401_000001_add_title_normalized;
+mod m20240501_000001_add_video_description;
+mod m20240601_000001_add_video_view_count;
+mod m20240701_000001_create_tags;
+mod m20240801_000001_timestamps_with_time_zone;
+mod m20240901_000001_add_video_fts;
+mod m20241001_000001_add_video_status;
+mod m20241101_000001_add_video_position;
 
 pub struct Migrator;
 
@@ -9,6 +21,16 @@ impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         vec![
             Box::new(m20240101_000001_create_videos::Migration),
+            Box::new(m20240201_000001_backfill_updated_at::Migration),
+            Box::new(m20240301_000001_add_video_indexes::Migration),
+            Box::new(m20240401_000001_add_title_normalized::Migration),
+            Box::new(m20240501_000001_add_video_description::Migration),
+            Box::new(m20240601_000001_add_video_view_count::Migration),
+            Box::new(m20240701_000001_create_tags::Migration),
+            Box::new(m20240801_000001_timestamps_with_time_zone::Migration),
+            Box::new(m20240901_000001_add_video_fts::Migration),
+            Box::new(m20241001_000001_add_video_status::Migration),
+            Box::new(m20241101_000001_add_video_position::Migration),
         ]
     }
 }
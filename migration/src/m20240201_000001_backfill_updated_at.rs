@@ -0,0 +1,254 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend, Statement};
+
+/// Repairs `videos` tables created by the old hand-written `init_db` schema
+///
+/// Those tables may lack `updated_at`/`deleted_at` or allow `updated_at` to
+/// be null. This migration adds any missing columns, backfills
+/// `updated_at = created_at` where it is null and then makes `updated_at`
+/// non-null. Every step is a no-op on a table created by the initial
+/// migration, so it is safe to run repeatedly.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [Videos::UpdatedAt, Videos::DeletedAt] {
+            if !manager.has_column("videos", column.to_string()).await? {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Videos::Table)
+                            .add_column(ColumnDef::new(column).timestamp().null())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Videos::Table)
+                    .value(Videos::UpdatedAt, Expr::col(Videos::CreatedAt))
+                    .and_where(Expr::col(Videos::UpdatedAt).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        match manager.get_database_backend() {
+            DbBackend::Sqlite => {
+                if updated_at_is_nullable_sqlite(manager).await? {
+                    rebuild_sqlite_table(manager).await?;
+                }
+            }
+            DbBackend::Postgres => {
+                manager
+                    .get_connection()
+                    .execute_unprepared(r#"ALTER TABLE "videos" ALTER COLUMN "updated_at" SET NOT NULL"#)
+                    .await?;
+            }
+            DbBackend::MySql => {
+                manager
+                    .alter_table(
+                        Table::alter()
+                            .table(Videos::Table)
+                            .modify_column(ColumnDef::new(Videos::UpdatedAt).timestamp().not_null())
+                            .to_owned(),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // The backfilled values can't be told apart from real ones, so there
+        // is nothing meaningful to undo.
+        Ok(())
+    }
+}
+
+/// Reads the `notnull` flag of `videos.updated_at` from SQLite's table info
+async fn updated_at_is_nullable_sqlite(manager: &SchemaManager<'_>) -> Result<bool, DbErr> {
+    let rows = manager
+        .get_connection()
+        .query_all(Statement::from_string(
+            DbBackend::Sqlite,
+            r#"PRAGMA table_info("videos")"#,
+        ))
+        .await?;
+
+    for row in rows {
+        let name: String = row.try_get("", "name")?;
+        if name == "updated_at" {
+            let not_null: i32 = row.try_get("", "notnull")?;
+            return Ok(not_null == 0);
+        }
+    }
+
+    Ok(false)
+}
+
+/// SQLite can't alter a column's nullability, so copy the rows into a
+/// freshly created table with the intended schema
+async fn rebuild_sqlite_table(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    manager
+        .rename_table(
+            Table::rename()
+                .table(Videos::Table, VideosBackup::Table)
+                .to_owned(),
+        )
+        .await?;
+
+    manager
+        .create_table(
+            Table::create()
+                .table(Videos::Table)
+                .col(
+                    ColumnDef::new(Videos::Id)
+                        .integer()
+                        .not_null()
+                        .auto_increment()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Videos::Title).string().not_null())
+                .col(ColumnDef::new(Videos::YoutubeId).string().not_null())
+                .col(ColumnDef::new(Videos::CreatedAt).timestamp().not_null())
+                .col(ColumnDef::new(Videos::UpdatedAt).timestamp().not_null())
+                .col(ColumnDef::new(Videos::DeletedAt).timestamp().null())
+                .to_owned(),
+        )
+        .await?;
+
+    let columns = [
+        Videos::Id,
+        Videos::Title,
+        Videos::YoutubeId,
+        Videos::CreatedAt,
+        Videos::UpdatedAt,
+        Videos::DeletedAt,
+    ];
+    manager
+        .exec_stmt(
+            Query::insert()
+                .into_table(Videos::Table)
+                .columns(columns)
+                .select_from(
+                    Query::select()
+                        .columns(columns)
+                        .from(VideosBackup::Table)
+                        .to_owned(),
+                )
+                .map_err(|e| DbErr::Migration(e.to_string()))?
+                .to_owned(),
+        )
+        .await?;
+
+    manager
+        .drop_table(Table::drop().table(VideosBackup::Table).to_owned())
+        .await
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum Videos {
+    Table,
+    Id,
+    Title,
+    YoutubeId,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum VideosBackup {
+    #[sea_orm(iden = "videos_backfill_backup")]
+    Table,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm_migration::sea_orm::{Database, DatabaseConnection};
+
+    async fn legacy_database(create_sql: &str, insert_sql: &str) -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(create_sql).await.unwrap();
+        db.execute_unprepared(insert_sql).await.unwrap();
+        db
+    }
+
+    async fn video_timestamps(db: &DatabaseConnection) -> (String, Option<String>) {
+        let row = db
+            .query_one(Statement::from_string(
+                DbBackend::Sqlite,
+                "SELECT created_at, updated_at FROM videos WHERE id = 1",
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        (
+            row.try_get("", "created_at").unwrap(),
+            row.try_get("", "updated_at").unwrap(),
+        )
+    }
+
+    #[async_std::test]
+    async fn test_backfills_null_updated_at() {
+        let db = legacy_database(
+            "CREATE TABLE videos (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                youtube_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NULL,
+                deleted_at TEXT NULL
+            )",
+            "INSERT INTO videos (title, youtube_id, created_at, updated_at)
+             VALUES ('Legacy', 'dQw4w9WgXcQ', '2024-01-01 00:00:00', NULL)",
+        )
+        .await;
+
+        let manager = SchemaManager::new(&db);
+        Migration.up(&manager).await.unwrap();
+        // Running again must be harmless
+        Migration.up(&manager).await.unwrap();
+
+        let (created_at, updated_at) = video_timestamps(&db).await;
+        assert_eq!(updated_at.as_deref(), Some(created_at.as_str()));
+        assert!(!updated_at_is_nullable_sqlite(&manager).await.unwrap());
+
+        let null_insert = db
+            .execute_unprepared(
+                "INSERT INTO videos (title, youtube_id, created_at, updated_at)
+                 VALUES ('New', 'xQc9WgXw4Qd', '2024-01-02 00:00:00', NULL)",
+            )
+            .await;
+        assert!(null_insert.is_err());
+    }
+
+    #[async_std::test]
+    async fn test_adds_missing_timestamp_columns() {
+        let db = legacy_database(
+            "CREATE TABLE videos (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                youtube_id TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            "INSERT INTO videos (title, youtube_id, created_at)
+             VALUES ('Legacy', 'dQw4w9WgXcQ', '2024-01-01 00:00:00')",
+        )
+        .await;
+
+        let manager = SchemaManager::new(&db);
+        Migration.up(&manager).await.unwrap();
+
+        let (created_at, updated_at) = video_timestamps(&db).await;
+        assert_eq!(updated_at.as_deref(), Some(created_at.as_str()));
+        assert!(manager.has_column("videos", "deleted_at").await.unwrap());
+    }
+}
@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+use crate::indexes::{ensure_video_indexes, video_indexes, VIDEOS_TABLE};
+
+/// Adds the lookup and list-ordering indexes on `videos`
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        ensure_video_indexes(manager).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, _) in video_indexes() {
+            manager
+                .drop_index(Index::drop().name(name).table(Alias::new(VIDEOS_TABLE)).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Migrator, MigratorTrait};
+    use sea_orm_migration::sea_orm::Database;
+
+    #[async_std::test]
+    async fn test_indexes_created_and_idempotent() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        Migrator::up(&db, None).await.unwrap();
+
+        let manager = SchemaManager::new(&db);
+        for (name, _) in video_indexes() {
+            assert!(manager.has_index(VIDEOS_TABLE, name).await.unwrap(), "{} missing", name);
+        }
+
+        let outcomes = ensure_video_indexes(&manager).await.unwrap();
+        assert!(outcomes.iter().all(|outcome| !outcome.created));
+    }
+}
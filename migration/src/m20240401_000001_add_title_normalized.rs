@@ -0,0 +1,105 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `videos.title_normalized`, the lowercased and trimmed title
+///
+/// Existing rows are backfilled from `title`. The unique index over this
+/// column is optional and managed at startup from configuration, see
+/// `indexes::sync_title_normalized_unique`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager.has_column("videos", "title_normalized").await? {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Videos::Table)
+                        .add_column(
+                            ColumnDef::new(Videos::TitleNormalized)
+                                .string()
+                                .not_null()
+                                .default(""),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Videos::Table)
+                    .value(
+                        Videos::TitleNormalized,
+                        Func::lower(Func::cust(Alias::new("TRIM")).arg(Expr::col(Videos::Title))),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .drop_column(Videos::TitleNormalized)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Videos {
+    Table,
+    Title,
+    TitleNormalized,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm_migration::sea_orm::{ConnectionTrait, Database, DbBackend, Statement};
+
+    #[async_std::test]
+    async fn test_backfills_title_normalized() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE videos (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                youtube_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                deleted_at TEXT NULL
+            )",
+        )
+        .await
+        .unwrap();
+        db.execute_unprepared(
+            "INSERT INTO videos (title, youtube_id, created_at, updated_at)
+             VALUES ('  Hello World ', 'dQw4w9WgXcQ', '2024-01-01 00:00:00', '2024-01-01 00:00:00')",
+        )
+        .await
+        .unwrap();
+
+        let manager = SchemaManager::new(&db);
+        Migration.up(&manager).await.unwrap();
+        // Running again must be harmless
+        Migration.up(&manager).await.unwrap();
+
+        let row = db
+            .query_one(Statement::from_string(
+                DbBackend::Sqlite,
+                "SELECT title_normalized FROM videos WHERE id = 1",
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        let normalized: String = row.try_get("", "title_normalized").unwrap();
+        assert_eq!(normalized, "hello world");
+    }
+}
@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the optional `videos.description` text column
+///
+/// Existing rows keep `NULL`, which the entity reads as no description.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("videos", "description").await? {
+            return Ok(());
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .add_column(ColumnDef::new(Videos::Description).text().null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .drop_column(Videos::Description)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Videos {
+    Table,
+    Description,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm_migration::sea_orm::{ConnectionTrait, Database, DbBackend, Statement};
+
+    #[async_std::test]
+    async fn test_existing_rows_get_null_description() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE videos (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                youtube_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                deleted_at TEXT NULL
+            )",
+        )
+        .await
+        .unwrap();
+        db.execute_unprepared(
+            "INSERT INTO videos (title, youtube_id, created_at, updated_at)
+             VALUES ('Old', 'dQw4w9WgXcQ', '2024-01-01 00:00:00', '2024-01-01 00:00:00')",
+        )
+        .await
+        .unwrap();
+
+        let manager = SchemaManager::new(&db);
+        Migration.up(&manager).await.unwrap();
+        // Running again must be harmless
+        Migration.up(&manager).await.unwrap();
+
+        let row = db
+            .query_one(Statement::from_string(
+                DbBackend::Sqlite,
+                "SELECT description FROM videos WHERE id = 1",
+            ))
+            .await
+            .unwrap()
+            .unwrap();
+        let description: Option<String> = row.try_get("", "description").unwrap();
+        assert_eq!(description, None);
+    }
+}
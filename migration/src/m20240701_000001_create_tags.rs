@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+/// Creates `tags` and the `video_tags` join table linking them to videos
+///
+/// Tag names are unique; links are removed with either side.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tags::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tags::Name).string().not_null().unique_key())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(VideoTags::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(VideoTags::VideoId).integer().not_null())
+                    .col(ColumnDef::new(VideoTags::TagId).integer().not_null())
+                    .primary_key(Index::create().col(VideoTags::VideoId).col(VideoTags::TagId))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VideoTags::Table, VideoTags::VideoId)
+                            .to(Videos::Table, Videos::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(VideoTags::Table, VideoTags::TagId)
+                            .to(Tags::Table, Tags::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(VideoTags::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Tags::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Videos {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Tags {
+    Table,
+    Id,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum VideoTags {
+    Table,
+    VideoId,
+    TagId,
+}
@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// Stores the `videos` timestamps as `timestamptz` on Postgres
+///
+/// The earlier migrations create them as plain `timestamp`, which Postgres
+/// refuses to decode into the entity's `DateTime<Utc>`. Existing values are
+/// read as UTC, the zone they were always written in. SQLite keeps
+/// timestamps as text and MySQL's `timestamp` already maps to UTC, so both
+/// are left alone.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const COLUMNS: [&str; 3] = ["created_at", "updated_at", "deleted_at"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+
+        for column in COLUMNS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"ALTER TABLE "videos" ALTER COLUMN "{0}" TYPE timestamptz USING "{0}" AT TIME ZONE 'UTC'"#,
+                    column
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+
+        for column in COLUMNS {
+            manager
+                .get_connection()
+                .execute_unprepared(&format!(
+                    r#"ALTER TABLE "videos" ALTER COLUMN "{0}" TYPE timestamp USING "{0}" AT TIME ZONE 'UTC'"#,
+                    column
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, DbBackend};
+
+/// Indexes video titles and descriptions for full-text search
+///
+/// SQLite gets an FTS5 table, `videos_fts`, mirroring `videos` through
+/// triggers so every insert, update and delete keeps it in sync; it is
+/// skipped when the SQLite build lacks FTS5. Postgres gets a GIN index on
+/// the same `tsvector` expression the repository queries, which Postgres
+/// maintains itself. MySQL is left alone and keeps using LIKE.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const SQLITE_UP: [&str; 4] = [
+    r#"CREATE TRIGGER IF NOT EXISTS "videos_fts_insert" AFTER INSERT ON "videos" BEGIN
+        INSERT INTO "videos_fts" (rowid, "title", "description") VALUES (new."id", new."title", new."description");
+    END"#,
+    r#"CREATE TRIGGER IF NOT EXISTS "videos_fts_delete" AFTER DELETE ON "videos" BEGIN
+        INSERT INTO "videos_fts" ("videos_fts", rowid, "title", "description") VALUES ('delete', old."id", old."title", old."description");
+    END"#,
+    r#"CREATE TRIGGER IF NOT EXISTS "videos_fts_update" AFTER UPDATE OF "title", "description" ON "videos" BEGIN
+        INSERT INTO "videos_fts" ("videos_fts", rowid, "title", "description") VALUES ('delete', old."id", old."title", old."description");
+        INSERT INTO "videos_fts" (rowid, "title", "description") VALUES (new."id", new."title", new."description");
+    END"#,
+    r#"INSERT INTO "videos_fts" ("videos_fts") VALUES ('rebuild')"#,
+];
+
+const SQLITE_DOWN: [&str; 4] = [
+    r#"DROP TRIGGER IF EXISTS "videos_fts_insert""#,
+    r#"DROP TRIGGER IF EXISTS "videos_fts_delete""#,
+    r#"DROP TRIGGER IF EXISTS "videos_fts_update""#,
+    r#"DROP TABLE IF EXISTS "videos_fts""#,
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        match manager.get_database_backend() {
+            DbBackend::Sqlite => {
+                let created = conn
+                    .execute_unprepared(
+                        r#"CREATE VIRTUAL TABLE IF NOT EXISTS "videos_fts" USING fts5(
+                            "title", "description",
+                            content='videos', content_rowid='id', tokenize='porter unicode61'
+                        )"#,
+                    )
+                    .await;
+                match created {
+                    Ok(_) => {}
+                    Err(e) if e.to_string().contains("no such module") => return Ok(()),
+                    Err(e) => return Err(e),
+                }
+
+                for statement in SQLITE_UP {
+                    conn.execute_unprepared(statement).await?;
+                }
+                Ok(())
+            }
+            DbBackend::Postgres => {
+                conn.execute_unprepared(
+                    r#"CREATE INDEX IF NOT EXISTS "idx_videos_fts" ON "videos"
+                        USING GIN (to_tsvector('english', coalesce("title", '') || ' ' || coalesce("description", '')))"#,
+                )
+                .await?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let conn = manager.get_connection();
+        match manager.get_database_backend() {
+            DbBackend::Sqlite => {
+                for statement in SQLITE_DOWN {
+                    conn.execute_unprepared(statement).await?;
+                }
+                Ok(())
+            }
+            DbBackend::Postgres => {
+                conn.execute_unprepared(r#"DROP INDEX IF EXISTS "idx_videos_fts""#).await?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `videos.status`, starting existing rows as `draft`
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("videos", "status").await? {
+            return Ok(());
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .add_column(
+                        ColumnDef::new(Videos::Status)
+                            .string_len(16)
+                            .not_null()
+                            .default("draft"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .drop_column(Videos::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Videos {
+    Table,
+    Status,
+}
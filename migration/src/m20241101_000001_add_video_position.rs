@@ -0,0 +1,130 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds `videos.position`, the manual ordering used by playlist UIs
+///
+/// Existing live rows are numbered 1, 2, 3, ... in id order, so the manual
+/// order starts out as the creation order. Soft-deleted rows keep 0 and are
+/// appended when restored. Running again leaves positions that have since
+/// been rearranged alone.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.has_column("videos", "position").await? {
+            return Ok(());
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .add_column(ColumnDef::new(Videos::Position).integer().not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Videos::Table)
+                    .value(
+                        Videos::Position,
+                        Expr::cust(
+                            r#"(SELECT COUNT(*) FROM "videos" AS "earlier" WHERE "earlier"."id" <= "videos"."id" AND "earlier"."deleted_at" IS NULL)"#,
+                        ),
+                    )
+                    .and_where(Expr::col(Videos::DeletedAt).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_videos_position")
+                    .table(Videos::Table)
+                    .col(Videos::Position)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("idx_videos_position").table(Videos::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Videos::Table)
+                    .drop_column(Videos::Position)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Videos {
+    Table,
+    Position,
+    DeletedAt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm_migration::sea_orm::{ConnectionTrait, Database, DbBackend, Statement};
+
+    #[async_std::test]
+    async fn test_backfills_live_positions_in_id_order() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(
+            "CREATE TABLE videos (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                youtube_id TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                deleted_at TEXT NULL
+            )",
+        )
+        .await
+        .unwrap();
+        for id in [3, 5, 7, 9] {
+            db.execute_unprepared(&format!(
+                "INSERT INTO videos (id, title, youtube_id, created_at, updated_at)
+                 VALUES ({}, 'Video', 'dQw4w9WgXcQ', '2024-01-01 00:00:00', '2024-01-01 00:00:00')",
+                id
+            ))
+            .await
+            .unwrap();
+        }
+        db.execute_unprepared("UPDATE videos SET deleted_at = '2024-02-01 00:00:00' WHERE id = 5")
+            .await
+            .unwrap();
+
+        let manager = SchemaManager::new(&db);
+        Migration.up(&manager).await.unwrap();
+        db.execute_unprepared("UPDATE videos SET position = 1 WHERE id = 9").await.unwrap();
+        // Running again must keep rearranged positions
+        Migration.up(&manager).await.unwrap();
+
+        let rows = db
+            .query_all(Statement::from_string(
+                DbBackend::Sqlite,
+                "SELECT id, position FROM videos ORDER BY id",
+            ))
+            .await
+            .unwrap();
+        let positions: Vec<(i32, i32)> = rows
+            .iter()
+            .map(|row| (row.try_get("", "id").unwrap(), row.try_get("", "position").unwrap()))
+            .collect();
+        // The soft-deleted row is skipped and leaves no gap
+        assert_eq!(positions, vec![(3, 1), (5, 0), (7, 2), (9, 1)]);
+    }
+}
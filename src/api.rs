@@ -1,228 +1,393 @@
-use ntex::web::{self, types::{Json, Path, Query, State}, HttpResponse, Responder};
-use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
-use validator::Validate;
-use tracing::info;
+use serde_json::Value;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
 use chrono::{DateTime, Utc};
 
-use crate::db::{self, VideoQuery};
+use crate::entity::video::{self, VideoStatus};
+use crate::services::recent_ops::RecentOp;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateVideoRequest {
     #[validate(length(min = 1, max = 100))]
     pub title: String,
-    #[validate(length(min = 11, max = 11))]
+    #[validate(length(min = 11, max = 11), custom = "validate_youtube_id")]
     pub youtube_id: String,
+    #[serde(default)]
+    #[validate(length(max = 5000))]
+    pub description: Option<String>,
+    /// Tag names; trimmed, lowercased and deduplicated before saving
+    #[serde(default)]
+    #[validate(length(max = 20), custom = "validate_tag_names")]
+    pub tags: Vec<String>,
+    /// Initial status; `draft` when omitted
+    #[serde(default)]
+    pub status: Option<VideoStatus>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+/// Rejects YouTube IDs with characters outside `A-Za-z0-9_-`
+pub fn validate_youtube_id(youtube_id: &str) -> Result<(), ValidationError> {
+    if youtube_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("youtube_id_format");
+        error.message = Some("may only contain letters, digits, '_' and '-'".into());
+        Err(error)
+    }
+}
+
+/// Longest accepted tag name, in characters
+pub const MAX_TAG_LEN: usize = 50;
+
+fn validate_tag_names(tags: &[String]) -> Result<(), ValidationError> {
+    let valid = |tag: &String| {
+        let len = tag.trim().chars().count();
+        (1..=MAX_TAG_LEN).contains(&len)
+    };
+    if tags.iter().all(valid) {
+        Ok(())
+    } else {
+        let mut error = ValidationError::new("tag_length");
+        error.message = Some(format!("each tag must be 1 to {} characters", MAX_TAG_LEN).into());
+        Err(error)
+    }
+}
+
+/// Deserializes a field that may be absent, `null` or a value into `None`, `Some(None)` or `Some(Some(_))`
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct UpdateVideoRequest {
     #[validate(length(min = 1, max = 100))]
     pub title: Option<String>,
-    #[validate(length(min = 11, max = 11))]
+    #[validate(length(min = 11, max = 11), custom = "validate_youtube_id")]
     pub youtube_id: Option<String>,
+    /// Left as it is when absent; `null` clears it
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<String>)]
+    #[validate(length(max = 5000))]
+    pub description: Option<Option<String>>,
+    #[serde(default)]
+    pub status: Option<VideoStatus>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Body of a full replace (`PUT`): every writable field must be present
+/// 
+/// An omitted `description` is replaced with `null`, like an explicit one.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct ReplaceVideoRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub title: String,
+    #[validate(length(min = 11, max = 11), custom = "validate_youtube_id")]
+    pub youtube_id: String,
+    #[serde(default)]
+    #[validate(length(max = 5000))]
+    pub description: Option<String>,
+}
+
+impl From<ReplaceVideoRequest> for UpdateVideoRequest {
+    fn from(req: ReplaceVideoRequest) -> Self {
+        UpdateVideoRequest {
+            title: Some(req.title),
+            youtube_id: Some(req.youtube_id),
+            description: Some(req.description),
+            status: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VideoResponse {
     pub id: i32,
     pub title: String,
     pub youtube_id: String,
+    pub description: Option<String>,
+    pub view_count: i64,
+    #[serde(default)]
+    pub status: VideoStatus,
+    /// 1-based place in the manual ordering, for `order_by=position`
+    #[serde(default)]
+    pub position: i32,
+    /// Tag names sorted alphabetically
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Fields containing the `search` term, ignoring case; only set on search results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched_fields: Option<Vec<String>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Converts a bare row; `tags` is left empty as tags live in another table
+impl From<video::Model> for VideoResponse {
+    fn from(video: video::Model) -> Self {
+        Self {
+            id: video.id,
+            title: video.title,
+            youtube_id: video.youtube_id,
+            description: video.description,
+            view_count: video.view_count,
+            status: video.status,
+            position: video.position,
+            tags: Vec::new(),
+            created_at: video.created_at,
+            updated_at: video.updated_at,
+            deleted_at: video.deleted_at,
+            matched_fields: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PaginatedVideoResponse {
     pub videos: Vec<VideoResponse>,
     pub total: u64,
     pub page: u64,
     pub per_page: u64,
     pub total_pages: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_including_deleted: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
-#[web::get("/")]
-pub async fn index() -> impl Responder {
-    HttpResponse::Ok()
-        .content_type("text/plain")
-        .body("Welcome to Video API!")
-}
-
-#[web::post("/videos")]
-pub async fn create_video(
-    req: Json<CreateVideoRequest>,
-    data: State<DatabaseConnection>,
-) -> impl Responder {
-    info!("Creating new video: {}", req.title);
-    
-    match req.validate() {
-        Ok(_) => (),
-        Err(e) => return HttpResponse::BadRequest().json(&serde_json::json!({
-            "error": format!("Validation error: {}", e)
-        })),
-    }
+/// One page of `GET /videos?cursor=true`, ordered by id ascending
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CursorVideoResponse {
+    pub videos: Vec<VideoResponse>,
+    pub per_page: u64,
+    /// Pass as `after` to fetch the next page; `null` once the last page is reached
+    pub next_cursor: Option<i32>,
+}
 
-    match db::create_video(data.get_ref(), req.title.clone(), req.youtube_id.clone()).await {
-        Ok(video) => HttpResponse::Created().json(&VideoResponse {
-            id: video.id,
-            title: video.title,
-            youtube_id: video.youtube_id,
-            created_at: video.created_at,
-            updated_at: video.updated_at,
-            deleted_at: video.deleted_at,
-        }),
-        Err(e) => HttpResponse::InternalServerError().json(&serde_json::json!({
-            "error": format!("Database error: {}", e)
-        })),
-    }
+/// schema.org `VideoObject` JSON-LD document describing a video
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoObject {
+    #[serde(rename = "@context")]
+    pub context: String,
+    #[serde(rename = "@type")]
+    pub kind: String,
+    pub name: String,
+    #[serde(rename = "embedUrl")]
+    pub embed_url: String,
+    #[serde(rename = "thumbnailUrl")]
+    pub thumbnail_url: String,
+    #[serde(rename = "uploadDate")]
+    pub upload_date: DateTime<Utc>,
 }
 
-#[web::get("/videos")]
-pub async fn list_videos(
-    query: Query<VideoQuery>,
-    data: State<DatabaseConnection>,
-) -> impl Responder {
-    info!("Listing videos with query: {:?}", query);
-    
-    match db::list_videos(data.get_ref(), query.into_inner()).await {
-        Ok(result) => {
-            let videos = result.videos.into_iter().map(|v| VideoResponse {
-                id: v.id,
-                title: v.title,
-                youtube_id: v.youtube_id,
-                created_at: v.created_at,
-                updated_at: v.updated_at,
-                deleted_at: v.deleted_at,
-            }).collect();
-
-            HttpResponse::Ok().json(&PaginatedVideoResponse {
-                videos,
-                total: result.total,
-                page: result.page,
-                per_page: result.per_page,
-                total_pages: result.total_pages,
-            })
-        },
-        Err(e) => HttpResponse::InternalServerError().json(&serde_json::json!({
-            "error": format!("Database error: {}", e)
-        })),
+impl From<&VideoResponse> for VideoObject {
+    fn from(video: &VideoResponse) -> Self {
+        Self {
+            context: "https://schema.org".to_string(),
+            kind: "VideoObject".to_string(),
+            name: video.title.clone(),
+            embed_url: format!("https://www.youtube.com/embed/{}", video.youtube_id),
+            thumbnail_url: format!("https://i.ytimg.com/vi/{}/hqdefault.jpg", video.youtube_id),
+            upload_date: video.created_at,
+        }
     }
 }
 
-#[web::get("/videos/{id}")]
-pub async fn get_video(
-    id: Path<i32>,
-    data: State<DatabaseConnection>,
-) -> impl Responder {
-    info!("Getting video with id: {}", id);
-    
-    match db::get_video(data.get_ref(), *id).await {
-        Ok(Some(video)) => HttpResponse::Ok().json(&VideoResponse {
-            id: video.id,
-            title: video.title,
-            youtube_id: video.youtube_id,
-            created_at: video.created_at,
-            updated_at: video.updated_at,
-            deleted_at: video.deleted_at,
-        }),
-        Ok(None) => HttpResponse::NotFound().json(&serde_json::json!({
-            "error": format!("Video with id {} not found", id)
-        })),
-        Err(e) => HttpResponse::InternalServerError().json(&serde_json::json!({
-            "error": format!("Database error: {}", e)
-        })),
-    }
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct FetchVideosRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub ids: Vec<i32>,
 }
 
-#[web::put("/videos/{id}")]
-pub async fn update_video(
-    id: Path<i32>,
-    req: Json<UpdateVideoRequest>,
-    data: State<DatabaseConnection>,
-) -> impl Responder {
-    info!("Updating video with id: {}", id);
-    
-    match req.validate() {
-        Ok(_) => (),
-        Err(e) => return HttpResponse::BadRequest().json(&serde_json::json!({
-            "error": format!("Validation error: {}", e)
-        })),
-    }
+/// Body of `POST /videos/fetch`, with ids in their public form
+/// 
+/// Integers in raw mode and hashid strings when a hashid salt is configured;
+/// the controller decodes them into a `FetchVideosRequest`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PublicFetchVideosRequest {
+    pub ids: Vec<Value>,
+}
 
-    match db::update_video(data.get_ref(), *id, req.title.clone(), req.youtube_id.clone()).await {
-        Ok(Some(video)) => HttpResponse::Ok().json(&VideoResponse {
-            id: video.id,
-            title: video.title,
-            youtube_id: video.youtube_id,
-            created_at: video.created_at,
-            updated_at: video.updated_at,
-            deleted_at: video.deleted_at,
-        }),
-        Ok(None) => HttpResponse::NotFound().json(&serde_json::json!({
-            "error": format!("Video with id {} not found", id)
-        })),
-        Err(e) => HttpResponse::InternalServerError().json(&serde_json::json!({
-            "error": format!("Database error: {}", e)
-        })),
-    }
+/// Body of `PATCH /videos/{id}/position`
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MoveVideoRequest {
+    /// 1-based place to move the video to; larger values move it to the end
+    #[validate(range(min = 1))]
+    pub position: i32,
 }
 
-#[web::delete("/videos/{id}")]
-pub async fn delete_video(
-    id: Path<i32>,
-    data: State<DatabaseConnection>,
-) -> impl Responder {
-    info!("Deleting video with id: {}", id);
-    
-    match db::delete_video(data.get_ref(), *id).await {
-        Ok(true) => HttpResponse::Ok().json(&serde_json::json!({
-            "message": format!("Video with id {} deleted", id)
-        })),
-        Ok(false) => HttpResponse::NotFound().json(&serde_json::json!({
-            "error": format!("Video with id {} not found", id)
-        })),
-        Err(e) => HttpResponse::InternalServerError().json(&serde_json::json!({
-            "error": format!("Database error: {}", e)
-        })),
-    }
+/// Body of `POST /videos/swap`, with ids in their public form
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapVideosRequest {
+    pub a: Value,
+    pub b: Value,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use ntex::web::test;
-    use chrono::Utc;
-
-    #[ntex::test]
-    async fn test_create_video() {
-        let db = db::init_db().await;
-        let app = test::init_service(
-            web::App::new()
-                .state(db)
-                .configure(crate::app::config_app),
-        )
-        .await;
-
-        let req = test::TestRequest::post()
-            .uri("/videos")
-            .set_json(&CreateVideoRequest {
-                title: "Test Video".to_string(),
-                youtube_id: "dQw4w9WgXcQ".to_string(),
-            })
-            .to_request();
-
-        let resp = test::call_service(&app, req).await;
-        assert_eq!(resp.status(), 201);
-
-        let body = test::read_body(resp).await;
-        let video: VideoResponse = serde_json::from_slice(&body).unwrap();
-
-        assert_eq!(video.title, "Test Video");
-        assert_eq!(video.youtube_id, "dQw4w9WgXcQ");
-        assert!(video.id > 0);
-        assert!(video.created_at <= Utc::now());
-        assert!(video.updated_at <= Utc::now());
-        assert!(video.deleted_at.is_none());
-    }
+/// The two videos of a swap, with their exchanged positions
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SwapVideosResponse {
+    pub videos: Vec<VideoResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FetchVideosResponse {
+    pub found: Vec<VideoResponse>,
+    pub missing: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchDeleteResponse {
+    pub deleted: Vec<i32>,
+    pub not_found: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportRowError {
+    pub line: u64,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportValidationResponse {
+    pub valid_rows: u64,
+    pub errors: Vec<ImportRowError>,
+}
+
+/// Outcome of a JSON import
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportResponse {
+    pub inserted: usize,
+    pub updated: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RelatedQuery {
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelatedVideosResponse {
+    pub videos: Vec<VideoResponse>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GetVideoQuery {
+    /// Also find a soft-deleted video; requires the admin token
+    pub include_deleted: Option<bool>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DeleteQuery {
+    /// Remove the row instead of soft-deleting it
+    pub permanent: Option<bool>,
+    /// Answer a soft delete with `200` and the deleted video instead of `204`
+    #[serde(rename = "return")]
+    pub return_video: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PerPageBounds {
+    pub min: u64,
+    pub max: u64,
+    pub default: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PaginationMeta {
+    pub per_page: PerPageBounds,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetaResponse {
+    pub pagination: PaginationMeta,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ReindexQuery {
+    /// Resume after this id, as returned in a previous `next_cursor`
+    pub after_id: Option<i32>,
+    /// Stop after this many batches; the whole table is swept when unset
+    pub max_batches: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReindexResponse {
+    pub processed: u64,
+    pub updated: u64,
+    pub next_cursor: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReindexDbResponse {
+    /// Indexes that were missing and have just been created
+    pub created: Vec<String>,
+    /// Every expected index, all of which now exist
+    pub present: Vec<String>,
+    /// Whether planner statistics were refreshed with `ANALYZE`
+    pub analyzed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ResetResponse {
+    /// Videos removed, soft-deleted ones included; tags are cleared too
+    pub removed: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ViewCountResponse {
+    pub id: i32,
+    pub view_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentOpsResponse {
+    pub ops: Vec<RecentOp>,
+}
+
+/// Overview of the stored videos; every field but `deleted` ignores soft-deleted ones
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoStatsResponse {
+    pub total: u64,
+    pub deleted: u64,
+    /// Created since midnight UTC
+    pub created_today: u64,
+    pub newest_id: Option<i32>,
+    pub oldest_created_at: Option<DateTime<Utc>>,
+}
+
+/// Videos sharing one YouTube ID
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub youtube_id: String,
+    pub ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicatesResponse {
+    pub groups: Vec<DuplicateGroup>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+}
+
+/// Self-description served at `/` to clients asking for JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexResponse {
+    pub name: String,
+    pub version: String,
+    /// Routes as `METHOD /path`
+    pub endpoints: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    pub version: String,
+    pub git_sha: String,
 }
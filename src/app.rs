@@ -1,15 +1,36 @@
-use ntex::web;
+use ntex::web::{self, types::JsonConfig};
 
-use crate::api::{
-    index, create_video, list_videos, get_video,
-    update_video, delete_video,
-};
+use crate::config::Config;
+use crate::controllers::system_controller;
+use crate::controllers::video_controller::{self, VideoController};
 
-pub fn config_app(cfg: &mut web::ServiceConfig) {
-    cfg.service(index)
-        .service(list_videos)
-        .service(create_video)
-        .service(get_video)
-        .service(update_video)
-        .service(delete_video);
+/// Configures every route of the application
+/// 
+/// The system routes need the `DatabaseConnection` and `Metrics` in app
+/// state; the video routes are mounted under `config.api_base_path` and
+/// accept JSON bodies of up to `config.max_json_bytes`.
+/// 
+/// # Example
+/// 
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::app::config_app;
+/// use ntex_api::config::Config;
+/// use ntex_api::controllers::video_controller::VideoController;
+/// use ntex_api::repositories::video_repository::VideoRepository;
+/// use ntex_api::services::video_service::VideoService;
+/// use sea_orm::DatabaseConnection;
+/// 
+/// fn app(db: DatabaseConnection) {
+///     let config = Config::default();
+///     let controller = VideoController::new(VideoService::new(VideoRepository::new(db.clone())));
+///     let app = web::App::new()
+///         .state(db)
+///         .configure(|cfg| config_app(cfg, controller, &config));
+/// }
+/// ```
+pub fn config_app(cfg: &mut web::ServiceConfig, controller: VideoController, config: &Config) {
+    cfg.state(JsonConfig::default().limit(config.max_json_bytes));
+    system_controller::config(cfg, &config.api_base_path);
+    video_controller::config(cfg, controller, &config.api_base_path);
 }
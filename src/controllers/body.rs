@@ -0,0 +1,113 @@
+use ntex::http::Payload;
+use ntex::web::types::Json;
+use ntex::web::{ErrorRenderer, FromRequest, HttpRequest};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::{AppError, AppResult};
+
+/// JSON request body extractor whose failures render as `AppError`
+///
+/// Reads the body like `Json`, honouring the `JsonConfig` in app state,
+/// but failures get the usual JSON error body instead of ntex's plain
+/// text: `413` for a body over the size limit, `400` with
+/// `invalid JSON body: ...` for one that doesn't parse.
+#[derive(Debug)]
+pub struct JsonBody<T>(pub T);
+
+impl<T> JsonBody<T> {
+    /// Returns the parsed body
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, Err> FromRequest<Err> for JsonBody<T>
+where
+    T: DeserializeOwned + 'static,
+    Err: ErrorRenderer,
+{
+    type Error = AppError;
+
+    async fn from_request(req: &HttpRequest, payload: &mut Payload) -> Result<Self, Self::Error> {
+        <Json<T> as FromRequest<Err>>::from_request(req, payload)
+            .await
+            .map(|json| JsonBody(json.into_inner()))
+            .map_err(AppError::from)
+    }
+}
+
+/// Deserializes a JSON request body into `T`
+///
+/// In strict mode any top-level field `T` doesn't know about is rejected
+/// (for array bodies, any such field on one of the items)
+/// instead of being silently dropped. Unknown fields are found by
+/// serializing the parsed value back and comparing keys, so `T` must
+/// serialize every field it accepts (optional fields included).
+///
+/// # Errors
+/// * `AppError::BadRequest` - If the body doesn't match `T`, or has unknown fields in strict mode
+pub fn parse<T: DeserializeOwned + Serialize>(body: Value, strict: bool) -> AppResult<T> {
+    let parsed: T = serde_json::from_value(body.clone())
+        .map_err(|e| AppError::BadRequest(format!("invalid JSON body: {}", e)))?;
+
+    if strict {
+        if let Ok(known) = serde_json::to_value(&parsed) {
+            if let Some(field) = unknown_field(&body, &known) {
+                return Err(AppError::BadRequest(format!("unknown field `{}`", field)));
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Finds a top-level field of `given` missing from `known`, looking into each item of an array body
+fn unknown_field<'a>(given: &'a Value, known: &Value) -> Option<&'a str> {
+    match (given, known) {
+        (Value::Object(given), Value::Object(known)) => given
+            .keys()
+            .find(|key| !known.contains_key(*key))
+            .map(String::as_str),
+        (Value::Array(given), Value::Array(known)) => given
+            .iter()
+            .zip(known)
+            .find_map(|(given, known)| unknown_field(given, known)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::UpdateVideoRequest;
+    use serde_json::json;
+
+    #[test]
+    fn test_strict_rejects_unknown_field() {
+        let body = json!({ "title": "x", "admin": true });
+        match parse::<UpdateVideoRequest>(body.clone(), true) {
+            Err(AppError::BadRequest(message)) => assert_eq!(message, "unknown field `admin`"),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+
+        let lenient: UpdateVideoRequest = parse(body, false).unwrap();
+        assert_eq!(lenient.title.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_strict_accepts_omitted_optional_fields() {
+        let parsed: UpdateVideoRequest = parse(json!({ "title": "x" }), true).unwrap();
+        assert_eq!(parsed.youtube_id, None);
+    }
+
+    #[test]
+    fn test_strict_checks_each_array_item() {
+        let body = json!([{ "title": "x" }, { "title": "y", "admin": true }]);
+        match parse::<Vec<UpdateVideoRequest>>(body, true) {
+            Err(AppError::BadRequest(message)) => assert_eq!(message, "unknown field `admin`"),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+use crate::config::Config;
+
+/// Restricts the video fields exposed on the public API
+///
+/// Applied to responses under `/api/v1/videos` when `public_video_fields`
+/// is configured; admin endpoints always return every field. The `id` field
+/// is always kept so clients can address the resource.
+#[derive(Clone, Debug)]
+pub struct FieldAllowlist {
+    fields: BTreeSet<String>,
+}
+
+impl FieldAllowlist {
+    /// Builds the allowlist described by `config`, if any
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.public_video_fields.as_ref().map(|fields| Self {
+            fields: fields.iter().cloned().chain(["id".to_string()]).collect(),
+        })
+    }
+
+    /// Drops fields outside the allowlist from every video in `value`
+    ///
+    /// Handles both a single video object and envelopes carrying a `videos`
+    /// or `found` array; the envelope's own fields are left alone.
+    pub fn apply(&self, value: &mut Value) {
+        let mut is_envelope = false;
+        for key in ["videos", "found"] {
+            if let Some(videos) = value.get_mut(key).and_then(Value::as_array_mut) {
+                is_envelope = true;
+                for video in videos {
+                    self.retain(video);
+                }
+            }
+        }
+        if !is_envelope {
+            self.retain(value);
+        }
+    }
+
+    fn retain(&self, video: &mut Value) {
+        if let Some(object) = video.as_object_mut() {
+            let kept: Map<String, Value> = std::mem::take(object)
+                .into_iter()
+                .filter(|(key, _)| self.fields.contains(key))
+                .collect();
+            *object = kept;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn allowlist(fields: &[&str]) -> FieldAllowlist {
+        FieldAllowlist::from_config(&Config {
+            public_video_fields: Some(fields.iter().map(|f| f.to_string()).collect()),
+            ..Config::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(FieldAllowlist::from_config(&Config::default()).is_none());
+    }
+
+    #[test]
+    fn test_strips_video_fields_but_keeps_id_and_envelope() {
+        let mut page = json!({
+            "videos": [{ "id": 1, "title": "A", "deleted_at": null }],
+            "total": 1,
+        });
+        allowlist(&["title"]).apply(&mut page);
+        assert_eq!(page, json!({ "videos": [{ "id": 1, "title": "A" }], "total": 1 }));
+    }
+}
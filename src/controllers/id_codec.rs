@@ -0,0 +1,195 @@
+use harsh::Harsh;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::error::{AppError, AppResult};
+
+/// Translates between internal integer ids and their public representation
+///
+/// In `Raw` mode (the default) ids are exposed as plain integers. When a
+/// hashid salt is configured, ids are encoded into short, non-sequential
+/// strings in responses and decoded again from the request path.
+#[derive(Clone)]
+pub enum IdCodec {
+    Raw,
+    Hashids(Harsh),
+}
+
+impl IdCodec {
+    /// Builds the codec described by `config`
+    ///
+    /// Falls back to `Raw` mode when no salt is configured or the hashids
+    /// alphabet cannot be built from the given settings.
+    pub fn from_config(config: &Config) -> Self {
+        let Some(salt) = config.hashid_salt.as_deref() else {
+            return IdCodec::Raw;
+        };
+
+        match Harsh::builder()
+            .salt(salt)
+            .length(config.hashid_min_length)
+            .build()
+        {
+            Ok(harsh) => IdCodec::Hashids(harsh),
+            Err(e) => {
+                tracing::error!("Invalid hashid configuration, using raw ids: {}", e);
+                IdCodec::Raw
+            }
+        }
+    }
+
+    /// Returns true when ids are exposed as plain integers
+    pub fn is_raw(&self) -> bool {
+        matches!(self, IdCodec::Raw)
+    }
+
+    /// Encodes an internal id into its public form
+    pub fn encode(&self, id: i32) -> Value {
+        match self {
+            IdCodec::Raw => Value::from(id),
+            IdCodec::Hashids(harsh) => Value::from(harsh.encode(&[id as u64])),
+        }
+    }
+
+    /// Decodes a public id taken from the request path
+    ///
+    /// # Errors
+    /// * `AppError::BadRequest` - If the value is not a valid id for the active mode
+    pub fn decode(&self, raw: &str) -> AppResult<i32> {
+        match self {
+            IdCodec::Raw => raw
+                .parse::<i32>()
+                .map_err(|_| AppError::BadRequest("invalid id".to_string())),
+            IdCodec::Hashids(harsh) => match harsh.decode(raw).as_deref() {
+                Ok([id]) => i32::try_from(*id)
+                    .map_err(|_| AppError::BadRequest("invalid id".to_string())),
+                _ => Err(AppError::BadRequest("invalid id".to_string())),
+            },
+        }
+    }
+
+    /// Decodes a public id taken from a JSON request body
+    ///
+    /// Accepts an integer in raw mode and a hashid string otherwise, like
+    /// `decode` does for the same id written as text.
+    ///
+    /// # Errors
+    /// * `AppError::BadRequest` - If the value is not a valid id for the active mode
+    pub fn decode_value(&self, id: &Value) -> AppResult<i32> {
+        match id {
+            Value::String(raw) => self.decode(raw),
+            Value::Number(raw) => self.decode(&raw.to_string()),
+            _ => Err(AppError::BadRequest("invalid id".to_string())),
+        }
+    }
+
+    /// Rewrites an integer id, or an array of them, into public form in place
+    pub fn encode_ids_in(&self, ids: &mut Value) {
+        match ids {
+            Value::Array(items) => items.iter_mut().for_each(|id| self.encode_ids_in(id)),
+            id => {
+                if let Some(raw) = id.as_i64().and_then(|raw| i32::try_from(raw).ok()) {
+                    *id = self.encode(raw);
+                }
+            }
+        }
+    }
+
+    /// Serializes `body` and rewrites video ids into their public form
+    ///
+    /// Handles both a single video object and envelopes carrying a `videos`
+    /// or `found` array.
+    ///
+    /// # Errors
+    /// * `AppError::Internal` - If the body cannot be represented as JSON
+    pub fn encode_body<T: Serialize>(&self, body: &T) -> AppResult<Value> {
+        let mut value = serde_json::to_value(body)
+            .map_err(|e| AppError::Internal(format!("Failed to encode response: {}", e)))?;
+
+        let mut is_envelope = false;
+        for key in ["videos", "found", "ops"] {
+            if let Some(videos) = value.get_mut(key).and_then(Value::as_array_mut) {
+                is_envelope = true;
+                for video in videos {
+                    self.encode_id_field(video);
+                }
+            }
+        }
+        if !is_envelope {
+            self.encode_id_field(&mut value);
+        }
+
+        Ok(value)
+    }
+
+    fn encode_id_field(&self, object: &mut Value) {
+        if let Some(id) = object.get_mut("id") {
+            if let Some(raw) = id.as_i64().and_then(|raw| i32::try_from(raw).ok()) {
+                *id = self.encode(raw);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashid_codec() -> IdCodec {
+        IdCodec::from_config(&Config {
+            hashid_salt: Some("test salt".to_string()),
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn test_raw_mode_is_default() {
+        let codec = IdCodec::from_config(&Config::default());
+        assert!(codec.is_raw());
+        assert_eq!(codec.encode(42), Value::from(42));
+        assert_eq!(codec.decode("42").unwrap(), 42);
+    }
+
+    #[test]
+    fn test_hashid_round_trip() {
+        let codec = hashid_codec();
+        for id in [1, 42, 123_456, i32::MAX] {
+            let encoded = codec.encode(id);
+            let encoded = encoded.as_str().unwrap();
+            assert!(encoded.len() >= Config::default().hashid_min_length);
+            assert_ne!(encoded, id.to_string());
+            assert_eq!(codec.decode(encoded).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn test_invalid_hashid_is_rejected() {
+        let codec = hashid_codec();
+        assert!(matches!(codec.decode("not-a-hashid!"), Err(AppError::BadRequest(_))));
+        assert!(matches!(codec.decode("42"), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_body_ids_round_trip() {
+        let codec = hashid_codec();
+        let mut ids = serde_json::json!([3, 9]);
+        codec.encode_ids_in(&mut ids);
+        assert_eq!(ids, serde_json::json!([codec.encode(3), codec.encode(9)]));
+        assert_eq!(codec.decode_value(&ids[1]).unwrap(), 9);
+        assert!(matches!(codec.decode_value(&Value::from(9)), Err(AppError::BadRequest(_))));
+
+        let raw = IdCodec::Raw;
+        assert_eq!(raw.decode_value(&Value::from(9)).unwrap(), 9);
+        assert!(matches!(raw.decode_value(&Value::Null), Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_encode_body_rewrites_list_ids() {
+        let codec = hashid_codec();
+        let body = serde_json::json!({ "videos": [{ "id": 7 }], "total": 1 });
+        let encoded = codec.encode_body(&body).unwrap();
+        assert_eq!(encoded["videos"][0]["id"], codec.encode(7));
+        assert_eq!(encoded["total"], 1);
+    }
+}
@@ -1 +1,8 @@
+pub mod body;
+pub mod field_allowlist;
+pub mod id_codec;
+pub mod paging;
+pub mod negotiation;
+pub mod streaming;
+pub mod system_controller;
 pub mod video_controller;
@@ -0,0 +1,65 @@
+use ntex::http::header;
+use ntex::web::{HttpRequest, HttpResponse, HttpResponseBuilder};
+use serde::Serialize;
+
+use crate::error::{AppError, AppResult};
+
+/// Media type used for MessagePack encoded responses
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Returns true when the client's `Accept` header asks for MessagePack
+///
+/// Both `application/msgpack` and the legacy `application/x-msgpack` are
+/// recognised; anything else (including a missing header) selects JSON.
+pub fn wants_msgpack(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(|accept| {
+            accept.split(',').any(|part| {
+                let media_type = part.split(';').next().unwrap_or("").trim();
+                media_type.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE)
+                    || media_type.eq_ignore_ascii_case("application/x-msgpack")
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Returns true when the client's `Accept` header lists `application/json`
+pub fn wants_json(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(|accept| {
+            accept.split(',').any(|part| {
+                let media_type = part.split(';').next().unwrap_or("").trim();
+                media_type.eq_ignore_ascii_case("application/json")
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Serializes `body` as MessagePack or JSON depending on the request's `Accept` header
+///
+/// # Arguments
+/// * `req` - The incoming request used for negotiation
+/// * `builder` - Response builder carrying the status and any extra headers
+/// * `body` - The payload to serialize
+///
+/// # Errors
+/// * `AppError::Internal` - If MessagePack encoding fails
+pub fn respond<T: Serialize>(
+    req: &HttpRequest,
+    mut builder: HttpResponseBuilder,
+    body: &T,
+) -> AppResult<HttpResponse> {
+    builder.header(header::VARY, "Accept");
+
+    if wants_msgpack(req) {
+        let bytes = rmp_serde::to_vec_named(body)
+            .map_err(|e| AppError::Internal(format!("MessagePack encoding failed: {}", e)))?;
+        Ok(builder.content_type(MSGPACK_CONTENT_TYPE).body(bytes))
+    } else {
+        Ok(builder.json(body))
+    }
+}
@@ -0,0 +1,82 @@
+use ntex::web::HttpRequest;
+
+use crate::api::PaginatedVideoResponse;
+
+/// Builds an RFC 5988 `Link` header value for page-numbered lists
+///
+/// Each link repeats the request's URL and query with only `page`
+/// replaced. `first` and `last` are always present; `prev` and `next` are
+/// left out on the first and last page. Returns `None` when there are no
+/// pages.
+pub fn links(req: &HttpRequest, page: u64, total_pages: u64) -> Option<String> {
+    if total_pages == 0 {
+        return None;
+    }
+
+    let info = req.connection_info();
+    let base = format!("{}://{}{}", info.scheme(), info.host(), req.path());
+    let kept: Vec<&str> = req
+        .query_string()
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some("page"))
+        .collect();
+    let url = |page: u64| {
+        let mut query = kept.clone();
+        let page = format!("page={}", page);
+        query.push(&page);
+        format!("<{}?{}>", base, query.join("&"))
+    };
+
+    let mut links = Vec::with_capacity(4);
+    if page > 1 {
+        links.push(format!("{}; rel=\"prev\"", url((page - 1).min(total_pages))));
+    }
+    if page < total_pages {
+        links.push(format!("{}; rel=\"next\"", url(page + 1)));
+    }
+    links.push(format!("{}; rel=\"first\"", url(1)));
+    links.push(format!("{}; rel=\"last\"", url(total_pages)));
+    Some(links.join(", "))
+}
+
+/// Builds a `Content-Range` value such as `videos 0-9/42` for a page
+///
+/// Offsets are 0-based and inclusive; a page without videos is written as
+/// `videos */42`.
+pub fn content_range(page: &PaginatedVideoResponse) -> String {
+    if page.videos.is_empty() {
+        return format!("videos */{}", page.total);
+    }
+    let start = page.page.saturating_sub(1) * page.per_page;
+    let end = start + page.videos.len() as u64 - 1;
+    format!("videos {}-{}/{}", start, end, page.total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::test::TestRequest;
+
+    fn link_header(uri: &str, page: u64, total_pages: u64) -> Option<String> {
+        let req = TestRequest::get().uri(uri).header("host", "example.com").to_http_request();
+        links(&req, page, total_pages)
+    }
+
+    #[test]
+    fn test_boundaries_omit_prev_and_next() {
+        assert_eq!(
+            link_header("/api/v1/videos", 1, 2).unwrap(),
+            "<http://example.com/api/v1/videos?page=2>; rel=\"next\", \
+             <http://example.com/api/v1/videos?page=1>; rel=\"first\", \
+             <http://example.com/api/v1/videos?page=2>; rel=\"last\""
+        );
+        assert!(!link_header("/api/v1/videos?page=2", 2, 2).unwrap().contains("rel=\"next\""));
+        assert_eq!(link_header("/api/v1/videos", 1, 0), None);
+    }
+
+    #[test]
+    fn test_other_query_parameters_are_kept() {
+        let value = link_header("/api/v1/videos?search=cats&page=3&per_page=5", 3, 4).unwrap();
+        assert!(value.contains("<http://example.com/api/v1/videos?search=cats&per_page=5&page=2>; rel=\"prev\""));
+    }
+}
@@ -0,0 +1,97 @@
+use futures::stream;
+use ntex::http::header::{self, ContentEncoding};
+use ntex::time::{interval, Millis};
+use ntex::util::Bytes;
+use ntex::web::{BodyEncoding, HttpResponse, HttpResponseBuilder};
+use serde_json::Value;
+use std::convert::Infallible;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Sends a JSON envelope as a chunked stream instead of one buffered body
+///
+/// The envelope is written as `{"<array_key>":[` followed by one chunk per
+/// item and finally the remaining envelope fields, so clients parse the
+/// same document a buffered response would carry. The response has no
+/// `Content-Length`.
+/// Bodies that aren't objects, or lack an `array_key` array, are sent whole
+/// as a single chunk.
+pub fn json_envelope(mut builder: HttpResponseBuilder, mut body: Value, array_key: &str) -> HttpResponse {
+    let items = match body.as_object_mut().and_then(|object| object.remove(array_key)) {
+        Some(Value::Array(items)) => items,
+        Some(other) => {
+            body[array_key] = other;
+            Vec::new()
+        }
+        None => Vec::new(),
+    };
+
+    let head = Bytes::from(format!("{{{}:[", Value::from(array_key)));
+    let tail = match body {
+        Value::Object(rest) => {
+            let mut tail = String::from("]");
+            for (key, value) in rest {
+                tail.push_str(&format!(",{}:{}", Value::from(key), value));
+            }
+            tail.push('}');
+            tail
+        }
+        _ => "]}".to_string(),
+    };
+
+    let chunks = std::iter::once(head)
+        .chain(items.into_iter().enumerate().map(|(index, item)| {
+            let separator = if index == 0 { "" } else { "," };
+            Bytes::from(format!("{}{}", separator, item))
+        }))
+        .chain(std::iter::once(Bytes::from(tail)))
+        .map(Ok::<_, Infallible>);
+
+    builder
+        .content_type("application/json")
+        .streaming(stream::iter(chunks))
+}
+
+/// Sends messages from a broadcast channel as a Server-Sent Events stream
+///
+/// Each message `encode` turns into text is written as one `data:` frame; a
+/// `: keep-alive` comment goes out whenever `keep_alive` passes without one,
+/// so proxies don't close an idle connection. A subscriber that falls behind
+/// skips the messages it missed. The stream ends when the channel closes.
+/// It is never compressed, since an encoder would hold frames back.
+pub fn event_stream<T, F>(
+    mut builder: HttpResponseBuilder,
+    events: broadcast::Receiver<T>,
+    keep_alive: Millis,
+    encode: F,
+) -> HttpResponse
+where
+    T: Clone + 'static,
+    F: Fn(&T) -> Option<String> + 'static,
+{
+    let state = (events, interval(keep_alive), encode);
+    let frames = stream::unfold(state, |(mut events, ticks, encode)| async move {
+        loop {
+            let frame = tokio::select! {
+                _ = ticks.tick() => ": keep-alive\n\n".to_string(),
+                event = events.recv() => match event {
+                    Ok(event) => match encode(&event) {
+                        Some(data) => format!("data: {}\n\n", data),
+                        None => continue,
+                    },
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Event stream subscriber skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+            };
+            return Some((Ok::<_, Infallible>(Bytes::from(frame)), (events, ticks, encode)));
+        }
+    });
+
+    builder
+        .content_type("text/event-stream")
+        .header(header::CACHE_CONTROL, "no-cache")
+        .encoding(ContentEncoding::Identity)
+        .streaming(Box::pin(frames))
+}
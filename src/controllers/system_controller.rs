@@ -0,0 +1,222 @@
+use ntex::http::header;
+use ntex::web::{self, types::State, HttpRequest, HttpResponse, Responder};
+use sea_orm::DatabaseConnection;
+
+use crate::api::{HealthResponse, IndexResponse, VersionResponse};
+use crate::controllers::{negotiation, video_controller};
+use crate::middleware::metrics::{Metrics, METRICS_PATH};
+use crate::middleware::pre_ping::LIVENESS_PATH;
+use crate::openapi::{ApiDoc, OPENAPI_PATH};
+use utoipa::OpenApi;
+
+/// Git sha the binary was built from, or `"unknown"` when unavailable
+pub const GIT_SHA: &str = match option_env!("GIT_SHA") {
+    Some(sha) => sha,
+    None => "unknown",
+};
+
+/// Version of the REST API reported by the index
+pub const API_VERSION: &str = "1";
+
+/// Builds the index description of the routes, the video ones under `base_path`
+pub fn api_index(base_path: &str) -> IndexResponse {
+    let system = [
+        "GET /".to_string(),
+        "GET /version".to_string(),
+        format!("GET {}", LIVENESS_PATH),
+        "GET /health/db".to_string(),
+        format!("GET {}", OPENAPI_PATH),
+        format!("GET {}", METRICS_PATH),
+    ];
+    let videos = video_controller::ROUTES.iter().map(|route| {
+        let (method, path) = route.split_once(' ').unwrap_or(("GET", route));
+        format!("{} {}{}", method, base_path, path)
+    });
+
+    IndexResponse {
+        name: "Video API".to_string(),
+        version: API_VERSION.to_string(),
+        endpoints: system.into_iter().chain(videos).collect(),
+    }
+}
+
+/// Greets visitors at the root path, or describes the API to JSON clients
+/// 
+/// Requests accepting `application/json` get the route list built by
+/// `api_index`; everyone else gets the plain-text greeting.
+/// 
+/// # Example
+/// 
+/// ```text
+/// GET /
+/// Accept: application/json
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 200 OK
+/// Content-Type: application/json
+/// 
+/// { "name": "Video API", "version": "1", "endpoints": ["GET /", "GET /version", ...] }
+/// ```
+pub async fn index(req: HttpRequest, api: State<IndexResponse>) -> impl Responder {
+    let mut builder = HttpResponse::Ok();
+    builder.header(header::VARY, "Accept");
+    if negotiation::wants_json(&req) {
+        builder.json(api.get_ref())
+    } else {
+        builder.content_type("text/plain").body("Welcome to Video API!")
+    }
+}
+
+/// Reports the crate version and the git sha the binary was built from
+/// 
+/// # Example
+/// 
+/// ```text
+/// GET /version
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 200 OK
+/// Content-Type: application/json
+/// 
+/// { "version": "0.1.0", "git_sha": "579ff9c" }
+/// ```
+pub async fn version() -> impl Responder {
+    HttpResponse::Ok().json(&VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: GIT_SHA.to_string(),
+    })
+}
+
+/// Liveness probe: answers as long as the process serves requests
+/// 
+/// Never touches the database, so it succeeds before migrations have run
+/// and while the database is down.
+/// 
+/// # Example
+/// 
+/// ```text
+/// GET /health
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 200 OK
+/// Content-Type: application/json
+/// 
+/// { "status": "ok" }
+/// ```
+pub async fn health() -> impl Responder {
+    HttpResponse::Ok().json(&HealthResponse {
+        status: "ok".to_string(),
+    })
+}
+
+/// Readiness probe: pings the database
+/// 
+/// Answers `503` with `{ "status": "db_unavailable" }` when the ping fails.
+/// 
+/// # Example
+/// 
+/// ```text
+/// GET /health/db
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 200 OK
+/// Content-Type: application/json
+/// 
+/// { "status": "ok" }
+/// ```
+pub async fn health_db(db: State<DatabaseConnection>) -> impl Responder {
+    match db.ping().await {
+        Ok(()) => HttpResponse::Ok().json(&HealthResponse {
+            status: "ok".to_string(),
+        }),
+        Err(e) => {
+            tracing::error!("Database health check failed: {}", e);
+            HttpResponse::ServiceUnavailable().json(&HealthResponse {
+                status: "db_unavailable".to_string(),
+            })
+        }
+    }
+}
+
+/// Serves the OpenAPI 3.0 document describing the video routes
+/// 
+/// # Example
+/// 
+/// ```text
+/// GET /api/v1/openapi.json
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 200 OK
+/// Content-Type: application/json
+/// 
+/// { "openapi": "3.0.3", "paths": { "/api/v1/videos": { ... } }, ... }
+/// ```
+pub async fn openapi() -> impl Responder {
+    HttpResponse::Ok().json(&ApiDoc::openapi())
+}
+
+/// Serves the collected metrics in the Prometheus text format
+/// 
+/// # Example
+/// 
+/// ```text
+/// GET /metrics
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 200 OK
+/// Content-Type: text/plain; version=0.0.4
+/// 
+/// http_requests_total{method="GET",route="/api/v1/videos",status="200"} 3
+/// ```
+pub async fn metrics(metrics: State<Metrics>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics.render())
+}
+
+/// Registers the `/metrics` scrape route
+/// 
+/// Needs the `Metrics` in app state.
+pub fn metrics_config(cfg: &mut web::ServiceConfig) {
+    cfg.route(METRICS_PATH, web::get().to(metrics));
+}
+
+/// Registers the `/api/v1/openapi.json` document route
+pub fn openapi_config(cfg: &mut web::ServiceConfig) {
+    cfg.route(OPENAPI_PATH, web::get().to(openapi));
+}
+
+/// Registers the `/health` liveness and `/health/db` readiness probes
+/// 
+/// `/health/db` needs the `DatabaseConnection` in app state.
+pub fn health_config(cfg: &mut web::ServiceConfig) {
+    cfg.route(LIVENESS_PATH, web::get().to(health))
+        .route("/health/db", web::get().to(health_db));
+}
+
+/// Configures the service-level routes that don't belong to a resource
+/// 
+/// `base_path` is where the video routes are mounted, as listed by the index.
+/// 
+/// # Example
+/// 
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::controllers::system_controller;
+/// 
+/// let app = web::App::new().configure(|cfg| system_controller::config(cfg, "/api/v1"));
+/// ```
+pub fn config(cfg: &mut web::ServiceConfig, base_path: &str) {
+    cfg.state(api_index(base_path))
+        .route("/", web::get().to(index))
+        .route("/version", web::get().to(version));
+    health_config(cfg);
+    openapi_config(cfg);
+    metrics_config(cfg);
+}
@@ -1,10 +1,217 @@
-use ntex::web::{self, types::{Json, Path, Query}, HttpResponse, Responder};
-use crate::services::video_service::VideoService;
-use crate::api::{CreateVideoRequest, UpdateVideoRequest};
+use ntex::http::{header, Method};
+use ntex::time::Millis;
+use ntex::util::Bytes;
+use ntex::web::{self, types::{Json, Path, Query}, HttpRequest, HttpResponse, Responder};
+use crate::controllers::body::{self, JsonBody};
+use crate::controllers::field_allowlist::FieldAllowlist;
+use crate::controllers::id_codec::IdCodec;
+use crate::controllers::paging;
+use crate::controllers::negotiation;
+use crate::controllers::streaming;
+use crate::services::events::VideoEvent;
+use crate::services::idempotency::{Claim, IdempotencyKeys};
+use crate::services::quota::CreateQuota;
+use crate::services::video_service::{VideoService, EXPORT_PAGE_SIZE};
+use crate::api::{
+    CreateVideoRequest, DeleteQuery, FetchVideosRequest, GetVideoQuery, MoveVideoRequest, MetaResponse, PaginationMeta, PerPageBounds,
+    PublicFetchVideosRequest, RecentOpsResponse, SwapVideosRequest, ReindexQuery, RelatedQuery, ReplaceVideoRequest, UpdateVideoRequest, VideoObject, VideoResponse,
+};
 use crate::db::VideoQuery;
-use crate::error::AppResult;
+use crate::error::{AppError, AppResult};
+use chrono::{DateTime, Utc};
+use futures::{stream, StreamExt};
+use ntex::service::{fn_factory_with_config, fn_service};
+use tokio::sync::broadcast::error::RecvError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::net::IpAddr;
 use std::sync::Arc;
 
+/// Methods supported on the video collection (`/api/v1/videos`)
+pub const COLLECTION_METHODS: &str = "GET, POST, OPTIONS";
+
+/// Methods supported on a single video (`/api/v1/videos/{id}`)
+pub const ITEM_METHODS: &str = "GET, PUT, PATCH, DELETE, OPTIONS";
+
+/// Public routes registered by `config`, relative to the base path
+/// 
+/// Listed by the API index; the admin routes are left out.
+pub const ROUTES: [&str; 29] = [
+    "GET /meta",
+    "GET /videos",
+    "POST /videos",
+    "POST /videos/fetch",
+    "POST /videos/swap",
+    "POST /videos/batch",
+    "DELETE /videos/batch",
+    "GET /videos/by-youtube/{youtube_id}",
+    "GET /videos/random",
+    "GET /videos/stats",
+    "GET /videos/events",
+    "GET /videos/stream",
+    "GET /videos/duplicates",
+    "GET /videos/trash",
+    "GET /videos/export.csv",
+    "POST /videos/import",
+    "POST /videos/import/validate",
+    "GET /videos/{id}",
+    "PUT /videos/{id}",
+    "PATCH /videos/{id}",
+    "DELETE /videos/{id}",
+    "POST /videos/{id}/restore",
+    "POST /videos/{id}/publish",
+    "PATCH /videos/{id}/position",
+    "POST /videos/{id}/view",
+    "GET /videos/{id}/json-ld",
+    "GET /videos/{id}/related",
+    "OPTIONS /videos",
+    "OPTIONS /videos/{id}",
+];
+
+/// Media type of JSON-LD documents
+pub const JSON_LD_CONTENT_TYPE: &str = "application/ld+json";
+
+/// Response header carrying the total number of videos matching a list query
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Media type of CSV exports
+pub const CSV_CONTENT_TYPE: &str = "text/csv";
+
+/// Silence after which an event stream sends a keep-alive comment
+pub const SSE_KEEP_ALIVE: Millis = Millis(15_000);
+
+/// Request header asking a mutating endpoint to roll back instead of committing
+pub const DRY_RUN_HEADER: &str = "x-dry-run";
+
+/// Request header making a create safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header marking a create answered from an earlier request with the same key
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Returns the request's non-empty `Idempotency-Key`, if any
+fn idempotency_key(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Request header setting the page size of list requests that don't pass `per_page`
+pub const PAGE_SIZE_HEADER: &str = "x-page-size";
+
+/// Fills in a missing `per_page` from the request's `X-Page-Size` header
+/// 
+/// An explicit `per_page` wins; without either, the service falls back to
+/// the configured default. The value is clamped like `per_page`.
+/// 
+/// # Errors
+/// * `AppError::BadRequest` - If the header isn't a non-negative integer
+fn apply_page_size(query: &mut VideoQuery, req: &HttpRequest) -> AppResult<()> {
+    if query.per_page.is_some() {
+        return Ok(());
+    }
+    if let Some(value) = req.headers().get(PAGE_SIZE_HEADER) {
+        let size = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .ok_or_else(|| AppError::BadRequest("X-Page-Size must be a non-negative integer".to_string()))?;
+        query.per_page = Some(size);
+    }
+    Ok(())
+}
+
+/// Returns true when the request carries `X-Dry-Run: true`
+fn is_dry_run(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(DRY_RUN_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Resolves the client's IP for the create quota
+/// 
+/// This is the connection's peer address. Only when that peer is one of
+/// `trusted_proxies` is `X-Forwarded-For` consulted, taking the nearest
+/// address that isn't itself a trusted proxy, since anything further left
+/// was written by the client.
+fn client_ip(req: &HttpRequest, trusted_proxies: &[IpAddr]) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+    if !trusted_proxies.contains(&peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = req.headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|hop| hop.trim().parse().ok())
+        .collect();
+    forwarded
+        .iter()
+        .rev()
+        .find(|hop| !trusted_proxies.contains(hop))
+        .or(forwarded.first())
+        .copied()
+        .or(Some(peer))
+}
+
+/// Returns true when the request's `If-None-Match` header matches `etag`
+fn if_none_match(req: &HttpRequest, etag: &str) -> bool {
+    req.headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value.split(',').map(str::trim).any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+        })
+}
+
+/// Reads the version a write is conditioned on from the request's `If-Match`
+/// 
+/// Returns the `updated_at` encoded in the first strong tag naming
+/// `public_id`, or `None` for `*`, which matches any current version.
+/// 
+/// # Errors
+/// * `AppError::PreconditionRequired` - If the header is missing
+/// * `AppError::PreconditionFailed` - If no tag can match this video
+fn if_match(req: &HttpRequest, public_id: &str) -> AppResult<Option<DateTime<Utc>>> {
+    let value = req
+        .headers()
+        .get(header::IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AppError::PreconditionRequired("If-Match header is required".to_string()))?;
+    if value.trim() == "*" {
+        return Ok(None);
+    }
+
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.starts_with("W/"))
+        .filter_map(|tag| tag.trim_matches('"').rsplit_once('-'))
+        .filter(|(id, _)| *id == public_id)
+        .find_map(|(_, micros)| micros.parse().ok().and_then(DateTime::from_timestamp_micros))
+        .map(Some)
+        .ok_or_else(|| AppError::PreconditionFailed("If-Match does not match the current version".to_string()))
+}
+
+/// Serializes a response body so ids in it can be rewritten into public form
+fn to_json<T: Serialize>(body: &T) -> AppResult<Value> {
+    serde_json::to_value(body)
+        .map_err(|e| AppError::Internal(format!("Failed to encode response: {}", e)))
+}
+
+/// Marks a response as produced by a rolled-back dry run
+fn mark_dry_run(builder: &mut web::HttpResponseBuilder, dry_run: bool) {
+    if dry_run {
+        builder.header(DRY_RUN_HEADER, "applied");
+    }
+}
+
 /// Video controller that handles HTTP requests for video resources
 /// 
 /// This controller provides a RESTful API for managing video resources.
@@ -36,25 +243,25 @@ use std::sync::Arc;
 ///             
 ///             cfg.service(
 ///                 web::scope("/api/v1/videos")
-///                     .route("", web::post().to(move |req| {
+///                     .route("", web::post().to(move |req, http_req| {
 ///                         let ctrl = std::sync::Arc::clone(&c1);
-///                         async move { ctrl.create_video(req).await }
+///                         async move { ctrl.create_video(req, http_req).await }
 ///                     }))
-///                     .route("", web::get().to(move |query| {
+///                     .route("", web::get().to(move |query, req| {
 ///                         let ctrl = std::sync::Arc::clone(&c2);
-///                         async move { ctrl.list_videos(query).await }
+///                         async move { ctrl.list_videos(query, req).await }
 ///                     }))
-///                     .route("/{id}", web::get().to(move |id| {
+///                     .route("/{id}", web::get().to(move |id, query, req| {
 ///                         let ctrl = std::sync::Arc::clone(&c3);
-///                         async move { ctrl.get_video(id).await }
+///                         async move { ctrl.get_video(id, query, req).await }
 ///                     }))
-///                     .route("/{id}", web::put().to(move |id, req| {
+///                     .route("/{id}", web::put().to(move |id, req, http_req| {
 ///                         let ctrl = std::sync::Arc::clone(&c4);
-///                         async move { ctrl.update_video(id, req).await }
+///                         async move { ctrl.update_video(id, req, http_req).await }
 ///                     }))
-///                     .route("/{id}", web::delete().to(move |id| {
+///                     .route("/{id}", web::delete().to(move |id, query, http_req| {
 ///                         let ctrl = std::sync::Arc::clone(&c5);
-///                         async move { ctrl.delete_video(id).await }
+///                         async move { ctrl.delete_video(id, query, http_req).await }
 ///                     }))
 ///             );
 ///         });
@@ -63,6 +270,10 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct VideoController {
     service: VideoService,
+    ids: IdCodec,
+    public_fields: Option<FieldAllowlist>,
+    create_quota: Option<CreateQuota>,
+    idempotency: IdempotencyKeys,
 }
 
 impl VideoController {
@@ -86,13 +297,284 @@ impl VideoController {
 /// }
 /// ```
     pub fn new(service: VideoService) -> Self {
-        Self { service }
+        let ids = IdCodec::from_config(service.config());
+        let public_fields = FieldAllowlist::from_config(service.config());
+        let create_quota = service.config().daily_create_quota.map(CreateQuota::new);
+        let idempotency = IdempotencyKeys::new(service.config().idempotency_ttl_secs);
+        Self { service, ids, public_fields, create_quota, idempotency }
+    }
+
+    /// Decodes a public id taken from the request path
+    /// 
+    /// # Errors
+    /// * `AppError::BadRequest` - If the value is not a valid id
+    pub fn decode_id(&self, raw: &str) -> AppResult<i32> {
+        self.ids.decode(raw)
+    }
+
+    /// Parses a JSON request body, honouring the `strict_json` setting
+    /// 
+    /// # Errors
+    /// * `AppError::BadRequest` - If the body is malformed or, in strict mode, has unknown fields
+    pub fn body<T: DeserializeOwned + Serialize>(&self, body: JsonBody<Value>) -> AppResult<Json<T>> {
+        body::parse(body.into_inner(), self.service.config().strict_json).map(Json)
+    }
+
+    /// Returns the public form of `id` as it appears in URLs
+    fn public_id(&self, id: i32) -> String {
+        match self.ids.encode(id) {
+            Value::String(id) => id,
+            other => other.to_string(),
+        }
+    }
+
+    /// Builds the entity tag of a video from its public id and last update time
+    fn etag(&self, video: &VideoResponse) -> String {
+        format!("\"{}-{}\"", self.public_id(video.id), video.updated_at.timestamp_micros())
+    }
+
+    /// Serializes a response body for the public API
+    /// 
+    /// Video ids are encoded into their public form and fields outside the
+    /// configured allowlist are dropped.
+    fn respond<T: Serialize>(
+        &self,
+        req: &HttpRequest,
+        builder: web::HttpResponseBuilder,
+        body: &T,
+    ) -> AppResult<HttpResponse> {
+        if self.ids.is_raw() && self.public_fields.is_none() {
+            negotiation::respond(req, builder, body)
+        } else {
+            negotiation::respond(req, builder, &self.public_body(body)?)
+        }
+    }
+
+    /// Serializes a JSON response body for the public API, as `respond` does
+    fn json<T: Serialize>(&self, mut builder: web::HttpResponseBuilder, body: &T) -> AppResult<HttpResponse> {
+        if self.ids.is_raw() && self.public_fields.is_none() {
+            Ok(builder.json(body))
+        } else {
+            Ok(builder.json(&self.public_body(body)?))
+        }
+    }
+
+    /// Serializes a JSON response body for an admin endpoint, keeping every field
+    fn admin_json<T: Serialize>(&self, mut builder: web::HttpResponseBuilder, body: &T) -> AppResult<HttpResponse> {
+        if self.ids.is_raw() {
+            Ok(builder.json(body))
+        } else {
+            Ok(builder.json(&self.ids.encode_body(body)?))
+        }
+    }
+
+    fn public_body<T: Serialize>(&self, body: &T) -> AppResult<Value> {
+        let mut value = self.ids.encode_body(body)?;
+        if let Some(fields) = &self.public_fields {
+            fields.apply(&mut value);
+        }
+        Ok(value)
+    }
+
+    /// Describes the API limits clients should validate against
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/meta
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// {
+    ///   "pagination": {
+    ///     "per_page": { "min": 1, "max": 100, "default": 10 }
+    ///   }
+    /// }
+    /// ```
+    pub async fn meta(&self) -> AppResult<impl Responder> {
+        let config = self.service.config();
+        Ok(HttpResponse::Ok().json(&MetaResponse {
+            pagination: PaginationMeta {
+                per_page: PerPageBounds {
+                    min: config.min_per_page,
+                    max: config.max_per_page.max(config.min_per_page),
+                    default: config.clamp_per_page(None),
+                },
+            },
+        }))
+    }
+
+    /// Checks the request carries the configured admin bearer token
+    /// 
+    /// # Errors
+    /// * `AppError::Forbidden` - If no admin token is configured
+    /// * `AppError::Unauthorized` - If the token is missing or wrong
+    fn require_admin(&self, req: &HttpRequest) -> AppResult<()> {
+        let Some(expected) = self.service.config().admin_token.as_deref() else {
+            return Err(AppError::Forbidden("admin endpoints are disabled".to_string()));
+        };
+
+        let given = req.headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        if given != Some(expected) {
+            return Err(AppError::Unauthorized("admin token required".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Regenerates the derived `title_normalized` key in batches, leaving titles as they are
+    /// 
+    /// Pass `after_id` to resume from a previous response's `next_cursor`
+    /// and `max_batches` to bound how much work one request does. Requires
+    /// `Authorization: Bearer <ADMIN_TOKEN>`.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/admin/reindex?max_batches=10
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "processed": 1000, "updated": 12, "next_cursor": 1000 }
+    /// ```
+    pub async fn reindex(&self, query: Query<ReindexQuery>, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        let result = self.service.reindex(query.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(&result))
+    }
+
+    /// Creates any missing database indexes and refreshes planner statistics
+    /// 
+    /// Requires `Authorization: Bearer <ADMIN_TOKEN>`.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/admin/reindex-db
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// {
+    ///   "created": ["idx_videos_title"],
+    ///   "present": ["idx_videos_youtube_id", "idx_videos_title", "idx_videos_created_at_id"],
+    ///   "analyzed": true
+    /// }
+    /// ```
+    pub async fn reindex_db(&self, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        let result = self.service.ensure_indexes().await?;
+        Ok(HttpResponse::Ok().json(&result))
+    }
+
+    /// Removes every video, for resetting test and development environments
+    /// 
+    /// Requires `Authorization: Bearer <ADMIN_TOKEN>` and `ALLOW_DATA_RESET`,
+    /// and is refused outright when `APP_ENV` is `production`.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/admin/reset
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "removed": 42 }
+    /// ```
+    pub async fn reset(&self, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        let result = self.service.reset_data().await?;
+        Ok(HttpResponse::Ok().json(&result))
+    }
+
+    /// Lists the most recent creates, updates and deletes, oldest first
+    /// 
+    /// Requires `Authorization: Bearer <ADMIN_TOKEN>`. The log lives in memory
+    /// only, holds at most `RECENT_OPS_CAPACITY` entries and is empty after a
+    /// restart. Dry runs are not recorded.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/admin/recent-ops
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// {
+    ///   "ops": [
+    ///     { "op": "create", "id": 7, "at": "2024-05-16T12:00:00Z" },
+    ///     { "op": "delete", "id": 7, "at": "2024-05-16T12:05:00Z" }
+    ///   ]
+    /// }
+    /// ```
+    pub async fn recent_ops(&self, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        self.admin_json(HttpResponse::Ok(), &RecentOpsResponse { ops: self.service.recent_ops() })
+    }
+
+    /// Retrieves any video, soft-deleted or not, with every field
+    /// 
+    /// Requires `Authorization: Bearer <ADMIN_TOKEN>`. Unlike the public
+    /// `GET /api/v1/videos/{id}`, `public_video_fields` is not applied.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/admin/videos/1
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 1, "title": "My Video", "deleted_at": "2024-05-16T12:00:00Z", ... }
+    /// ```
+    pub async fn admin_get_video(&self, id: Path<i32>, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        let video = self.service.get_video_including_deleted(id.into_inner()).await?;
+        self.admin_json(HttpResponse::Ok(), &video)
     }
 
     /// Creates a new video resource
     /// 
+    /// With `X-Dry-Run: true` the insert is rolled back and the response
+    /// carries `X-Dry-Run: applied`. When a daily create quota is configured,
+    /// each client IP is limited to that many committed creates per UTC day
+    /// and gets `429 Too Many Requests` with the reset time once it is used
+    /// up; dry runs and rejected creates don't count.
+    /// 
+    /// A committed create carries `Location` and `ETag`, so clients can go
+    /// straight to conditional requests against the new resource.
+    /// 
+    /// A create sent with an `Idempotency-Key` that already succeeded is not
+    /// repeated: the original video is answered with `201` again, marked
+    /// `Idempotent-Replayed: true`. A second request arriving while the first
+    /// is still running gets `409 Conflict`. Dry runs ignore the key.
+    /// 
     /// # Arguments
     /// * `req` - JSON payload containing video creation data
+    /// * `http_req` - The incoming request, used to detect dry runs and the client IP
     /// 
     /// # Returns
     /// * `AppResult<impl Responder>` - Returns the created video on success
@@ -112,6 +594,8 @@ impl VideoController {
     /// ```text
     /// HTTP/1.1 201 Created
     /// Content-Type: application/json
+    /// Location: /api/v1/videos/1
+    /// ETag: "1-1672531200000000"
     /// 
     /// {
     ///   "id": 1,
@@ -124,7 +608,7 @@ impl VideoController {
     /// ```
     /// 
     /// ```no_run
-    /// use ntex::web::types::Json;
+    /// use ntex::web::{test::TestRequest, types::Json};
     /// use ntex_api::api::CreateVideoRequest;
     /// use ntex_api::controllers::video_controller::VideoController;
     /// use ntex_api::services::video_service::VideoService;
@@ -139,21 +623,200 @@ impl VideoController {
     ///     let request = CreateVideoRequest {
     ///         title: "My Awesome Video".to_string(),
     ///         youtube_id: "dQw4w9WgXcQ".to_string(),
+    ///         description: None,
+    ///         status: None,
+    ///         tags: vec!["music".to_string()],
     ///     };
     /// 
-    ///     let response = controller.create_video(Json(request)).await?;
+    ///     let http_req = TestRequest::default().to_http_request();
+    ///     let response = controller.create_video(Json(request), http_req).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn create_video(&self, req: Json<CreateVideoRequest>) -> AppResult<impl Responder> {
-        let video = self.service.create_video(req.into_inner()).await?;
-        Ok(HttpResponse::Created().json(&video))
+    pub async fn create_video(&self, req: Json<CreateVideoRequest>, http_req: HttpRequest) -> AppResult<impl Responder> {
+        let dry_run = is_dry_run(&http_req);
+        let key = if dry_run { None } else { idempotency_key(&http_req) };
+
+        if let Some(key) = &key {
+            if let Claim::Replay(id) = self.idempotency.claim(key, Utc::now())? {
+                let video = self.service.get_video(id).await?;
+                let mut builder = self.created(&http_req, &video);
+                builder.header(IDEMPOTENT_REPLAYED_HEADER, "true");
+                return self.json(builder, &video);
+            }
+        }
+
+        let created = self.create_video_once(req.into_inner(), &http_req, dry_run).await;
+        if let Some(key) = &key {
+            match &created {
+                Ok(video) => self.idempotency.complete(key, video.id, Utc::now()),
+                Err(_) => self.idempotency.release(key),
+            }
+        }
+        let video = created?;
+
+        let mut builder = if dry_run { HttpResponse::Created() } else { self.created(&http_req, &video) };
+        mark_dry_run(&mut builder, dry_run);
+        self.json(builder, &video)
+    }
+
+    /// Performs the insert, or rolls it back for a dry run
+    /// 
+    /// Only a committed insert uses up the client's quota: dry runs skip it,
+    /// and a create rejected by validation or a conflict gives it back.
+    async fn create_video_once(&self, req: CreateVideoRequest, http_req: &HttpRequest, dry_run: bool) -> AppResult<VideoResponse> {
+        if dry_run {
+            return self.service.create_video_dry_run(req).await;
+        }
+
+        let quota = self.acquire_quota(http_req, 1)?;
+        let created = self.service.create_video(req).await;
+        if created.is_err() {
+            self.release_quota(quota, 1);
+        }
+        created
+    }
+
+    /// Takes `count` creates from the client's daily quota, if one is configured
+    /// 
+    /// Returns the quota and IP charged, for `release_quota` to refund.
+    /// 
+    /// # Errors
+    /// * `AppError::TooManyRequests` - If the client's remaining allowance is smaller than `count`
+    fn acquire_quota(&self, http_req: &HttpRequest, count: u32) -> AppResult<Option<(&CreateQuota, IpAddr)>> {
+        let Some(quota) = &self.create_quota else {
+            return Ok(None);
+        };
+        let Some(ip) = client_ip(http_req, &self.service.config().trusted_proxies) else {
+            return Ok(None);
+        };
+        quota.acquire_many(ip, Utc::now(), count)?;
+        Ok(Some((quota, ip)))
+    }
+
+    /// Gives back creates taken by `acquire_quota` for a request that wrote nothing
+    fn release_quota(&self, charged: Option<(&CreateQuota, IpAddr)>, count: u32) {
+        if let Some((quota, ip)) = charged {
+            quota.release(ip, Utc::now(), count);
+        }
+    }
+
+    /// Starts a `201 Created` response pointing at `video` with its `Location` and `ETag`
+    fn created(&self, http_req: &HttpRequest, video: &VideoResponse) -> web::HttpResponseBuilder {
+        let mut builder = HttpResponse::Created();
+        builder
+            .header(header::LOCATION, format!("{}/{}", http_req.path().trim_end_matches('/'), self.public_id(video.id)))
+            .header(header::ETAG, self.etag(video));
+        builder
+    }
+
+    /// Creates several videos in one request
+    /// 
+    /// The batch is all-or-nothing: one invalid item rejects the request with
+    /// its index, and nothing is written. Each video of a committed batch
+    /// counts against the daily create quota. With `X-Dry-Run: true` the
+    /// inserts are rolled back and nothing is counted.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/batch
+    /// Content-Type: application/json
+    /// 
+    /// [
+    ///   { "title": "Part 1", "youtube_id": "dQw4w9WgXcQ" },
+    ///   { "title": "Part 2", "youtube_id": "9bZkp7q19f0" }
+    /// ]
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 201 Created
+    /// Content-Type: application/json
+    /// 
+    /// [{ "id": 1, "title": "Part 1", ... }, { "id": 2, "title": "Part 2", ... }]
+    /// ```
+    pub async fn create_videos_batch(&self, reqs: Json<Vec<CreateVideoRequest>>, http_req: HttpRequest) -> AppResult<impl Responder> {
+        let reqs = reqs.into_inner();
+        let dry_run = is_dry_run(&http_req);
+        let videos = if dry_run {
+            self.service.create_videos_batch_dry_run(reqs).await?
+        } else {
+            let count = u32::try_from(reqs.len()).unwrap_or(u32::MAX);
+            let quota = self.acquire_quota(&http_req, count)?;
+            let created = self.service.create_videos_batch(reqs).await;
+            if created.is_err() {
+                self.release_quota(quota, count);
+            }
+            created?
+        };
+
+        let mut builder = HttpResponse::Created();
+        mark_dry_run(&mut builder, dry_run);
+        self.json(builder, &videos)
+    }
+
+    /// Soft-deletes several videos in one request
+    /// 
+    /// IDs that don't exist are listed in `not_found` rather than failing the
+    /// request. IDs are in their public form both ways. With
+    /// `X-Dry-Run: true` the deletes are rolled back.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// DELETE /api/v1/videos/batch
+    /// Content-Type: application/json
+    /// 
+    /// [1, 2, 99]
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "deleted": [1, 2], "not_found": [99] }
+    /// ```
+    pub async fn delete_videos_batch(&self, ids: Json<Vec<Value>>, http_req: HttpRequest) -> AppResult<impl Responder> {
+        let ids = ids
+            .iter()
+            .map(|id| self.ids.decode_value(id))
+            .collect::<AppResult<Vec<i32>>>()?;
+        let dry_run = is_dry_run(&http_req);
+        let result = if dry_run {
+            self.service.delete_videos_batch_dry_run(ids).await?
+        } else {
+            self.service.delete_videos_batch(ids).await?
+        };
+
+        let mut body = to_json(&result)?;
+        self.ids.encode_ids_in(&mut body["deleted"]);
+        self.ids.encode_ids_in(&mut body["not_found"]);
+
+        let mut builder = HttpResponse::Ok();
+        mark_dry_run(&mut builder, dry_run);
+        Ok(builder.json(&body))
     }
 
     /// Lists videos with optional filtering and pagination
     /// 
+    /// Responds with MessagePack instead of JSON when the request carries
+    /// `Accept: application/msgpack`. With `empty_list_no_content` enabled, a
+    /// page without any videos is answered with `204 No Content` instead.
+    /// Without `per_page`, the page size comes from an `X-Page-Size` header
+    /// and then from the configured default.
+    /// A `per_page` above the configured maximum is clamped and, unless
+    /// `per_page_clamp_warning` is off, flagged with a `Warning` header.
+    /// JSON pages with more rows than `stream_threshold_rows` are streamed
+    /// in chunks; smaller pages are buffered and carry `Content-Length`.
+    /// Page-numbered responses carry a `Link` header with `first`, `last`
+    /// and, away from the ends, `prev` and `next` URLs, and a
+    /// `Content-Range` such as `videos 0-9/42`. Every response carries the
+    /// matching total in `X-Total-Count`, except `cursor=true` pages, which
+    /// page by id and are answered by `list_videos_by_id`.
+    /// 
     /// # Arguments
     /// * `query` - Query parameters for filtering and pagination
+    /// * `req` - The incoming request, used for content negotiation and page links
     /// 
     /// # Returns
     /// * `AppResult<impl Responder>` - Returns a list of videos on success
@@ -187,7 +850,7 @@ impl VideoController {
     /// ```
     /// 
     /// ```no_run
-    /// use ntex::web::types::{Query, Path};
+    /// use ntex::web::{test::TestRequest, types::Query};
     /// use ntex_api::db::VideoQuery;
     /// use ntex_api::controllers::video_controller::VideoController;
     /// use ntex_api::services::video_service::VideoService;
@@ -205,21 +868,163 @@ impl VideoController {
     ///         search: Some("awesome".to_string()),
     ///         order_by: Some("created_at".to_string()),
     ///         order_direction: Some("desc".to_string()),
+    ///         ..Default::default()
     ///     };
     /// 
-    ///     let response = controller.list_videos(Query(query)).await?;
+    ///     let req = TestRequest::default().to_http_request();
+    ///     let response = controller.list_videos(Query(query), req).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn list_videos(&self, query: Query<VideoQuery>) -> AppResult<impl Responder> {
-        let videos = self.service.list_videos(query.into_inner()).await?;
-        Ok(HttpResponse::Ok().json(&videos))
+    pub async fn list_videos(&self, query: Query<VideoQuery>, req: HttpRequest) -> AppResult<impl Responder> {
+        let mut query = query.into_inner();
+        apply_page_size(&mut query, &req)?;
+
+        let config = self.service.config();
+        let clamped_to = config
+            .per_page_clamped_to(query.per_page)
+            .filter(|_| config.per_page_clamp_warning);
+
+        if query.cursor == Some(true) {
+            return self.list_videos_by_id(query, clamped_to, &req).await;
+        }
+
+        let keyset = query.after.is_some() || query.before.is_some();
+        let videos = self.service.list_videos(query).await?;
+        let no_content = videos.videos.is_empty() && config.empty_list_no_content;
+        let mut builder = if no_content {
+            HttpResponse::NoContent()
+        } else {
+            HttpResponse::Ok()
+        };
+        if let Some(max) = clamped_to {
+            builder.header(header::WARNING, format!("299 - \"per_page clamped to max {}\"", max));
+        }
+        builder.header(TOTAL_COUNT_HEADER, videos.total.to_string());
+        if !keyset {
+            if let Some(value) = paging::links(&req, videos.page, videos.total_pages) {
+                builder.header(header::LINK, value);
+            }
+            builder.header(header::CONTENT_RANGE, paging::content_range(&videos));
+        }
+
+        if no_content {
+            return Ok(builder.finish());
+        }
+        let stream = config
+            .stream_threshold_rows
+            .is_some_and(|threshold| videos.videos.len() > threshold);
+        if stream && !negotiation::wants_msgpack(&req) {
+            builder.header(header::VARY, "Accept");
+            return Ok(streaming::json_envelope(builder, self.public_body(&videos)?, "videos"));
+        }
+        self.respond(&req, builder, &videos)
+    }
+
+    /// Answers `GET /videos?cursor=true` with a page of videos ordered by id
+    ///
+    /// `after` holds the public id of the last video already seen; leave it
+    /// out (or empty) for the first page. The response's `next_cursor` is in
+    /// the same public form and is `null` on the last page.
+    async fn list_videos_by_id(
+        &self,
+        mut query: VideoQuery,
+        clamped_to: Option<u64>,
+        req: &HttpRequest,
+    ) -> AppResult<HttpResponse> {
+        let after = match query.after.take().filter(|after| !after.is_empty()) {
+            Some(after) => Some(self.ids.decode(&after)?),
+            None => None,
+        };
+        let page = self.service.list_videos_after_id(query, after).await?;
+
+        let no_content = page.videos.is_empty() && self.service.config().empty_list_no_content;
+        let mut builder = if no_content {
+            HttpResponse::NoContent()
+        } else {
+            HttpResponse::Ok()
+        };
+        if let Some(max) = clamped_to {
+            builder.header(header::WARNING, format!("299 - \"per_page clamped to max {}\"", max));
+        }
+        if no_content {
+            return Ok(builder.finish());
+        }
+
+        let mut body = self.public_body(&page)?;
+        if let Some(next_cursor) = body.get_mut("next_cursor") {
+            self.ids.encode_ids_in(next_cursor);
+        }
+        negotiation::respond(req, builder, &body)
+    }
+
+    /// Retrieves several videos by ID in a single request
+    /// 
+    /// IDs that don't resolve to a live video (never existed or soft-deleted)
+    /// are reported under `missing` instead of failing the whole request.
+    /// IDs are in their public form both ways, so hashids are accepted and
+    /// reported when a hashid salt is configured.
+    /// 
+    /// # Arguments
+    /// * `req` - JSON payload containing the IDs to fetch
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns the found videos and missing IDs
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/fetch
+    /// Content-Type: application/json
+    /// 
+    /// { "ids": [1, 42] }
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// {
+    ///   "found": [
+    ///     {
+    ///       "id": 1,
+    ///       "title": "My Awesome Video",
+    ///       "youtube_id": "dQw4w9WgXcQ",
+    ///       "created_at": "2023-01-01T00:00:00Z",
+    ///       "updated_at": "2023-01-01T00:00:00Z",
+    ///       "deleted_at": null
+    ///     }
+    ///   ],
+    ///   "missing": [42]
+    /// }
+    /// ```
+    pub async fn fetch_videos(&self, req: Json<PublicFetchVideosRequest>) -> AppResult<impl Responder> {
+        let ids = req
+            .ids
+            .iter()
+            .map(|id| self.ids.decode_value(id))
+            .collect::<AppResult<Vec<i32>>>()?;
+        let result = self.service.fetch_videos(FetchVideosRequest { ids }).await?;
+
+        let mut body = self.public_body(&result)?;
+        self.ids.encode_ids_in(&mut body["missing"]);
+        Ok(HttpResponse::Ok().json(&body))
     }
 
     /// Retrieves a specific video by ID
     /// 
+    /// Responds with MessagePack instead of JSON when the request carries
+    /// `Accept: application/msgpack`. The response carries an `ETag`; a
+    /// matching `If-None-Match` gets `304 Not Modified` without a body.
+    /// 
+    /// Soft-deleted videos are `404 Not Found` unless the query carries
+    /// `include_deleted=true`, which requires `Authorization: Bearer <ADMIN_TOKEN>`
+    /// and answers the row with its `deleted_at` populated.
+    /// 
     /// # Arguments
     /// * `id` - Path parameter containing the video ID
+    /// * `query` - Whether soft-deleted videos may be returned
+    /// * `req` - The incoming request, used for content negotiation and the admin token
     /// 
     /// # Returns
     /// * `AppResult<impl Responder>` - Returns the requested video on success
@@ -233,6 +1038,7 @@ impl VideoController {
     /// ```text
     /// HTTP/1.1 200 OK
     /// Content-Type: application/json
+    /// ETag: "1-1672531200000000"
     /// 
     /// {
     ///   "id": 1,
@@ -245,7 +1051,8 @@ impl VideoController {
     /// ```
     /// 
     /// ```no_run
-    /// use ntex::web::types::Path;
+    /// use ntex::web::{test::TestRequest, types::Query};
+    /// use ntex_api::api::GetVideoQuery;
     /// use ntex_api::controllers::video_controller::VideoController;
     /// use ntex_api::services::video_service::VideoService;
     /// use ntex_api::repositories::video_repository::VideoRepository;
@@ -257,31 +1064,385 @@ impl VideoController {
     ///     let controller = VideoController::new(service);
     ///     
     ///     let id = 1i32;
-    ///     let response = controller.get_video(id.into()).await?;
+    ///     let req = TestRequest::default().to_http_request();
+    ///     let query = Query(GetVideoQuery::default());
+    ///     let response = controller.get_video(id.into(), query, req).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_video(&self, id: Path<i32>) -> AppResult<impl Responder> {
-        let video = self.service.get_video(id.into_inner()).await?;
-        Ok(HttpResponse::Ok().json(&video))
+    pub async fn get_video(&self, id: Path<i32>, query: Query<GetVideoQuery>, req: HttpRequest) -> AppResult<impl Responder> {
+        let video = if query.include_deleted.unwrap_or(false) {
+            self.require_admin(&req)?;
+            self.service.get_video_including_deleted(id.into_inner()).await?
+        } else {
+            self.service.get_video(id.into_inner()).await?
+        };
+        let etag = self.etag(&video);
+        if if_none_match(&req, &etag) {
+            return Ok(HttpResponse::NotModified().header(header::ETAG, etag).finish());
+        }
+
+        let mut builder = HttpResponse::Ok();
+        builder.header(header::ETAG, etag);
+        self.respond(&req, builder, &video)
     }
 
-    /// Updates a specific video by ID
-    /// 
-    /// # Arguments
-    /// * `id` - Path parameter containing the video ID
-    /// * `req` - JSON payload containing video update data
+    /// Retrieves a video by its YouTube ID
     /// 
-    /// # Returns
-    /// * `AppResult<impl Responder>` - Returns the updated video on success
+    /// # Errors
+    /// * `AppError::Validation` - If the YouTube ID isn't exactly 11 characters
+    /// * `AppError::NotFound` - If no live video has this YouTube ID
     /// 
     /// # Example
     /// 
     /// ```text
-    /// PUT /api/v1/videos/1
+    /// GET /api/v1/videos/by-youtube/dQw4w9WgXcQ
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 1, "title": "My Video", "youtube_id": "dQw4w9WgXcQ", ... }
+    /// ```
+    pub async fn get_video_by_youtube_id(&self, youtube_id: Path<String>, req: HttpRequest) -> AppResult<impl Responder> {
+        let video = self.service.get_video_by_youtube_id(&youtube_id).await?;
+        self.respond(&req, HttpResponse::Ok(), &video)
+    }
+
+    /// Upgrades to a WebSocket pushing every committed create, update and delete
+    /// 
+    /// Each change arrives as a JSON text frame carrying the video in its
+    /// public form; restores are announced as updates. Permanent deletes and
+    /// data resets are not announced. Pings are answered; other frames from
+    /// the client are ignored.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/events
+    /// Connection: Upgrade
+    /// Upgrade: websocket
+    /// ```
+    /// 
+    /// ```text
+    /// {"type": "created", "video": {"id": 1, "title": "My Awesome Video", ...}}
+    /// ```
+    pub async fn video_events(&self, req: HttpRequest) -> Result<HttpResponse, web::Error> {
+        let ctrl = self.clone();
+
+        web::ws::start(req, fn_factory_with_config(move |sink: web::ws::WsSink| {
+            let ctrl = ctrl.clone();
+            let mut events = ctrl.service.subscribe_events();
+            async move {
+                ntex::rt::spawn(async move {
+                    let disconnected = sink.on_disconnect();
+                    futures::pin_mut!(disconnected);
+                    loop {
+                        let event = tokio::select! {
+                            _ = &mut disconnected => break,
+                            event = events.recv() => event,
+                        };
+                        let event = match event {
+                            Ok(event) => event,
+                            Err(RecvError::Lagged(skipped)) => {
+                                tracing::warn!("Video event subscriber skipped {} events", skipped);
+                                continue;
+                            }
+                            Err(RecvError::Closed) => break,
+                        };
+                        let Some(frame) = ctrl.event_json(&event) else {
+                            continue;
+                        };
+                        if sink.send(web::ws::Message::Text(frame.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+
+                Ok::<_, web::Error>(fn_service(|frame: web::ws::Frame| async move {
+                    Ok::<_, web::Error>(match frame {
+                        web::ws::Frame::Ping(payload) => Some(web::ws::Message::Pong(payload)),
+                        web::ws::Frame::Close(reason) => Some(web::ws::Message::Close(reason)),
+                        _ => None,
+                    })
+                }))
+            }
+        }))
+        .await
+    }
+
+    /// Streams every committed create, update and delete as Server-Sent Events
+    /// 
+    /// A simpler alternative to `video_events`: each change is one `data:`
+    /// frame carrying the same JSON a WebSocket subscriber receives, and a
+    /// `: keep-alive` comment is sent every `SSE_KEEP_ALIVE` of silence.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/stream
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: text/event-stream
+    /// 
+    /// data: {"type":"created","video":{"id":1,"title":"My Awesome Video",...}}
+    /// 
+    /// : keep-alive
+    /// ```
+    pub async fn stream_events(&self) -> AppResult<impl Responder> {
+        let ctrl = self.clone();
+        Ok(streaming::event_stream(
+            HttpResponse::Ok(),
+            self.service.subscribe_events(),
+            SSE_KEEP_ALIVE,
+            move |event| ctrl.event_json(event),
+        ))
+    }
+
+    /// Encodes a change event as pushed to subscribers, with the video in its public form
+    fn event_json(&self, event: &VideoEvent) -> Option<String> {
+        match self.public_body(&event.video) {
+            Ok(video) => Some(serde_json::json!({ "type": event.kind, "video": video }).to_string()),
+            Err(e) => {
+                tracing::warn!("Could not encode video event: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Reports video counts for a dashboard overview
+    /// 
+    /// `newest_id` is in its public form.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/stats
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
     /// Content-Type: application/json
     /// 
     /// {
+    ///   "total": 42,
+    ///   "deleted": 3,
+    ///   "created_today": 2,
+    ///   "newest_id": 45,
+    ///   "oldest_created_at": "2024-01-15T09:30:00Z"
+    /// }
+    /// ```
+    pub async fn stats(&self) -> AppResult<impl Responder> {
+        let stats = self.service.stats().await?;
+        let mut body = to_json(&stats)?;
+        self.ids.encode_ids_in(&mut body["newest_id"]);
+        Ok(HttpResponse::Ok().json(&body))
+    }
+
+    /// Lists groups of videos sharing a YouTube ID, soft-deleted ones included
+    /// 
+    /// IDs are in their public form.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/duplicates
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "groups": [{ "youtube_id": "dQw4w9WgXcQ", "ids": [3, 8] }] }
+    /// ```
+    pub async fn duplicates(&self) -> AppResult<impl Responder> {
+        let duplicates = self.service.find_duplicates().await?;
+        let mut body = to_json(&duplicates)?;
+        if let Some(groups) = body["groups"].as_array_mut() {
+            for group in groups {
+                self.ids.encode_ids_in(&mut group["ids"]);
+            }
+        }
+        Ok(HttpResponse::Ok().json(&body))
+    }
+
+    /// Retrieves one non-deleted video chosen at random
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If there are no live videos
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/random
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 7, "title": "Some Video", "youtube_id": "dQw4w9WgXcQ", ... }
+    /// ```
+    pub async fn random_video(&self, req: HttpRequest) -> AppResult<impl Responder> {
+        let video = self.service.get_random_video().await?;
+        self.respond(&req, HttpResponse::Ok(), &video)
+    }
+
+    /// Describes a video as a schema.org `VideoObject` JSON-LD document
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If the video doesn't exist or is deleted
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/1/json-ld
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/ld+json
+    /// 
+    /// {
+    ///   "@context": "https://schema.org",
+    ///   "@type": "VideoObject",
+    ///   "name": "My Awesome Video",
+    ///   "embedUrl": "https://www.youtube.com/embed/dQw4w9WgXcQ",
+    ///   "thumbnailUrl": "https://i.ytimg.com/vi/dQw4w9WgXcQ/hqdefault.jpg",
+    ///   "uploadDate": "2023-01-01T00:00:00Z"
+    /// }
+    /// ```
+    pub async fn get_json_ld(&self, id: Path<i32>) -> AppResult<impl Responder> {
+        let video = self.service.get_video(id.into_inner()).await?;
+        let body = serde_json::to_vec(&VideoObject::from(&video))
+            .map_err(|e| AppError::Internal(format!("Failed to encode JSON-LD: {}", e)))?;
+        Ok(HttpResponse::Ok().content_type(JSON_LD_CONTENT_TYPE).body(body))
+    }
+
+    /// Imports videos, upserting by YouTube ID
+    /// 
+    /// Videos whose YouTube ID already exists get the imported title; the
+    /// rest are created. The whole import is one transaction.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/import
+    /// Content-Type: application/json
+    /// 
+    /// [
+    ///   { "title": "Renamed Video", "youtube_id": "dQw4w9WgXcQ" },
+    ///   { "title": "Brand New Video", "youtube_id": "9bZkp7q19f0" }
+    /// ]
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "inserted": 1, "updated": 1 }
+    /// ```
+    pub async fn import_videos(&self, reqs: Json<Vec<CreateVideoRequest>>) -> AppResult<impl Responder> {
+        let result = self.service.import_videos(reqs.into_inner()).await?;
+        Ok(HttpResponse::Ok().json(&result))
+    }
+
+    /// Validates a CSV import file without creating any videos
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/import/validate
+    /// Content-Type: text/csv
+    /// 
+    /// title,youtube_id
+    /// My Awesome Video,dQw4w9WgXcQ
+    /// Same Video Again,dQw4w9WgXcQ
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// {
+    ///   "valid_rows": 1,
+    ///   "errors": [
+    ///     { "line": 3, "error": "duplicate youtube_id dQw4w9WgXcQ (first seen on line 2)" }
+    ///   ]
+    /// }
+    /// ```
+    pub async fn validate_import(&self, body: Bytes) -> AppResult<impl Responder> {
+        let result = self.service.validate_import(&body).await?;
+        Ok(HttpResponse::Ok().json(&result))
+    }
+
+    /// Lists videos related to a specific video by shared title words
+    /// 
+    /// Responds with MessagePack instead of JSON when the request carries
+    /// `Accept: application/msgpack`.
+    /// 
+    /// # Arguments
+    /// * `id` - Path parameter containing the source video ID
+    /// * `query` - Optional `limit` on how many videos to return
+    /// * `req` - The incoming request, used for content negotiation
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/1/related?limit=3
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// {
+    ///   "videos": [
+    ///     {
+    ///       "id": 5,
+    ///       "title": "Async Rust tutorial, part 2",
+    ///       "youtube_id": "dQw4w9WgXcQ",
+    ///       "created_at": "2023-01-02T00:00:00Z",
+    ///       "updated_at": "2023-01-02T00:00:00Z",
+    ///       "deleted_at": null
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    pub async fn get_related(&self, id: Path<i32>, query: Query<RelatedQuery>, req: HttpRequest) -> AppResult<impl Responder> {
+        let result = self.service.related_videos(id.into_inner(), query.into_inner()).await?;
+        self.respond(&req, HttpResponse::Ok(), &result)
+    }
+
+    /// Replaces a specific video by ID
+    /// 
+    /// Backs `PUT`: both `title` and `youtube_id` are required. Use
+    /// [`patch_video`](Self::patch_video) to change only some fields.
+    /// The request must carry the video's `ETag` in `If-Match`, or `*`;
+    /// without it the answer is `428`, and with a stale one `412`, so
+    /// concurrent writers can't silently overwrite each other. A committed
+    /// update answers with the new `ETag`. With `X-Dry-Run: true` the
+    /// update is rolled back and the response carries `X-Dry-Run: applied`.
+    /// 
+    /// # Arguments
+    /// * `id` - Path parameter containing the video ID
+    /// * `req` - JSON payload containing the full replacement
+    /// * `http_req` - The incoming request, used for `If-Match` and to detect dry runs
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns the updated video on success
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// PUT /api/v1/videos/1
+    /// Content-Type: application/json
+    /// If-Match: "1-1672531200000000"
+    /// 
+    /// {
     ///   "title": "Updated Video Title",
     ///   "youtube_id": "dQw4w9WgXcQ"
     /// }
@@ -302,8 +1463,8 @@ impl VideoController {
     /// ```
     /// 
     /// ```no_run
-    /// use ntex::web::types::{Path, Json};
-    /// use ntex_api::api::UpdateVideoRequest;
+    /// use ntex::web::{test::TestRequest, types::Json};
+    /// use ntex_api::api::ReplaceVideoRequest;
     /// use ntex_api::controllers::video_controller::VideoController;
     /// use ntex_api::services::video_service::VideoService;
     /// use ntex_api::repositories::video_repository::VideoRepository;
@@ -314,28 +1475,77 @@ impl VideoController {
     ///     let service = VideoService::new(repo);
     ///     let controller = VideoController::new(service);
     ///     
-    ///     let request = UpdateVideoRequest {
-    ///         title: Some("Updated Video Title".to_string()),
-    ///         youtube_id: Some("dQw4w9WgXcQ".to_string()),
+    ///     let request = ReplaceVideoRequest {
+    ///         title: "Updated Video Title".to_string(),
+    ///         youtube_id: "dQw4w9WgXcQ".to_string(),
+    ///         description: None,
     ///     };
     /// 
     ///     let id = 1i32;
-    ///     let response = controller.update_video(id.into(), Json(request)).await?;
+    ///     let http_req = TestRequest::default().to_http_request();
+    ///     let response = controller.update_video(id.into(), Json(request), http_req).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn update_video(&self, id: Path<i32>, req: Json<UpdateVideoRequest>) -> AppResult<impl Responder> {
-        let video = self.service.update_video(id.into_inner(), req.into_inner()).await?;
-        Ok(HttpResponse::Ok().json(&video))
+    pub async fn update_video(&self, id: Path<i32>, req: Json<ReplaceVideoRequest>, http_req: HttpRequest) -> AppResult<impl Responder> {
+        self.apply_update(id.into_inner(), req.into_inner().into(), &http_req).await
+    }
+
+    /// Partially updates a specific video by ID
+    /// 
+    /// Backs `PATCH`: only the fields present in the body are changed, and
+    /// `"description": null` clears the description.
+    /// `If-Match` and dry runs work as for [`update_video`](Self::update_video).
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// PATCH /api/v1/videos/1
+    /// Content-Type: application/json
+    /// If-Match: "1-1672531200000000"
+    /// 
+    /// {
+    ///   "title": "Updated Video Title"
+    /// }
+    /// ```
+    pub async fn patch_video(&self, id: Path<i32>, req: Json<UpdateVideoRequest>, http_req: HttpRequest) -> AppResult<impl Responder> {
+        self.apply_update(id.into_inner(), req.into_inner(), &http_req).await
+    }
+
+    async fn apply_update(&self, id: i32, req: UpdateVideoRequest, http_req: &HttpRequest) -> AppResult<HttpResponse> {
+        let expected_updated_at = if_match(http_req, &self.public_id(id))?;
+        let dry_run = is_dry_run(http_req);
+        let video = if dry_run {
+            self.service.update_video_dry_run(id, req, expected_updated_at).await?
+        } else {
+            self.service.update_video(id, req, expected_updated_at).await?
+        };
+
+        let mut builder = HttpResponse::Ok();
+        mark_dry_run(&mut builder, dry_run);
+        if !dry_run {
+            builder.header(header::ETAG, self.etag(&video));
+        }
+        self.json(builder, &video)
     }
 
     /// Deletes a specific video by ID
     /// 
+    /// Soft-deletes by default. With `?permanent=true` the row is removed
+    /// for good, which also works on a video that is already soft-deleted;
+    /// that requires `Authorization: Bearer <ADMIN_TOKEN>`.
+    /// A soft delete with `?return=true` answers `200` with the deleted
+    /// video, `deleted_at` included; permanent deletes have nothing to
+    /// return and reject it. With `X-Dry-Run: true` the delete is rolled
+    /// back and the response carries `X-Dry-Run: applied`.
+    /// 
     /// # Arguments
     /// * `id` - Path parameter containing the video ID
+    /// * `query` - Query parameters selecting a permanent delete or a returned body
+    /// * `http_req` - The incoming request, used for the admin token and to detect dry runs
     /// 
     /// # Returns
-    /// * `AppResult<impl Responder>` - Returns no content on success
+    /// * `AppResult<impl Responder>` - Returns no content, or the deleted video, on success
     /// 
     /// # Example
     /// 
@@ -348,7 +1558,9 @@ impl VideoController {
     /// ```
     /// 
     /// ```no_run
-    /// use ntex::web::types::Path;
+    /// use ntex::web::test::TestRequest;
+    /// use ntex::web::types::Query;
+    /// use ntex_api::api::DeleteQuery;
     /// use ntex_api::controllers::video_controller::VideoController;
     /// use ntex_api::services::video_service::VideoService;
     /// use ntex_api::repositories::video_repository::VideoRepository;
@@ -360,14 +1572,311 @@ impl VideoController {
     ///     let controller = VideoController::new(service);
     ///     
     ///     let id = 1i32;
-    ///     let response = controller.delete_video(id.into()).await?;
+    ///     let http_req = TestRequest::default().to_http_request();
+    ///     let response = controller.delete_video(id.into(), Query(DeleteQuery::default()), http_req).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete_video(&self, id: Path<i32>) -> AppResult<impl Responder> {
-        self.service.delete_video(id.into_inner()).await?;
-        Ok(HttpResponse::NoContent().finish())
+    pub async fn delete_video(&self, id: Path<i32>, query: Query<DeleteQuery>, http_req: HttpRequest) -> AppResult<impl Responder> {
+        let id = id.into_inner();
+        let dry_run = is_dry_run(&http_req);
+        let return_video = query.return_video.unwrap_or(false);
+        if query.permanent.unwrap_or(false) {
+            self.require_admin(&http_req)?;
+            if return_video {
+                return Err(AppError::BadRequest("return=true is only supported for soft deletes".to_string()));
+            }
+            if dry_run {
+                self.service.hard_delete_video_dry_run(id).await?;
+            } else {
+                self.service.hard_delete_video(id).await?;
+            }
+
+            let mut builder = HttpResponse::NoContent();
+            mark_dry_run(&mut builder, dry_run);
+            return Ok(builder.finish());
+        }
+
+        let video = if dry_run {
+            self.service.delete_video_dry_run(id).await?
+        } else {
+            self.service.delete_video(id).await?
+        };
+
+        if return_video {
+            let mut builder = HttpResponse::Ok();
+            mark_dry_run(&mut builder, dry_run);
+            return self.json(builder, &video);
+        }
+
+        let mut builder = HttpResponse::NoContent();
+        mark_dry_run(&mut builder, dry_run);
+        Ok(builder.finish())
+    }
+
+    /// Lists soft-deleted videos in the same shape as `list_videos`
+    /// 
+    /// The page size falls back to `X-Page-Size` like `list_videos`.
+    /// Requires `Authorization: Bearer <ADMIN_TOKEN>`, like every other view
+    /// of deleted rows, and so returns every field, `deleted_at` included.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/trash?page=1&per_page=10
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "videos": [...], "total": 1, "page": 1, "per_page": 10, "total_pages": 1 }
+    /// ```
+    pub async fn list_deleted_videos(&self, query: Query<VideoQuery>, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        let mut query = query.into_inner();
+        apply_page_size(&mut query, &req)?;
+
+        let videos = self.service.list_deleted_videos(query).await?;
+        if self.ids.is_raw() {
+            negotiation::respond(&req, HttpResponse::Ok(), &videos)
+        } else {
+            negotiation::respond(&req, HttpResponse::Ok(), &self.ids.encode_body(&videos)?)
+        }
+    }
+
+    /// Undoes the soft delete of a specific video
+    /// 
+    /// Requires `Authorization: Bearer <ADMIN_TOKEN>`.
+    /// 
+    /// # Arguments
+    /// * `id` - Path parameter containing the video ID
+    /// * `req` - The incoming request, carrying the admin token
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns the restored video, 404 if no
+    ///   video has this ID, or 409 if it isn't deleted
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/1/restore
+    /// Authorization: Bearer s3cret
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 1, "title": "My Video", "deleted_at": null, ... }
+    /// ```
+    pub async fn restore_video(&self, id: Path<i32>, req: HttpRequest) -> AppResult<impl Responder> {
+        self.require_admin(&req)?;
+        let video = self.service.restore_video(id.into_inner()).await?;
+        self.admin_json(HttpResponse::Ok(), &video)
+    }
+
+    /// Publishes a specific video
+    /// 
+    /// # Arguments
+    /// * `id` - Path parameter containing the video ID
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns the published video, 404 if no
+    ///   live video has this ID, or 409 if it is already published
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/1/publish
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 1, "title": "My Video", "status": "published", ... }
+    /// ```
+    pub async fn publish_video(&self, id: Path<i32>) -> AppResult<impl Responder> {
+        let video = self.service.publish_video(id.into_inner()).await?;
+        self.json(HttpResponse::Ok(), &video)
+    }
+
+    /// Moves a specific video within the manual ordering
+    /// 
+    /// # Arguments
+    /// * `id` - Path parameter containing the video ID
+    /// * `req` - JSON body with the 1-based `position` to move to
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns the moved video, 404 if no
+    ///   live video has this ID, or 400 if `position` is below 1
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// PATCH /api/v1/videos/3/position
+    /// Content-Type: application/json
+    /// 
+    /// { "position": 1 }
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 3, "title": "My Video", "position": 1, ... }
+    /// ```
+    pub async fn move_video(&self, id: Path<i32>, req: Json<MoveVideoRequest>) -> AppResult<impl Responder> {
+        let video = self.service.move_video(id.into_inner(), req.into_inner()).await?;
+        self.json(HttpResponse::Ok(), &video)
     }
+
+    /// Exchanges the manual positions of two videos
+    /// 
+    /// Both swap in one transaction; if either isn't a live video the
+    /// request is answered with 404 and neither changes. IDs are in their
+    /// public form.
+    /// 
+    /// # Arguments
+    /// * `req` - JSON body naming the two videos as `a` and `b`
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns both videos with their new positions, `a` first
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/swap
+    /// Content-Type: application/json
+    /// 
+    /// { "a": 1, "b": 2 }
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "videos": [{ "id": 1, "position": 2, ... }, { "id": 2, "position": 1, ... }] }
+    /// ```
+    pub async fn swap_videos(&self, req: Json<SwapVideosRequest>) -> AppResult<impl Responder> {
+        let a = self.ids.decode_value(&req.a)?;
+        let b = self.ids.decode_value(&req.b)?;
+        let swapped = self.service.swap_positions(a, b).await?;
+        self.json(HttpResponse::Ok(), &swapped)
+    }
+
+    /// Streams every live video as CSV
+    /// 
+    /// Rows are fetched `EXPORT_PAGE_SIZE` at a time and written to the body
+    /// as each page arrives, so the export never holds every video in
+    /// memory. IDs are in their public form.
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// GET /api/v1/videos/export.csv
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: text/csv
+    /// Content-Disposition: attachment; filename="videos.csv"
+    /// 
+    /// id,title,youtube_id,created_at,updated_at
+    /// 1,My Awesome Video,dQw4w9WgXcQ,2023-01-01T00:00:00+00:00,2023-01-01T00:00:00+00:00
+    /// ```
+    pub async fn export_csv(&self) -> AppResult<impl Responder> {
+        let header = Bytes::from_static(b"id,title,youtube_id,created_at,updated_at\n");
+        let ctrl = self.clone();
+        let rows = stream::try_unfold(Some(1), move |page| {
+            let ctrl = ctrl.clone();
+            async move {
+                let Some(page) = page else {
+                    return Ok(None);
+                };
+                let videos = ctrl.service.export_page(page).await?;
+                let next = (videos.len() as u64 == EXPORT_PAGE_SIZE).then_some(page + 1);
+                Ok::<_, AppError>(Some((ctrl.csv_rows(&videos)?, next)))
+            }
+        });
+
+        Ok(HttpResponse::Ok()
+            .content_type(CSV_CONTENT_TYPE)
+            .header(header::CONTENT_DISPOSITION, "attachment; filename=\"videos.csv\"")
+            .streaming(Box::pin(stream::once(async { Ok(header) }).chain(rows))))
+    }
+
+    /// Encodes videos as CSV rows, without a header
+    fn csv_rows(&self, videos: &[VideoResponse]) -> AppResult<Bytes> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for video in videos {
+            writer
+                .write_record([
+                    self.public_id(video.id),
+                    video.title.clone(),
+                    video.youtube_id.clone(),
+                    video.created_at.to_rfc3339(),
+                    video.updated_at.to_rfc3339(),
+                ])
+                .map_err(|e| AppError::Internal(format!("Failed to encode CSV: {}", e)))?;
+        }
+        let rows = writer
+            .into_inner()
+            .map_err(|e| AppError::Internal(format!("Failed to encode CSV: {}", e)))?;
+        Ok(Bytes::from(rows))
+    }
+
+    /// Records one view of a specific video
+    /// 
+    /// The counter is incremented atomically in the database, so concurrent
+    /// views are all counted. Views don't change `updated_at` or the ETag.
+    /// 
+    /// # Arguments
+    /// * `id` - Path parameter containing the video ID
+    /// 
+    /// # Returns
+    /// * `AppResult<impl Responder>` - Returns the new view count, or 404 if
+    ///   no live video has this ID
+    /// 
+    /// # Example
+    /// 
+    /// ```text
+    /// POST /api/v1/videos/1/view
+    /// ```
+    /// 
+    /// ```text
+    /// HTTP/1.1 200 OK
+    /// Content-Type: application/json
+    /// 
+    /// { "id": 1, "view_count": 42 }
+    /// ```
+    pub async fn record_view(&self, id: Path<i32>) -> AppResult<impl Responder> {
+        let views = self.service.record_view(id.into_inner()).await?;
+        self.json(HttpResponse::Ok(), &views)
+    }
+}
+
+/// Answers an `OPTIONS` probe with the methods allowed on a resource
+/// 
+/// # Arguments
+/// * `allow` - Comma separated list of allowed methods
+/// 
+/// # Example
+/// 
+/// ```text
+/// OPTIONS /api/v1/videos/1
+/// ```
+/// 
+/// ```text
+/// HTTP/1.1 204 No Content
+/// Allow: GET, PUT, PATCH, DELETE, OPTIONS
+/// ```
+pub async fn options(allow: &'static str) -> HttpResponse {
+    HttpResponse::NoContent()
+        .header(header::ALLOW, allow)
+        .finish()
 }
 
 /// Configures the video controller routes
@@ -375,6 +1884,7 @@ impl VideoController {
 /// # Arguments
 /// * `cfg` - Service configuration
 /// * `controller` - Video controller instance
+/// * `base_path` - Prefix of every route, normally `Config::api_base_path`
 /// 
 /// # Example
 /// 
@@ -392,39 +1902,233 @@ impl VideoController {
 ///     
 ///     let app = web::App::new()
 ///         .configure(|cfg| {
-///             video_controller::config(cfg, controller);
+///             video_controller::config(cfg, controller, "/api/v1");
 ///         });
 /// }
 /// ```
-pub fn config(cfg: &mut web::ServiceConfig, controller: VideoController) {
+pub fn config(cfg: &mut web::ServiceConfig, controller: VideoController, base_path: &str) {
     let controller = Arc::new(controller);
     let c1 = controller.clone();
     let c2 = controller.clone();
+    let c30 = controller.clone();
+    let c31 = controller.clone();
+    let c32 = controller.clone();
     let c3 = controller.clone();
     let c4 = controller.clone();
     let c5 = controller.clone();
+    let c6 = controller.clone();
+    let c7 = controller.clone();
+    let c8 = controller.clone();
+    let c9 = controller.clone();
+    let c10 = controller.clone();
+    let c11 = controller.clone();
+    let c12 = controller.clone();
+    let c13 = controller.clone();
+    let c14 = controller.clone();
+    let c15 = controller.clone();
+    let c16 = controller.clone();
+    let c17 = controller.clone();
+    let c18 = controller.clone();
+    let c19 = controller.clone();
+    let c20 = controller.clone();
+    let c21 = controller.clone();
+    let c22 = controller.clone();
+    let c23 = controller.clone();
+    let c24 = controller.clone();
+    let c25 = controller.clone();
+    let c26 = controller.clone();
+    let c27 = controller.clone();
+    let c28 = controller.clone();
+    let c29 = controller.clone();
     
+    cfg.route(&format!("{}/meta", base_path), web::get().to(move || {
+        let ctrl = Arc::clone(&c7);
+        async move { ctrl.meta().await }
+    }));
+
+    cfg.route(&format!("{}/admin/reindex", base_path), web::post().to(move |query: Query<ReindexQuery>, req: HttpRequest| {
+        let ctrl = Arc::clone(&c8);
+        async move { ctrl.reindex(query, req).await }
+    }));
+
+    cfg.route(&format!("{}/admin/reindex-db", base_path), web::post().to(move |req: HttpRequest| {
+        let ctrl = Arc::clone(&c9);
+        async move { ctrl.reindex_db(req).await }
+    }));
+
+    cfg.route(&format!("{}/admin/recent-ops", base_path), web::get().to(move |req: HttpRequest| {
+        let ctrl = Arc::clone(&c13);
+        async move { ctrl.recent_ops(req).await }
+    }));
+
+    cfg.route(&format!("{}/admin/reset", base_path), web::post().to(move |req: HttpRequest| {
+        let ctrl = Arc::clone(&c17);
+        async move { ctrl.reset(req).await }
+    }));
+
+    cfg.route(&format!("{}/admin/videos/{{id}}", base_path), web::get().to(move |id: Path<String>, req: HttpRequest| {
+        let ctrl = Arc::clone(&c16);
+        async move {
+            let id = ctrl.decode_id(&id)?;
+            ctrl.admin_get_video(id.into(), req).await
+        }
+    }));
+
     cfg.service(
-        web::scope("/api/v1/videos")
-            .route("", web::post().to(move |req: Json<CreateVideoRequest>| {
+        web::scope(format!("{}/videos", base_path))
+            .route("", web::post().to(move |req: JsonBody<Value>, http_req: HttpRequest| {
                 let ctrl = Arc::clone(&c1);
-                async move { ctrl.create_video(req).await }
+                async move {
+                    let req = ctrl.body::<CreateVideoRequest>(req)?;
+                    ctrl.create_video(req, http_req).await
+                }
             }))
-            .route("", web::get().to(move |query: Query<VideoQuery>| {
+            .route("", web::get().to(move |query: Query<VideoQuery>, req: HttpRequest| {
                 let ctrl = Arc::clone(&c2);
-                async move { ctrl.list_videos(query).await }
+                async move { ctrl.list_videos(query, req).await }
             }))
-            .route("/{id}", web::get().to(move |id: Path<i32>| {
+            .route("", web::method(Method::OPTIONS).to(|| options(COLLECTION_METHODS)))
+            .route("/fetch", web::post().to(move |req: JsonBody<Value>| {
+                let ctrl = Arc::clone(&c6);
+                async move {
+                    let req = ctrl.body::<PublicFetchVideosRequest>(req)?;
+                    ctrl.fetch_videos(req).await
+                }
+            }))
+            .route("/swap", web::post().to(move |req: JsonBody<Value>| {
+                let ctrl = Arc::clone(&c32);
+                async move {
+                    let req = ctrl.body::<SwapVideosRequest>(req)?;
+                    ctrl.swap_videos(req).await
+                }
+            }))
+            .route("/batch", web::post().to(move |req: JsonBody<Value>, http_req: HttpRequest| {
+                let ctrl = Arc::clone(&c18);
+                async move {
+                    let req = ctrl.body::<Vec<CreateVideoRequest>>(req)?;
+                    ctrl.create_videos_batch(req, http_req).await
+                }
+            }))
+            .route("/batch", web::delete().to(move |ids: JsonBody<Vec<Value>>, http_req: HttpRequest| {
+                let ctrl = Arc::clone(&c19);
+                async move { ctrl.delete_videos_batch(Json(ids.into_inner()), http_req).await }
+            }))
+            .route("/by-youtube/{youtube_id}", web::get().to(move |youtube_id: Path<String>, req: HttpRequest| {
+                let ctrl = Arc::clone(&c20);
+                async move { ctrl.get_video_by_youtube_id(youtube_id, req).await }
+            }))
+            .route("/random", web::get().to(move |req: HttpRequest| {
+                let ctrl = Arc::clone(&c25);
+                async move { ctrl.random_video(req).await }
+            }))
+            .route("/events", web::get().to(move |req: HttpRequest| {
+                let ctrl = Arc::clone(&c28);
+                async move { ctrl.video_events(req).await }
+            }))
+            .route("/stream", web::get().to(move || {
+                let ctrl = Arc::clone(&c29);
+                async move { ctrl.stream_events().await }
+            }))
+            .route("/stats", web::get().to(move || {
+                let ctrl = Arc::clone(&c27);
+                async move { ctrl.stats().await }
+            }))
+            .route("/duplicates", web::get().to(move || {
+                let ctrl = Arc::clone(&c26);
+                async move { ctrl.duplicates().await }
+            }))
+            .route("/trash", web::get().to(move |query: Query<VideoQuery>, req: HttpRequest| {
+                let ctrl = Arc::clone(&c15);
+                async move { ctrl.list_deleted_videos(query, req).await }
+            }))
+            .route("/export.csv", web::get().to(move || {
+                let ctrl = Arc::clone(&c23);
+                async move { ctrl.export_csv().await }
+            }))
+            .route("/import", web::post().to(move |req: JsonBody<Value>| {
+                let ctrl = Arc::clone(&c24);
+                async move {
+                    let req = ctrl.body::<Vec<CreateVideoRequest>>(req)?;
+                    ctrl.import_videos(req).await
+                }
+            }))
+            .route("/import/validate", web::post().to(move |body: Bytes| {
+                let ctrl = Arc::clone(&c11);
+                async move { ctrl.validate_import(body).await }
+            }))
+            .route("/{id}", web::get().to(move |id: Path<String>, query: Query<GetVideoQuery>, req: HttpRequest| {
                 let ctrl = Arc::clone(&c3);
-                async move { ctrl.get_video(id).await }
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.get_video(id.into(), query, req).await
+                }
             }))
-            .route("/{id}", web::put().to(move |id: Path<i32>, req: Json<UpdateVideoRequest>| {
+            .route("/{id}", web::put().to(move |id: Path<String>, req: JsonBody<Value>, http_req: HttpRequest| {
                 let ctrl = Arc::clone(&c4);
-                async move { ctrl.update_video(id, req).await }
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    let req = ctrl.body::<ReplaceVideoRequest>(req)?;
+                    ctrl.update_video(id.into(), req, http_req).await
+                }
             }))
-            .route("/{id}", web::delete().to(move |id: Path<i32>| {
+            .route("/{id}", web::patch().to(move |id: Path<String>, req: JsonBody<Value>, http_req: HttpRequest| {
+                let ctrl = Arc::clone(&c21);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    let req = ctrl.body::<UpdateVideoRequest>(req)?;
+                    ctrl.patch_video(id.into(), req, http_req).await
+                }
+            }))
+            .route("/{id}", web::delete().to(move |id: Path<String>, query: Query<DeleteQuery>, http_req: HttpRequest| {
                 let ctrl = Arc::clone(&c5);
-                async move { ctrl.delete_video(id).await }
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.delete_video(id.into(), query, http_req).await
+                }
+            }))
+            .route("/{id}", web::method(Method::OPTIONS).to(|| options(ITEM_METHODS)))
+            .route("/{id}/restore", web::post().to(move |id: Path<String>, req: HttpRequest| {
+                let ctrl = Arc::clone(&c14);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.restore_video(id.into(), req).await
+                }
+            }))
+            .route("/{id}/publish", web::post().to(move |id: Path<String>| {
+                let ctrl = Arc::clone(&c30);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.publish_video(id.into()).await
+                }
+            }))
+            .route("/{id}/position", web::patch().to(move |id: Path<String>, req: JsonBody<Value>| {
+                let ctrl = Arc::clone(&c31);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    let req = ctrl.body::<MoveVideoRequest>(req)?;
+                    ctrl.move_video(id.into(), req).await
+                }
+            }))
+            .route("/{id}/view", web::post().to(move |id: Path<String>| {
+                let ctrl = Arc::clone(&c22);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.record_view(id.into()).await
+                }
+            }))
+            .route("/{id}/json-ld", web::get().to(move |id: Path<String>| {
+                let ctrl = Arc::clone(&c12);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.get_json_ld(id.into()).await
+                }
+            }))
+            .route("/{id}/related", web::get().to(move |id: Path<String>, query: Query<RelatedQuery>, req: HttpRequest| {
+                let ctrl = Arc::clone(&c10);
+                async move {
+                    let id = ctrl.decode_id(&id)?;
+                    ctrl.get_related(id.into(), query, req).await
+                }
             }))
     );
 }
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+/// An instant parsed from a date query parameter, normalized to UTC
+///
+/// Accepted forms:
+/// - RFC 3339 timestamps, with any offset (`2024-05-01T09:30:00+02:00`)
+/// - Naive timestamps, taken as UTC (`2024-05-01T09:30:00`)
+/// - Plain dates, meaning midnight UTC (`2024-05-01`)
+/// - Relative offsets back from now in minutes, hours, days or weeks (`-30m`, `-7d`)
+/// - `now`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateFilter(DateTime<Utc>);
+
+impl DateFilter {
+    /// Parses `input`, resolving relative forms against `now`
+    ///
+    /// # Errors
+    /// Returns a message describing the accepted forms when `input` matches none of them.
+    pub fn parse(input: &str, now: DateTime<Utc>) -> Result<Self, String> {
+        let input = input.trim();
+
+        if input.eq_ignore_ascii_case("now") {
+            return Ok(Self(now));
+        }
+        if let Some(offset) = input.strip_prefix('-') {
+            return Self::parse_relative(offset)
+                .map(|ago| Self(now - ago))
+                .ok_or_else(|| Self::invalid(input));
+        }
+        if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+            return Ok(Self(at.with_timezone(&Utc)));
+        }
+        if let Ok(at) = NaiveDateTime::parse_from_str(input, "%Y-%m-%dT%H:%M:%S") {
+            return Ok(Self(Utc.from_utc_datetime(&at)));
+        }
+        if let Ok(day) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+            return Ok(Self(Utc.from_utc_datetime(&day.and_time(NaiveTime::MIN))));
+        }
+
+        Err(Self::invalid(input))
+    }
+
+    /// Returns the instant this filter refers to
+    pub fn instant(self) -> DateTime<Utc> {
+        self.0
+    }
+
+    fn parse_relative(offset: &str) -> Option<Duration> {
+        let unit = offset.chars().last()?;
+        let amount: i64 = offset[..offset.len() - unit.len_utf8()].parse().ok()?;
+        match unit {
+            'm' => Duration::try_minutes(amount),
+            'h' => Duration::try_hours(amount),
+            'd' => Duration::try_days(amount),
+            'w' => Duration::try_weeks(amount),
+            _ => None,
+        }
+    }
+
+    fn invalid(input: &str) -> String {
+        format!(
+            "invalid date '{}': expected RFC 3339, YYYY-MM-DD, a relative offset like -7d, or now",
+            input
+        )
+    }
+}
+
+impl From<DateTime<Utc>> for DateFilter {
+    fn from(at: DateTime<Utc>) -> Self {
+        Self(at)
+    }
+}
+
+impl fmt::Display for DateFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339())
+    }
+}
+
+impl<'de> Deserialize<'de> for DateFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let input = String::deserialize(deserializer)?;
+        Self::parse(&input, Utc::now()).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 16, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_absolute() {
+        let expected = Utc.with_ymd_and_hms(2024, 5, 1, 9, 30, 0).unwrap();
+        assert_eq!(DateFilter::parse("2024-05-01T09:30:00Z", now()).unwrap().instant(), expected);
+        assert_eq!(DateFilter::parse("2024-05-01T09:30:00", now()).unwrap().instant(), expected);
+        assert_eq!(
+            DateFilter::parse("2024-05-01", now()).unwrap().instant(),
+            Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        assert_eq!(DateFilter::parse("now", now()).unwrap().instant(), now());
+        assert_eq!(DateFilter::parse("-30m", now()).unwrap().instant(), now() - Duration::minutes(30));
+        assert_eq!(DateFilter::parse("-12h", now()).unwrap().instant(), now() - Duration::hours(12));
+        assert_eq!(DateFilter::parse("-7d", now()).unwrap().instant(), now() - Duration::days(7));
+        assert_eq!(DateFilter::parse("-2w", now()).unwrap().instant(), now() - Duration::weeks(2));
+        assert!(DateFilter::parse("-7y", now()).is_err());
+        assert!(DateFilter::parse("-d", now()).is_err());
+    }
+
+    #[test]
+    fn test_parse_timezone_offset_normalizes_to_utc() {
+        assert_eq!(
+            DateFilter::parse("2024-05-01T09:30:00+02:00", now()).unwrap().instant(),
+            Utc.with_ymd_and_hms(2024, 5, 1, 7, 30, 0).unwrap()
+        );
+        assert_eq!(
+            DateFilter::parse("2024-04-30T22:00:00-05:00", now()).unwrap().instant(),
+            Utc.with_ymd_and_hms(2024, 5, 1, 3, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_rejects_garbage() {
+        let parsed: Result<DateFilter, _> = serde_json::from_str("\"yesterday-ish\"");
+        assert!(parsed.is_err());
+        let parsed: DateFilter = serde_json::from_str("\"2024-05-01\"").unwrap();
+        assert_eq!(parsed.instant(), Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+    }
+}
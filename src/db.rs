@@ -1,13 +1,17 @@
 use sea_orm::{
-    Database, DatabaseConnection,
-    DbErr, EntityTrait, Set, ActiveModelTrait,
-    Condition, QueryFilter, PaginatorTrait, QuerySelect, ColumnTrait,
-    QueryOrder,
+    ConnectOptions, Database, DatabaseConnection,
+    DbErr, EntityTrait, Set, ActiveModelTrait, ActiveModelBehavior,
+    QueryFilter, ColumnTrait,
+    QueryOrder, Order, Select,
 };
-use serde::Deserialize;
-use chrono::Utc;
+use serde::{Deserialize, Deserializer};
+use std::str::FromStr;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc};
 use migration::MigratorTrait;
 
+use crate::config::Config;
+
+use crate::date_filter::DateFilter;
 use crate::entity::video::{self, Entity as Video, Model, ActiveModel};
 
 #[derive(Debug, Deserialize)]
@@ -17,6 +21,253 @@ pub struct VideoQuery {
     pub search: Option<String>,
     pub order_by: Option<String>,
     pub order_direction: Option<String>,
+    /// How `search` is matched against the title and `youtube_id`; defaults to `contains`
+    pub search_mode: Option<SearchMode>,
+    /// Compare `search` and the matched fields after lowercasing them
+    pub case_insensitive: Option<bool>,
+    /// Match `search` against whole words of the title instead of any substring
+    pub whole_word: Option<bool>,
+    /// Only include videos created at or after this instant
+    pub created_after: Option<DateFilter>,
+    /// Only include videos created at or before this instant
+    pub created_before: Option<DateFilter>,
+    /// Only include videos carrying this tag
+    pub tag: Option<String>,
+    /// Only include videos with one of these ids, given as `ids=1,2,3`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub ids: Option<Vec<i32>>,
+    /// Only include videos with one of these YouTube ids, given as `youtube_ids=abc,def`
+    #[serde(default, deserialize_with = "comma_separated")]
+    pub youtube_ids: Option<Vec<String>>,
+    /// Only include videos in this publication state
+    pub status: Option<video::VideoStatus>,
+    /// Canned time bucket resolved server-side into `created_after`
+    pub period: Option<Period>,
+    /// Also report how many rows match when soft-deleted videos are counted
+    pub include_deleted_total: Option<bool>,
+    /// Keyset cursor: return the page after this position (empty for the first page).
+    /// With `cursor=true` this is the id of the last video already seen instead
+    pub after: Option<String>,
+    /// Keyset cursor: return the page before this position
+    pub before: Option<String>,
+    /// Match the words of `search` against the title and description with the
+    /// database's full-text index, most relevant first; falls back to the
+    /// regular search where no index is available. Results carry no `matched_fields`
+    pub fts: Option<bool>,
+    /// Page by id: return videos with an id greater than `after`, ordered by id
+    /// ascending, without counting the total. Stable while rows are inserted
+    pub cursor: Option<bool>,
+}
+
+/// Parses a comma-separated query value such as `1,2,3` into a list
+///
+/// Blank items are skipped, so `1,,2,` is the same as `1,2`.
+fn comma_separated<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let input = String::deserialize(deserializer)?;
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            item.parse()
+                .map_err(|e| serde::de::Error::custom(format!("invalid list item '{}': {}", item, e)))
+        })
+        .collect::<Result<Vec<T>, _>>()
+        .map(Some)
+}
+
+/// How the `search` list filter is matched against video titles and YouTube IDs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// The title contains the search term anywhere
+    #[default]
+    Contains,
+    /// The title starts with the search term
+    Prefix,
+    /// The title equals the search term
+    Exact,
+}
+
+/// Calendar buckets accepted by the `period` list filter, in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Today,
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    /// Returns the first instant of the bucket containing `now`
+    /// 
+    /// Weeks start on Monday, following ISO 8601.
+    pub fn start(self, now: DateTime<Utc>) -> DateTime<Utc> {
+        let today = now.date_naive();
+        let first_day = match self {
+            Period::Today => today,
+            Period::Week => today - Duration::days(today.weekday().num_days_from_monday() as i64),
+            Period::Month => today.with_day(1).unwrap_or(today),
+            Period::Year => today.with_ordinal(1).unwrap_or(today),
+        };
+        Utc.from_utc_datetime(&first_day.and_time(NaiveTime::MIN))
+    }
+}
+
+/// Columns a video list can be ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    Id,
+    Title,
+    YoutubeId,
+    CreatedAt,
+    UpdatedAt,
+    Position,
+}
+
+impl SortKey {
+    /// Accepted `order_by` names, in the order they're listed in errors
+    pub const NAMES: &'static str = "id, title, youtube_id, created_at, updated_at or position";
+
+    /// Parses an `order_by` column name, ignoring ASCII case
+    pub fn parse(name: &str) -> Option<SortKey> {
+        [
+            ("id", SortKey::Id),
+            ("title", SortKey::Title),
+            ("youtube_id", SortKey::YoutubeId),
+            ("created_at", SortKey::CreatedAt),
+            ("updated_at", SortKey::UpdatedAt),
+            ("position", SortKey::Position),
+        ]
+        .into_iter()
+        .find(|(candidate, _)| name.eq_ignore_ascii_case(candidate))
+        .map(|(_, key)| key)
+    }
+
+    /// The column this key orders by
+    pub fn column(self) -> video::Column {
+        match self {
+            SortKey::Id => video::Column::Id,
+            SortKey::Title => video::Column::Title,
+            SortKey::YoutubeId => video::Column::YoutubeId,
+            SortKey::CreatedAt => video::Column::CreatedAt,
+            SortKey::UpdatedAt => video::Column::UpdatedAt,
+            SortKey::Position => video::Column::Position,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    fn order(self) -> Order {
+        match self {
+            SortDirection::Asc => Order::Asc,
+            SortDirection::Desc => Order::Desc,
+        }
+    }
+}
+
+/// One key of a list ordering
+///
+/// Lists are ordered by one or more sorts and then by `id` in the direction
+/// of the last one, so ties never reorder between requests. Both
+/// `(created_at, id)` and `(title, id)` are covered by indexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Sort {
+    pub key: SortKey,
+    pub direction: SortDirection,
+}
+
+impl Sort {
+    /// Newest first, the ordering used when nothing else is configured
+    pub const NEWEST_FIRST: Sort = Sort {
+        key: SortKey::CreatedAt,
+        direction: SortDirection::Desc,
+    };
+
+    /// Resolves `order_by`/`order_direction` query values against `default`
+    ///
+    /// Omitted values fall back to the default's key and direction.
+    ///
+    /// # Errors
+    /// Returns a message naming the accepted values when either is unknown.
+    pub fn resolve(order_by: Option<&str>, order_direction: Option<&str>, default: Sort) -> Result<Sort, String> {
+        let key = match order_by.map(str::trim) {
+            None => default.key,
+            Some(value) => SortKey::parse(value).ok_or_else(|| Self::invalid_key(value))?,
+        };
+        let direction = Self::resolve_direction(order_direction, default)?;
+        Ok(Sort { key, direction })
+    }
+
+    /// Resolves a possibly multi-key `order_by` such as `title,-created_at`
+    ///
+    /// A leading `-` sorts that key descending; other keys use
+    /// `order_direction`, falling back to the default's direction. An omitted
+    /// `order_by` yields just `default`'s key.
+    ///
+    /// # Errors
+    /// Returns a message naming the accepted values when a key or the
+    /// direction is unknown, or when a key is listed twice.
+    pub fn resolve_list(order_by: Option<&str>, order_direction: Option<&str>, default: Sort) -> Result<Vec<Sort>, String> {
+        let direction = Self::resolve_direction(order_direction, default)?;
+        let Some(order_by) = order_by else {
+            return Ok(vec![Sort { key: default.key, direction }]);
+        };
+
+        let mut sorts: Vec<Sort> = Vec::new();
+        for item in order_by.split(',').map(str::trim) {
+            let (name, direction) = match item.strip_prefix('-') {
+                Some(name) => (name.trim(), SortDirection::Desc),
+                None => (item, direction),
+            };
+            let key = SortKey::parse(name).ok_or_else(|| Self::invalid_key(item))?;
+            if sorts.iter().any(|sort| sort.key == key) {
+                return Err(format!("order_by lists '{}' more than once", name));
+            }
+            sorts.push(Sort { key, direction });
+        }
+        Ok(sorts)
+    }
+
+    fn resolve_direction(order_direction: Option<&str>, default: Sort) -> Result<SortDirection, String> {
+        match order_direction.map(str::trim) {
+            None => Ok(default.direction),
+            Some(value) if value.eq_ignore_ascii_case("asc") => Ok(SortDirection::Asc),
+            Some(value) if value.eq_ignore_ascii_case("desc") => Ok(SortDirection::Desc),
+            Some(value) => Err(format!("invalid order_direction '{}': expected asc or desc", value)),
+        }
+    }
+
+    fn invalid_key(value: &str) -> String {
+        format!("invalid order_by '{}': expected {}", value, SortKey::NAMES)
+    }
+
+    /// Orders `select` by each of `sorts`, then by `id` as the tie-breaker
+    pub fn apply(select: Select<Video>, sorts: &[Sort]) -> Select<Video> {
+        let mut select = select;
+        for sort in sorts {
+            select = select.order_by(sort.key.column(), sort.direction.order());
+        }
+        if sorts.iter().all(|sort| sort.key != SortKey::Id) {
+            let direction = sorts.last().map_or(SortDirection::Asc, |sort| sort.direction);
+            select = select.order_by(video::Column::Id, direction.order());
+        }
+        select
+    }
 }
 
 impl Default for VideoQuery {
@@ -27,24 +278,40 @@ impl Default for VideoQuery {
             search: None,
             order_by: Some("created_at".to_string()),
             order_direction: Some("desc".to_string()),
+            search_mode: None,
+            case_insensitive: None,
+            whole_word: None,
+            created_after: None,
+            created_before: None,
+            tag: None,
+            ids: None,
+            youtube_ids: None,
+            status: None,
+            period: None,
+            include_deleted_total: None,
+            after: None,
+            before: None,
+            fts: None,
+            cursor: None,
         }
     }
 }
 
-pub struct PaginatedVideos {
-    pub videos: Vec<Model>,
-    pub total: u64,
-    pub page: u64,
-    pub per_page: u64,
-    pub total_pages: u64,
-}
-
-pub async fn init_db() -> DatabaseConnection {
-    let db = Database::connect("sqlite::memory:").await.unwrap();
+/// Opens the pool for `config.database_url` and runs pending migrations
+pub async fn init_db(config: &Config) -> DatabaseConnection {
+    let mut options = ConnectOptions::new(config.database_url.clone());
+    options
+        .max_connections(config.db_max_connections)
+        .min_connections(config.db_min_connections)
+        .connect_timeout(std::time::Duration::from_secs(config.db_connect_timeout_secs))
+        .idle_timeout(std::time::Duration::from_secs(config.db_idle_timeout_secs));
+    let db = Database::connect(options).await.unwrap();
     migration::Migrator::up(&db, None).await.unwrap();
     db
 }
 
+/// Inserts a video; `created_at` and `updated_at` are both stamped with
+/// the same instant by `ActiveModelBehavior`
 pub async fn create_video(
     db: &DatabaseConnection,
     title: String,
@@ -53,110 +320,12 @@ pub async fn create_video(
     let video = ActiveModel {
         title: Set(title),
         youtube_id: Set(youtube_id),
-        ..Default::default()
+        ..ActiveModelBehavior::new()
     };
 
     video.insert(db).await
 }
 
-pub async fn list_videos(
-    db: &DatabaseConnection,
-    query: VideoQuery,
-) -> Result<PaginatedVideos, DbErr> {
-    let page = query.page.unwrap_or(1);
-    let per_page = query.per_page.unwrap_or(10);
-    let search = query.search.unwrap_or_default();
-    let order_by = query.order_by.unwrap_or_else(|| "created_at".to_string());
-    let order_direction = query.order_direction.unwrap_or_else(|| "desc".to_string());
-
-    let mut condition = Condition::all();
-    
-    // Only include non-deleted videos
-    condition = condition.add(video::Column::DeletedAt.is_null());
-
-    // Add search condition if search is not empty
-    if !search.is_empty() {
-        condition = condition
-            .add(video::Column::Title.contains(&search))
-            .add(video::Column::YoutubeId.contains(&search));
-    }
-
-    let mut select = Video::find().filter(condition);
-
-    // Add ordering
-    let order_by_col = match order_by.as_str() {
-        "title" => video::Column::Title,
-        "youtube_id" => video::Column::YoutubeId,
-        _ => video::Column::CreatedAt,
-    };
-
-    select = match order_direction.as_str() {
-        "asc" => select.order_by_asc(order_by_col),
-        _ => select.order_by_desc(order_by_col),
-    };
-
-    // Get total count
-    let total = select.clone().count(db).await?;
-
-    // Calculate pagination
-    let total_pages = (total + per_page - 1) / per_page;
-    let offset = (page - 1) * per_page;
-
-    // Get paginated videos
-    let videos = select
-        .offset(offset)
-        .limit(per_page)
-        .all(db)
-        .await?;
-
-    Ok(PaginatedVideos {
-        videos,
-        total,
-        page,
-        per_page,
-        total_pages,
-    })
-}
-
-pub async fn get_video(
-    db: &DatabaseConnection,
-    id: i32,
-) -> Result<Option<Model>, DbErr> {
-    Video::find_by_id(id)
-        .filter(video::Column::DeletedAt.is_null())
-        .one(db)
-        .await
-}
-
-pub async fn update_video(
-    db: &DatabaseConnection,
-    id: i32,
-    title: Option<String>,
-    youtube_id: Option<String>,
-) -> Result<Option<Model>, DbErr> {
-    let video = Video::find_by_id(id)
-        .filter(video::Column::DeletedAt.is_null())
-        .one(db)
-        .await?;
-
-    if let Some(video) = video {
-        let mut active_model: ActiveModel = video.clone().into();
-
-        if let Some(title) = title {
-            active_model.title = Set(title);
-        }
-
-        if let Some(youtube_id) = youtube_id {
-            active_model.youtube_id = Set(youtube_id);
-        }
-
-        let updated_video = active_model.update(db).await?;
-        Ok(Some(updated_video))
-    } else {
-        Ok(None)
-    }
-}
-
 pub async fn delete_video(
     db: &DatabaseConnection,
     id: i32,
@@ -1,2 +1,4 @@
+pub mod tag;
 pub mod video;
+pub mod video_tag;
 pub use video::*;
@@ -0,0 +1,40 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "tags")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// Trimmed, lowercased tag name
+    #[sea_orm(unique)]
+    pub name: String,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::video_tag::Entity")]
+    VideoTag,
+}
+
+impl Related<super::video_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoTag.def()
+    }
+}
+
+impl Related<super::video::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_tag::Relation::Video.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_tag::Relation::Tag.def().rev())
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// Returns the stored form of a tag name
+pub fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
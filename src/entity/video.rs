@@ -1,5 +1,23 @@
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Query;
+use sea_orm::{DbBackend, Statement};
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Publication state of a video; new videos start as drafts
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, ToSchema)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "lowercase")]
+pub enum VideoStatus {
+    #[default]
+    #[sea_orm(string_value = "draft")]
+    Draft,
+    #[sea_orm(string_value = "published")]
+    Published,
+    #[sea_orm(string_value = "archived")]
+    Archived,
+}
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
 #[sea_orm(table_name = "videos")]
@@ -7,14 +25,90 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub title: String,
+    /// `title` trimmed and lowercased, kept in sync on every save
+    pub title_normalized: String,
     pub youtube_id: String,
+    /// Free-form longer text; `NULL` for videos created before it existed
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    /// Times the video was viewed; only changed by atomic increments
+    #[sea_orm(default_value = 0)]
+    pub view_count: i64,
+    #[sea_orm(default_value = "draft")]
+    pub status: VideoStatus,
+    /// 1-based place in the manual ordering; see `VideoRepository::reorder`
+    /// 
+    /// Inserts are appended by `append_position`; 0 until then.
+    #[sea_orm(default_value = 0)]
+    pub position: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
+/// Key of the Postgres advisory lock that serializes `append_position`
+const POSITION_LOCK_KEY: i64 = 0x7669_6465_6f73;
+
+/// Moves video `id` to one past the last other live video, returning its new position
+/// 
+/// Runs as a single `UPDATE` once the row is written, so inside the
+/// transaction holding the insert it sees every committed position. On
+/// Postgres an advisory lock held until commit serializes concurrent appends.
+pub async fn append_position<C: ConnectionTrait>(db: &C, id: i32) -> Result<i32, DbErr> {
+    let backend = db.get_database_backend();
+    if backend == DbBackend::Postgres {
+        db.execute(Statement::from_string(
+            backend,
+            format!("SELECT pg_advisory_xact_lock({})", POSITION_LOCK_KEY),
+        ))
+        .await?;
+    }
+
+    let stmt = Query::update()
+        .table(Entity)
+        .value(
+            Column::Position,
+            Expr::cust_with_values(
+                r#"(SELECT COALESCE(MAX("position"), 0) + 1 FROM "videos" WHERE "deleted_at" IS NULL AND "id" <> $1)"#,
+                [id],
+            ),
+        )
+        .and_where(Column::Id.eq(id))
+        .returning_col(Column::Position)
+        .to_owned();
+    let row = db
+        .query_one(backend.build(&stmt))
+        .await?
+        .ok_or_else(|| DbErr::RecordNotFound(format!("video {}", id)))?;
+    row.try_get("", "position")
+}
+
+/// Returns the key stored in `title_normalized` for `title`
+pub fn normalize_title_key(title: &str) -> String {
+    title.trim().to_lowercase()
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::video_tag::Entity")]
+    VideoTag,
+}
+
+impl Related<super::video_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VideoTag.def()
+    }
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::video_tag::Relation::Tag.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::video_tag::Relation::Video.def().rev())
+    }
+}
 
 #[async_trait::async_trait]
 impl ActiveModelBehavior for ActiveModel {
@@ -32,10 +126,26 @@ impl ActiveModelBehavior for ActiveModel {
         C: ConnectionTrait,
     {
         let mut this = self;
-        this.updated_at = sea_orm::Set(Utc::now());
+        if let sea_orm::ActiveValue::Set(title) = &this.title {
+            this.title_normalized = sea_orm::Set(normalize_title_key(title));
+        }
+        // One reading, so a fresh row's created_at and updated_at are equal
+        let now = Utc::now();
+        this.updated_at = sea_orm::Set(now);
         if insert {
-            this.created_at = sea_orm::Set(Utc::now());
+            this.created_at = sea_orm::Set(now);
         }
         Ok(this)
     }
+
+    async fn after_save<C>(model: Model, db: &C, insert: bool) -> Result<Model, DbErr>
+    where
+        C: ConnectionTrait,
+    {
+        let mut model = model;
+        if insert && model.position == 0 {
+            model.position = append_position(db, model.id).await?;
+        }
+        Ok(model)
+    }
 }
@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+
+/// Links a video to one of its tags
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "video_tags")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub video_id: i32,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub tag_id: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::video::Entity",
+        from = "Column::VideoId",
+        to = "super::video::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Video,
+    #[sea_orm(
+        belongs_to = "super::tag::Entity",
+        from = "Column::TagId",
+        to = "super::tag::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Tag,
+}
+
+impl Related<super::video::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Video.def()
+    }
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Tag.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,13 @@
+use chrono::{DateTime, Utc};
+use ntex::http::{header, StatusCode};
+use ntex::web::error::JsonPayloadError;
 use ntex::web::{HttpResponse, WebResponseError, HttpRequest};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use thiserror::Error;
+use validator::{ValidationError, ValidationErrors};
+
+use crate::middleware::request_id::request_id;
 
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -9,6 +16,21 @@ pub enum AppError {
     
     #[error("Validation error: {0}")]
     Validation(String),
+
+    /// Field rule failures, rendered as `{ "error": message, "fields": { field: [problems] } }`
+    #[error("Validation error: {message}")]
+    ValidationDetailed {
+        message: String,
+        fields: BTreeMap<String, Vec<String>>,
+    },
+
+    /// Like `ValidationDetailed`, also echoing the offending values
+    #[error("Validation error: {message}")]
+    InvalidValue {
+        message: String,
+        fields: BTreeMap<String, Vec<String>>,
+        received: BTreeMap<String, String>,
+    },
     
     #[error("Not found: {0}")]
     NotFound(String),
@@ -18,40 +40,289 @@ pub enum AppError {
     
     #[error("Internal server error: {0}")]
     Internal(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// A conditional request whose `If-Match` no longer matches
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    /// A write that must be conditional arrived without `If-Match`
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
+
+    /// A request body above the configured size limit
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Too many requests: {message}")]
+    TooManyRequests {
+        message: String,
+        reset_at: DateTime<Utc>,
+    },
 }
 
-impl WebResponseError for AppError {
-    fn error_response(&self, _: &HttpRequest) -> HttpResponse {
+impl AppError {
+    /// Machine-readable identifier sent as `code` in every error body
+    pub fn code(&self) -> &'static str {
         match self {
+            AppError::Database(_) | AppError::Internal(_) => "internal_error",
+            AppError::Validation(_)
+            | AppError::ValidationDetailed { .. }
+            | AppError::InvalidValue { .. } => "validation_failed",
+            AppError::NotFound(_) => "not_found",
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Conflict(_) => "conflict",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::PreconditionFailed(_) => "precondition_failed",
+            AppError::PreconditionRequired(_) => "precondition_required",
+            AppError::PayloadTooLarge(_) => "payload_too_large",
+            AppError::TooManyRequests { .. } => "rate_limited",
+        }
+    }
+}
+
+/// Headline message of a failed field validation
+pub const VALIDATION_FAILED: &str = "validation failed";
+
+/// Describes one rule failure, e.g. `length must be >= 1`
+fn describe(error: &ValidationError) -> String {
+    if let Some(message) = &error.message {
+        return message.to_string();
+    }
+    if error.code != "length" {
+        return format!("failed {} check", error.code);
+    }
+
+    let param = |key: &str| error.params.get(key).and_then(Value::as_u64);
+    let len = error.params.get("value").and_then(|value| match value {
+        Value::String(s) => Some(s.chars().count() as u64),
+        Value::Array(items) => Some(items.len() as u64),
+        _ => None,
+    });
+    match (param("equal"), param("min"), param("max")) {
+        (Some(n), _, _) => format!("length must be {}", n),
+        (None, Some(min), Some(max)) if min == max => format!("length must be {}", min),
+        (None, Some(min), max) if max.is_none() || len.is_none_or(|len| len < min) => {
+            format!("length must be >= {}", min)
+        }
+        (None, _, Some(max)) => format!("length must be <= {}", max),
+        _ => "invalid length".to_string(),
+    }
+}
+
+/// Groups rule failures by field, each as human-readable messages
+pub fn field_messages(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, errors)| (field.to_string(), errors.iter().map(describe).collect()))
+        .collect()
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::ValidationDetailed {
+            message: VALIDATION_FAILED.to_string(),
+            fields: field_messages(&errors),
+        }
+    }
+}
+
+/// Maps JSON body extraction failures, see `controllers::body::JsonBody`
+impl From<JsonPayloadError> for AppError {
+    fn from(error: JsonPayloadError) -> Self {
+        match error {
+            JsonPayloadError::Overflow => AppError::PayloadTooLarge("payload too large".to_string()),
+            JsonPayloadError::ContentType => {
+                AppError::BadRequest("content type must be application/json".to_string())
+            }
+            JsonPayloadError::Deserialize(e) => AppError::BadRequest(format!("invalid JSON body: {}", e)),
+            JsonPayloadError::Payload(e) => AppError::BadRequest(format!("could not read request body: {}", e)),
+        }
+    }
+}
+
+impl WebResponseError for AppError {
+    /// Renders the error as `{ "error": ..., "code": ... }`, adding the
+    /// `request_id` assigned by `AssignRequestId` when there is one
+    fn error_response(&self, req: &HttpRequest) -> HttpResponse {
+        let (mut builder, mut body) = match self {
             AppError::Database(e) => {
                 tracing::error!("Database error: {}", e);
-                let error = json!({ "error": "Internal server error" });
-                HttpResponse::InternalServerError()
-                    .json(&error)
+                (HttpResponse::InternalServerError(), json!({ "error": "Internal server error" }))
             }
             AppError::Validation(msg) => {
-                let error = json!({ "error": msg });
-                HttpResponse::BadRequest()
-                    .json(&error)
+                (HttpResponse::BadRequest(), json!({ "error": msg }))
+            }
+            AppError::ValidationDetailed { message, fields } => {
+                (HttpResponse::BadRequest(), json!({ "error": message, "fields": fields }))
+            }
+            AppError::InvalidValue { message, fields, received } => {
+                (HttpResponse::BadRequest(), json!({ "error": message, "fields": fields, "received": received }))
             }
             AppError::NotFound(msg) => {
-                let error = json!({ "error": msg });
-                HttpResponse::NotFound()
-                    .json(&error)
+                (HttpResponse::NotFound(), json!({ "error": msg }))
             }
             AppError::BadRequest(msg) => {
-                let error = json!({ "error": msg });
-                HttpResponse::BadRequest()
-                    .json(&error)
+                (HttpResponse::BadRequest(), json!({ "error": msg }))
             }
             AppError::Internal(msg) => {
                 tracing::error!("Internal error: {}", msg);
-                let error = json!({ "error": "Internal server error" });
-                HttpResponse::InternalServerError()
-                    .json(&error)
+                (HttpResponse::InternalServerError(), json!({ "error": "Internal server error" }))
+            }
+            AppError::Conflict(msg) => {
+                (HttpResponse::Conflict(), json!({ "error": msg }))
+            }
+            AppError::Unauthorized(msg) => {
+                (HttpResponse::Unauthorized(), json!({ "error": msg }))
+            }
+            AppError::Forbidden(msg) => {
+                (HttpResponse::Forbidden(), json!({ "error": msg }))
+            }
+            AppError::PreconditionFailed(msg) => {
+                (HttpResponse::PreconditionFailed(), json!({ "error": msg }))
+            }
+            AppError::PreconditionRequired(msg) => {
+                (HttpResponse::build(StatusCode::PRECONDITION_REQUIRED), json!({ "error": msg }))
+            }
+            AppError::PayloadTooLarge(msg) => {
+                (HttpResponse::PayloadTooLarge(), json!({ "error": msg }))
             }
+            AppError::TooManyRequests { message, reset_at } => {
+                let retry_after = (*reset_at - Utc::now()).num_seconds().max(0);
+                let mut builder = HttpResponse::TooManyRequests();
+                builder.header(header::RETRY_AFTER, retry_after.to_string());
+                (builder, json!({ "error": message, "reset_at": reset_at }))
+            }
+        };
+
+        body["code"] = json!(self.code());
+        if let Some(id) = request_id(req) {
+            body["request_id"] = json!(id);
         }
+        builder.json(&body)
     }
 }
 
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::StatusCode;
+    use ntex::web::test::TestRequest;
+    use validator::Validate;
+
+    fn status(error: AppError) -> StatusCode {
+        error.error_response(&TestRequest::default().to_http_request()).status()
+    }
+
+    fn body(error: AppError) -> serde_json::Value {
+        let resp = error.error_response(&TestRequest::default().to_http_request());
+        match resp.body().as_ref() {
+            Some(ntex::http::body::Body::Bytes(bytes)) => serde_json::from_slice(bytes).unwrap(),
+            other => panic!("expected a JSON body, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_conflict_body_carries_code() {
+        assert_eq!(status(AppError::Conflict("taken".to_string())), StatusCode::CONFLICT);
+        assert_eq!(
+            body(AppError::Conflict("taken".to_string())),
+            serde_json::json!({ "error": "taken", "code": "conflict" })
+        );
+    }
+
+    #[test]
+    fn test_every_body_carries_its_code() {
+        assert_eq!(body(AppError::NotFound("gone".to_string()))["code"], "not_found");
+        assert_eq!(body(AppError::Validation("bad".to_string()))["code"], "validation_failed");
+        assert_eq!(body(AppError::Internal("oops".to_string()))["code"], "internal_error");
+    }
+
+    #[test]
+    fn test_each_variant_maps_to_its_status() {
+        let message = || "message".to_string();
+        assert_eq!(status(AppError::Database(sea_orm::DbErr::Custom(message()))), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status(AppError::Validation(message())), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            status(AppError::ValidationDetailed { message: message(), fields: BTreeMap::new() }),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(AppError::InvalidValue { message: message(), fields: BTreeMap::new(), received: BTreeMap::new() }),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(status(AppError::NotFound(message())), StatusCode::NOT_FOUND);
+        assert_eq!(status(AppError::BadRequest(message())), StatusCode::BAD_REQUEST);
+        assert_eq!(status(AppError::Internal(message())), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(status(AppError::Conflict(message())), StatusCode::CONFLICT);
+        assert_eq!(status(AppError::Unauthorized(message())), StatusCode::UNAUTHORIZED);
+        assert_eq!(status(AppError::Forbidden(message())), StatusCode::FORBIDDEN);
+        assert_eq!(status(AppError::PreconditionFailed(message())), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(status(AppError::PreconditionRequired(message())), StatusCode::PRECONDITION_REQUIRED);
+        assert_eq!(
+            status(AppError::TooManyRequests { message: message(), reset_at: Utc::now() }),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_convert_to_field_messages() {
+        let errors = crate::api::CreateVideoRequest {
+            title: String::new(),
+            youtube_id: "short".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(
+            body(AppError::from(errors)),
+            serde_json::json!({
+                "error": "validation failed",
+                "code": "validation_failed",
+                "fields": {
+                    "title": ["length must be >= 1"],
+                    "youtube_id": ["length must be 11"],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_too_long_value_reports_max() {
+        let errors = crate::api::CreateVideoRequest {
+            title: "x".repeat(101),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(field_messages(&errors)["title"], vec!["length must be <= 100"]);
+    }
+
+    #[test]
+    fn test_json_payload_errors_are_mapped() {
+        assert_eq!(status(JsonPayloadError::Overflow.into()), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let parse_error = serde_json::from_str::<Value>("{").unwrap_err();
+        match AppError::from(JsonPayloadError::Deserialize(parse_error)) {
+            AppError::BadRequest(message) => assert!(message.starts_with("invalid JSON body: "), "{}", message),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+    }
+}
@@ -2,8 +2,11 @@ pub mod api;
 pub mod app;
 pub mod config;
 pub mod controllers;
+pub mod date_filter;
 pub mod db;
 pub mod entity;
 pub mod error;
+pub mod middleware;
+pub mod openapi;
 pub mod repositories;
 pub mod services;
@@ -1,34 +1,45 @@
-use ntex::web;
-use tracing::{info, Level};
-use tracing_subscriber::FmtSubscriber;
+#![recursion_limit = "512"]
 
-use crate::controllers::video_controller::VideoController;
-use crate::repositories::video_repository::VideoRepository;
-use crate::services::video_service::VideoService;
+use ntex::web;
+use tracing::info;
 
-mod api;
-mod app;
-mod config;
-mod controllers;
-mod db;
-mod entity;
-mod error;
-mod repositories;
-mod services;
+use ntex_api::config;
+use ntex_api::app;
+use ntex_api::controllers::video_controller::VideoController;
+use ntex_api::db;
+use ntex_api::middleware::access_log::AccessLog;
+use ntex_api::middleware::api_key::RequireApiKey;
+use ntex_api::middleware::compression::Compression;
+use ntex_api::middleware::debug_sql::{self, DebugSql};
+use ntex_api::middleware::https::EnforceHttps;
+use ntex_api::middleware::metrics::{Metrics, RecordMetrics};
+use ntex_api::middleware::pre_ping::PrePing;
+use ntex_api::middleware::request_id::AssignRequestId;
+use ntex_api::repositories::video_repository::VideoRepository;
+use ntex_api::services::video_service::VideoService;
 
 #[ntex::main]
 async fn main() -> std::io::Result<()> {
-    // Initialize logging
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .init();
-
     // Load configuration
-    let config = config::Config::from_env();
+    let config = config::Config::from_env()
+        .and_then(|config| config.validate().map(|_| config))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+    // Initialize logging
+    config::init_tracing(&config).map_err(std::io::Error::other)?;
     info!("Starting server with config: {:?}", config);
     
     // Initialize database
-    let db = db::init_db().await;
+    let mut db = db::init_db(&config).await;
+    let metrics = Metrics::new();
+    let query_metrics = metrics.clone();
+    let debug_sql = config.debug_sql;
+    db.set_metric_callback(move |info| {
+        query_metrics.record_query(info);
+        if debug_sql {
+            debug_sql::record_statement(info);
+        }
+    });
     info!("Database initialized");
     
     let addr = format!("{}:{}", config.server_host, config.server_port);
@@ -36,13 +47,26 @@ async fn main() -> std::io::Result<()> {
 
     // Initialize repository, service and controller
     let repository = VideoRepository::new(db.clone());
-    let service = VideoService::new(repository);
+    let service = VideoService::with_config(repository, config.clone());
+    service
+        .sync_title_uniqueness()
+        .await
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
     let controller = VideoController::new(service);
 
     web::HttpServer::new(move || {
         web::App::new()
+            .wrap(Compression::default())
+            .wrap(RequireApiKey::new(config.api_key.clone()))
+            .wrap(DebugSql::new(config.debug_sql))
+            .wrap(PrePing::new(db.clone(), config.pre_ping))
+            .wrap(EnforceHttps::new(config.hsts_max_age, config.https_redirect))
+            .wrap(RecordMetrics::new(metrics.clone()))
+            .wrap(AccessLog::new(config.redacted_query_keys.clone()))
+            .wrap(AssignRequestId)
             .state(db.clone())
-            .configure(|cfg| controllers::video_controller::config(cfg, controller.clone()))
+            .state(metrics.clone())
+            .configure(|cfg| app::config_app(cfg, controller.clone(), &config))
     })
     .bind(&addr)?
     .run()
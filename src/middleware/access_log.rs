@@ -0,0 +1,180 @@
+use ntex::http::header::{HeaderName, HeaderValue};
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{WebRequest, WebResponse};
+use std::rc::Rc;
+use std::time::Instant;
+
+/// Query parameters redacted from access logs unless configured otherwise
+pub const DEFAULT_REDACTED_QUERY_KEYS: [&str; 2] = ["token", "api_key"];
+
+/// Response header carrying the time spent producing the response, in milliseconds
+pub const RESPONSE_TIME_HEADER: &str = "x-response-time-ms";
+
+/// Replaces the values of `keys` in a raw query string with `***`
+///
+/// Keys are matched case-insensitively; parameter order and every other
+/// value are kept as they were sent.
+pub fn redact_query(query: &str, keys: &[String]) -> String {
+    query
+        .split('&')
+        .map(|pair| {
+            let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+            if keys.iter().any(|redacted| redacted.eq_ignore_ascii_case(key)) {
+                format!("{}=***", key)
+            } else {
+                pair.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Middleware writing one access log line per request
+///
+/// Events are emitted at `INFO` through `tracing` under the `access`
+/// target, with `method`, `path` (including the query), `status` and
+/// `elapsed_ms` fields. The values of sensitive query parameters are
+/// replaced by `***` so tokens never reach the logs. Every response also
+/// carries the elapsed time in `X-Response-Time-Ms`, measured up to the
+/// point the response, serialized body included, leaves the inner
+/// services; streamed bodies are still being sent afterwards.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::access_log::AccessLog;
+///
+/// let app = web::App::new().wrap(AccessLog::new(vec!["token".to_string()]));
+/// ```
+pub struct AccessLog {
+    redacted_keys: Rc<Vec<String>>,
+}
+
+impl AccessLog {
+    /// Creates the middleware, redacting the given query parameter names
+    pub fn new(redacted_keys: Vec<String>) -> Self {
+        Self {
+            redacted_keys: Rc::new(redacted_keys),
+        }
+    }
+}
+
+impl<S> Middleware<S> for AccessLog {
+    type Service = AccessLogMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        AccessLogMiddleware {
+            service,
+            redacted_keys: self.redacted_keys.clone(),
+        }
+    }
+}
+
+pub struct AccessLogMiddleware<S> {
+    service: S,
+    redacted_keys: Rc<Vec<String>>,
+}
+
+impl<S> AccessLogMiddleware<S> {
+    /// Formats the request target with sensitive query values redacted
+    fn target(&self, path: &str, query: &str) -> String {
+        if query.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}?{}", path, redact_query(query, &self.redacted_keys))
+        }
+    }
+}
+
+impl<S, E> Service<WebRequest<E>> for AccessLogMiddleware<S>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let started = Instant::now();
+        let method = req.method().clone();
+        let target = self.target(req.path(), req.query_string());
+
+        let mut resp = ctx.call(&self.service, req).await?;
+        let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+        tracing::info!(
+            target: "access",
+            method = %method,
+            path = %target,
+            status = resp.status().as_u16(),
+            elapsed_ms,
+            "request completed"
+        );
+        if let Ok(value) = HeaderValue::from_str(&format!("{:.3}", elapsed_ms)) {
+            resp.headers_mut().insert(HeaderName::from_static(RESPONSE_TIME_HEADER), value);
+        }
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys() -> Vec<String> {
+        DEFAULT_REDACTED_QUERY_KEYS.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn test_token_value_is_redacted() {
+        assert_eq!(redact_query("token=secret", &keys()), "token=***");
+        assert_eq!(
+            redact_query("page=2&API_KEY=abc&search=cats", &keys()),
+            "page=2&API_KEY=***&search=cats"
+        );
+    }
+
+    #[test]
+    fn test_access_target_keeps_other_params() {
+        let middleware = AccessLog::new(keys()).create(());
+        assert_eq!(
+            middleware.target("/api/v1/videos", "token=secret&page=1"),
+            "/api/v1/videos?token=***&page=1"
+        );
+        assert_eq!(middleware.target("/version", ""), "/version");
+    }
+
+    #[ntex::test]
+    async fn test_response_time_header_is_set() {
+        use ntex::time::{sleep, Millis};
+        use ntex::web::{self, test, App, HttpResponse};
+
+        let app = test::init_service(
+            App::new().wrap(AccessLog::new(keys())).route(
+                "/slow",
+                web::get().to(|| async {
+                    sleep(Millis(20)).await;
+                    HttpResponse::Ok().finish()
+                }),
+            ),
+        )
+        .await;
+
+        let resp = test::call_service(&app, test::TestRequest::get().uri("/slow").to_request()).await;
+        let elapsed_ms: f64 = resp
+            .headers()
+            .get(RESPONSE_TIME_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(elapsed_ms >= 20.0, "{}", elapsed_ms);
+    }
+}
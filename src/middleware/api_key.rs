@@ -0,0 +1,163 @@
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{DefaultError, WebRequest, WebResponse};
+use std::rc::Rc;
+
+use crate::error::AppError;
+use crate::middleware::pre_ping::LIVENESS_PATH;
+
+/// Header carrying the client's API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Paths answered without an API key: the index and the health probes
+pub const DEFAULT_PUBLIC_PATHS: [&str; 3] = ["/", LIVENESS_PATH, "/health/db"];
+
+/// Middleware requiring a matching `X-API-Key` header on every request
+///
+/// Requests with a missing or wrong key get `401` with
+/// `{ "error": "unauthorized" }`. Paths in the public allow-list are always
+/// passed through. Without a configured key the middleware does nothing,
+/// so keyless setups keep working.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::api_key::RequireApiKey;
+///
+/// let app = web::App::new().wrap(RequireApiKey::new(Some("s3cret".to_string())));
+/// ```
+pub struct RequireApiKey {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    key: Option<String>,
+    public_paths: Vec<String>,
+}
+
+impl RequireApiKey {
+    /// Creates the middleware
+    ///
+    /// # Arguments
+    /// * `key` - The accepted API key; requests aren't checked when unset
+    pub fn new(key: Option<String>) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                key,
+                public_paths: DEFAULT_PUBLIC_PATHS.iter().map(|p| p.to_string()).collect(),
+            }),
+        }
+    }
+
+    /// Adds a path that is answered without an API key
+    pub fn allow(mut self, path: &str) -> Self {
+        Rc::get_mut(&mut self.inner)
+            .expect("Multiple copies exist")
+            .public_paths
+            .push(path.to_string());
+        self
+    }
+}
+
+impl<S> Middleware<S> for RequireApiKey {
+    type Service = RequireApiKeyMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        RequireApiKeyMiddleware {
+            service,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+pub struct RequireApiKeyMiddleware<S> {
+    service: S,
+    inner: Rc<Inner>,
+}
+
+impl<S> Service<WebRequest<DefaultError>> for RequireApiKeyMiddleware<S>
+where
+    S: Service<WebRequest<DefaultError>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<DefaultError>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let Some(expected) = self.inner.key.as_deref() else {
+            return ctx.call(&self.service, req).await;
+        };
+        if self.inner.public_paths.iter().any(|path| req.path() == path) {
+            return ctx.call(&self.service, req).await;
+        }
+
+        let given = req
+            .headers()
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok());
+        if given != Some(expected) {
+            return Ok(req.render_error(AppError::Unauthorized("unauthorized".to_string())));
+        }
+
+        ctx.call(&self.service, req).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{self, test, App, HttpResponse};
+
+    async fn call(key: Option<&str>, path: &str, header: Option<&str>) -> (u16, Option<serde_json::Value>) {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequireApiKey::new(key.map(str::to_string)))
+                .route("/", web::get().to(|| async { HttpResponse::Ok().finish() }))
+                .route(LIVENESS_PATH, web::get().to(|| async { HttpResponse::Ok().finish() }))
+                .route("/api/v1/videos", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get().uri(path);
+        if let Some(header) = header {
+            req = req.header(API_KEY_HEADER, header);
+        }
+        let resp = test::call_service(&app, req.to_request()).await;
+        let status = resp.status().as_u16();
+        let body = test::read_body(resp).await;
+        (status, serde_json::from_slice(&body).ok())
+    }
+
+    #[ntex::test]
+    async fn test_matching_key_is_allowed() {
+        assert_eq!(call(Some("s3cret"), "/api/v1/videos", Some("s3cret")).await.0, 200);
+    }
+
+    #[ntex::test]
+    async fn test_missing_or_wrong_key_is_rejected() {
+        for header in [None, Some("wrong")] {
+            let (status, body) = call(Some("s3cret"), "/api/v1/videos", header).await;
+            assert_eq!(status, 401);
+            let body = body.unwrap();
+            assert_eq!(body["error"], "unauthorized");
+            assert_eq!(body["code"], "unauthorized");
+        }
+    }
+
+    #[ntex::test]
+    async fn test_public_paths_bypass_the_key() {
+        assert_eq!(call(Some("s3cret"), "/", None).await.0, 200);
+        assert_eq!(call(Some("s3cret"), LIVENESS_PATH, None).await.0, 200);
+    }
+
+    #[ntex::test]
+    async fn test_no_configured_key_checks_nothing() {
+        assert_eq!(call(None, "/api/v1/videos", None).await.0, 200);
+    }
+}
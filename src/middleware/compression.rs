@@ -0,0 +1,117 @@
+use ntex::http::body::{BodySize, MessageBody};
+use ntex::http::header::ContentEncoding;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::middleware::Compress;
+use ntex::web::{BodyEncoding, WebRequest, WebResponse};
+
+/// Bodies shorter than this many bytes are sent uncompressed
+pub const DEFAULT_MIN_COMPRESS_SIZE: u64 = 1024;
+
+/// Middleware compressing response bodies with gzip or deflate
+///
+/// Wraps ntex's `Compress`, which picks the encoding from the client's
+/// `Accept-Encoding`, but leaves bodies of known size below `min_size`
+/// alone, since compressing them costs more than it saves. Streamed bodies
+/// of unknown size are always compressed.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::compression::Compression;
+///
+/// let app = web::App::new().wrap(Compression::default());
+/// ```
+pub struct Compression {
+    min_size: u64,
+}
+
+impl Compression {
+    /// Creates the middleware
+    ///
+    /// # Arguments
+    /// * `min_size` - Smallest body, in bytes, that gets compressed
+    pub fn new(min_size: u64) -> Self {
+        Self { min_size }
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_COMPRESS_SIZE)
+    }
+}
+
+impl<S> Middleware<S> for Compression {
+    type Service = <Compress as Middleware<SkipSmallBodies<S>>>::Service;
+
+    fn create(&self, service: S) -> Self::Service {
+        Compress::default().create(SkipSmallBodies {
+            service,
+            min_size: self.min_size,
+        })
+    }
+}
+
+/// Marks responses below the size threshold as `identity` so `Compress` skips them
+pub struct SkipSmallBodies<S> {
+    service: S,
+    min_size: u64,
+}
+
+impl<S, E> Service<WebRequest<E>> for SkipSmallBodies<S>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let mut resp = ctx.call(&self.service, req).await?;
+        if let BodySize::Sized(size) = resp.response().body().size() {
+            if size < self.min_size {
+                resp.response_mut().encoding(ContentEncoding::Identity);
+            }
+        }
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{self, test, App, HttpResponse};
+
+    async fn content_encoding(body_len: usize) -> Option<String> {
+        let app = test::init_service(
+            App::new().wrap(Compression::default()).route(
+                "/",
+                web::get().to(move || async move { HttpResponse::Ok().body("x".repeat(body_len)) }),
+            ),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/").header("accept-encoding", "gzip").to_request();
+        let resp = test::call_service(&app, req).await;
+        resp.headers()
+            .get("content-encoding")
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[ntex::test]
+    async fn test_large_body_is_gzipped() {
+        assert_eq!(content_encoding(4096).await.as_deref(), Some("gzip"));
+    }
+
+    #[ntex::test]
+    async fn test_small_body_is_left_alone() {
+        assert_eq!(content_encoding(100).await, None);
+    }
+}
@@ -0,0 +1,165 @@
+use ntex::http::header::HeaderValue;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{WebRequest, WebResponse};
+use sea_orm::metric::Info;
+use sea_orm::DatabaseConnection;
+use std::cell::Cell;
+
+/// Request header asking for the statements of one request to be logged and counted
+pub const DEBUG_SQL_HEADER: &str = "x-debug-sql";
+
+/// Response header carrying how many statements the request executed
+pub const SQL_COUNT_HEADER: &str = "x-sql-count";
+
+tokio::task_local! {
+    static STATEMENTS: Cell<u32>;
+}
+
+/// Counts and logs a statement if it runs inside a request being debugged
+///
+/// Statements executed outside `DebugSql` scopes are ignored, so installing
+/// the callback costs nothing for ordinary requests. Raw `execute_unprepared`
+/// calls bypass sea-orm's metrics and are not counted.
+pub fn record_statement(info: &Info<'_>) {
+    let _ = STATEMENTS.try_with(|count| {
+        count.set(count.get() + 1);
+        tracing::info!(target: "sql", "{} ({:?}, failed: {})", info.statement, info.elapsed, info.failed);
+    });
+}
+
+/// Installs `record_statement` as the connection's metric callback
+///
+/// Must be called before the connection is cloned into services, since each
+/// clone keeps the callback it was created with.
+pub fn install(db: &mut DatabaseConnection) {
+    db.set_metric_callback(record_statement);
+}
+
+/// Middleware enabling per-request SQL statement logging with `X-Debug-Sql: true`
+///
+/// Debugged requests log every statement under the `sql` target and get an
+/// `X-Sql-Count` response header. It is meant for development only: when
+/// disabled the request header is ignored and nothing is added to responses.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::debug_sql::{self, DebugSql};
+/// use sea_orm::DatabaseConnection;
+///
+/// fn app(mut db: DatabaseConnection) {
+///     debug_sql::install(&mut db);
+///     let app = web::App::new().state(db).wrap(DebugSql::new(true));
+/// }
+/// ```
+pub struct DebugSql {
+    enabled: bool,
+}
+
+impl DebugSql {
+    /// Creates the middleware; `enabled` should only be set in development
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+impl<S> Middleware<S> for DebugSql {
+    type Service = DebugSqlMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        DebugSqlMiddleware {
+            service,
+            enabled: self.enabled,
+        }
+    }
+}
+
+pub struct DebugSqlMiddleware<S> {
+    service: S,
+    enabled: bool,
+}
+
+impl<S, E> Service<WebRequest<E>> for DebugSqlMiddleware<S>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let debug = self.enabled
+            && req
+                .headers()
+                .get(DEBUG_SQL_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+        if !debug {
+            return ctx.call(&self.service, req).await;
+        }
+
+        let (result, count) = STATEMENTS
+            .scope(Cell::new(0), async {
+                let result = ctx.call(&self.service, req).await;
+                (result, STATEMENTS.with(Cell::get))
+            })
+            .await;
+
+        let mut resp = result?;
+        resp.headers_mut()
+            .insert(SQL_COUNT_HEADER.parse().unwrap(), HeaderValue::from(count));
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{self, test, App, HttpResponse};
+    use sea_orm::{ConnectionTrait, Database, DbBackend, Statement};
+
+    async fn call_with(enabled: bool, header: Option<&str>) -> WebResponse {
+        let mut db = Database::connect("sqlite::memory:").await.unwrap();
+        install(&mut db);
+        let app = test::init_service(
+            App::new()
+                .wrap(DebugSql::new(enabled))
+                .state(db)
+                .route("/", web::get().to(|db: web::types::State<DatabaseConnection>| async move {
+                    for sql in ["SELECT 1", "SELECT 2"] {
+                        db.query_one(Statement::from_string(DbBackend::Sqlite, sql)).await.unwrap();
+                    }
+                    HttpResponse::Ok().finish()
+                })),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get().uri("/");
+        if let Some(value) = header {
+            req = req.header(DEBUG_SQL_HEADER, value);
+        }
+        test::call_service(&app, req.to_request()).await
+    }
+
+    #[ntex::test]
+    async fn test_debug_header_reports_statement_count() {
+        let resp = call_with(true, Some("true")).await;
+        assert_eq!(resp.headers().get(SQL_COUNT_HEADER).unwrap(), "2");
+    }
+
+    #[ntex::test]
+    async fn test_no_count_without_header_or_when_disabled() {
+        let resp = call_with(true, None).await;
+        assert!(resp.headers().get(SQL_COUNT_HEADER).is_none());
+
+        let resp = call_with(false, Some("true")).await;
+        assert!(resp.headers().get(SQL_COUNT_HEADER).is_none());
+    }
+}
@@ -0,0 +1,193 @@
+use ntex::http::header::{self, HeaderValue};
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{HttpResponse, WebRequest, WebResponse};
+use std::rc::Rc;
+
+/// Path prefixes left alone so load balancer probes keep working over plain HTTP
+pub const DEFAULT_EXEMPT_PREFIXES: [&str; 1] = ["/health"];
+
+/// Middleware enforcing HTTPS behind a TLS-terminating proxy
+///
+/// With `redirect` enabled, requests the proxy marks as plain HTTP through
+/// `X-Forwarded-Proto: http` get a `308` to the same URL over `https`.
+/// Requests without the header are passed through, since the scheme can't
+/// be known. With an HSTS max age set, responses carry
+/// `Strict-Transport-Security`. Health check paths are exempt from both.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::https::EnforceHttps;
+///
+/// let app = web::App::new().wrap(EnforceHttps::new(Some(31_536_000), true));
+/// ```
+pub struct EnforceHttps {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    hsts: Option<HeaderValue>,
+    redirect: bool,
+    exempt_prefixes: Vec<String>,
+}
+
+impl EnforceHttps {
+    /// Creates the middleware
+    ///
+    /// # Arguments
+    /// * `hsts_max_age` - `max-age` in seconds for `Strict-Transport-Security`; no header when unset
+    /// * `redirect` - Whether plain HTTP requests are redirected to HTTPS
+    pub fn new(hsts_max_age: Option<u64>, redirect: bool) -> Self {
+        let hsts = hsts_max_age.map(|max_age| {
+            HeaderValue::from_str(&format!("max-age={}; includeSubDomains", max_age))
+                .expect("a numeric max-age is a valid header value")
+        });
+        Self {
+            inner: Rc::new(Inner {
+                hsts,
+                redirect,
+                exempt_prefixes: DEFAULT_EXEMPT_PREFIXES.iter().map(|p| p.to_string()).collect(),
+            }),
+        }
+    }
+
+    /// Adds a path prefix that is neither redirected nor given HSTS
+    pub fn exempt(mut self, prefix: &str) -> Self {
+        Rc::get_mut(&mut self.inner)
+            .expect("Multiple copies exist")
+            .exempt_prefixes
+            .push(prefix.to_string());
+        self
+    }
+}
+
+impl<S> Middleware<S> for EnforceHttps {
+    type Service = EnforceHttpsMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        EnforceHttpsMiddleware {
+            service,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+pub struct EnforceHttpsMiddleware<S> {
+    service: S,
+    inner: Rc<Inner>,
+}
+
+/// Returns true when the proxy reports the original request as plain HTTP
+fn forwarded_as_http<E>(req: &WebRequest<E>) -> bool {
+    req.headers()
+        .get("x-forwarded-proto")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .is_some_and(|proto| proto.trim().eq_ignore_ascii_case("http"))
+}
+
+impl<S, E> Service<WebRequest<E>> for EnforceHttpsMiddleware<S>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let exempt = self
+            .inner
+            .exempt_prefixes
+            .iter()
+            .any(|prefix| req.path().starts_with(prefix.as_str()));
+        if exempt {
+            return ctx.call(&self.service, req).await;
+        }
+
+        if self.inner.redirect && forwarded_as_http(&req) {
+            let host = req.connection_info().host().to_string();
+            let target = req
+                .uri()
+                .path_and_query()
+                .map_or_else(|| req.path().to_string(), |pq| pq.as_str().to_string());
+            let mut response = HttpResponse::PermanentRedirect()
+                .header(header::LOCATION, format!("https://{}{}", host, target))
+                .finish();
+            if let Some(hsts) = &self.inner.hsts {
+                response.headers_mut().insert(header::STRICT_TRANSPORT_SECURITY, hsts.clone());
+            }
+            return Ok(req.into_response(response));
+        }
+
+        let mut resp = ctx.call(&self.service, req).await?;
+        if let Some(hsts) = &self.inner.hsts {
+            resp.headers_mut().insert(header::STRICT_TRANSPORT_SECURITY, hsts.clone());
+        }
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::http::StatusCode;
+    use ntex::web::{self, test, App};
+
+    async fn call(middleware: EnforceHttps, req: test::TestRequest) -> WebResponse {
+        let app = test::init_service(
+            App::new()
+                .wrap(middleware)
+                .route("/videos", web::get().to(|| async { HttpResponse::Ok().finish() }))
+                .route("/health", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+        test::call_service(&app, req.to_request()).await
+    }
+
+    #[ntex::test]
+    async fn test_hsts_header_added_when_configured() {
+        let resp = call(EnforceHttps::new(Some(600), false), test::TestRequest::get().uri("/videos")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::STRICT_TRANSPORT_SECURITY).unwrap(),
+            "max-age=600; includeSubDomains"
+        );
+
+        let resp = call(EnforceHttps::new(None, false), test::TestRequest::get().uri("/videos")).await;
+        assert!(resp.headers().get(header::STRICT_TRANSPORT_SECURITY).is_none());
+    }
+
+    #[ntex::test]
+    async fn test_forwarded_http_redirected_to_https() {
+        let req = test::TestRequest::get()
+            .uri("/videos?page=2")
+            .header(header::HOST, "example.com")
+            .header("x-forwarded-proto", "http");
+        let resp = call(EnforceHttps::new(None, true), req).await;
+        assert_eq!(resp.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(resp.headers().get(header::LOCATION).unwrap(), "https://example.com/videos?page=2");
+    }
+
+    #[ntex::test]
+    async fn test_forwarded_https_and_health_checks_pass_through() {
+        let req = test::TestRequest::get()
+            .uri("/videos")
+            .header("x-forwarded-proto", "https");
+        let resp = call(EnforceHttps::new(None, true), req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri("/health")
+            .header("x-forwarded-proto", "http");
+        let resp = call(EnforceHttps::new(Some(600), true), req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get(header::STRICT_TRANSPORT_SECURITY).is_none());
+    }
+}
@@ -0,0 +1,216 @@
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{WebRequest, WebResponse};
+use prometheus::{Encoder, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder};
+use sea_orm::metric::Info;
+use std::time::Instant;
+
+/// Path the Prometheus scrape endpoint is served at
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Route label of requests that matched no route, kept constant so probes
+/// for random paths can't grow the label set
+pub const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+/// Prometheus registry with the HTTP and database metrics of this service
+///
+/// Clones share the same registry and metrics, so one instance can be
+/// handed to the middleware, the database callback and app state.
+///
+/// * `http_requests_total{method, route, status}` - Responses sent
+/// * `http_request_duration_seconds{method, route}` - Time to produce a response
+/// * `db_queries_total{outcome}` - Statements run through sea-orm, `ok` or `error`
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    requests: IntCounterVec,
+    durations: HistogramVec,
+    queries: IntCounterVec,
+}
+
+impl Metrics {
+    /// Creates a fresh registry with every metric registered
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP responses sent"),
+            &["method", "route", "status"],
+        )
+        .unwrap();
+        let durations = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "Time spent producing HTTP responses"),
+            &["method", "route"],
+        )
+        .unwrap();
+        let queries = IntCounterVec::new(
+            Opts::new("db_queries_total", "Database statements executed through sea-orm"),
+            &["outcome"],
+        )
+        .unwrap();
+
+        registry.register(Box::new(requests.clone())).unwrap();
+        registry.register(Box::new(durations.clone())).unwrap();
+        registry.register(Box::new(queries.clone())).unwrap();
+
+        Self { registry, requests, durations, queries }
+    }
+
+    /// Counts one executed statement; meant for `DatabaseConnection::set_metric_callback`
+    ///
+    /// Raw `execute_unprepared` calls bypass sea-orm's metrics and are not counted.
+    pub fn record_query(&self, info: &Info<'_>) {
+        let outcome = if info.failed { "error" } else { "ok" };
+        self.queries.with_label_values(&[outcome]).inc();
+    }
+
+    /// Renders every metric in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("text encoding into a Vec can't fail");
+        String::from_utf8(buffer).expect("the text format is UTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rebuilds the route template of `path` from the parameters it matched
+///
+/// `/api/v1/videos/42/related` with `id = 42` becomes
+/// `/api/v1/videos/{id}/related`, so per-video requests share one series.
+fn route_template(path: &str, params: &[(&str, &str)]) -> String {
+    path.split('/')
+        .map(|segment| {
+            params
+                .iter()
+                .find(|(_, value)| *value == segment && !segment.is_empty())
+                .map_or_else(|| segment.to_string(), |(name, _)| format!("{{{}}}", name))
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Middleware feeding request counts and durations into `Metrics`
+///
+/// Requests are labelled with their route template rather than the raw
+/// path. A `404` without any matched path parameter is labelled
+/// [`UNMATCHED_ROUTE`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::metrics::{Metrics, RecordMetrics};
+///
+/// let metrics = Metrics::new();
+/// let app = web::App::new()
+///     .wrap(RecordMetrics::new(metrics.clone()))
+///     .state(metrics);
+/// ```
+pub struct RecordMetrics {
+    metrics: Metrics,
+}
+
+impl RecordMetrics {
+    /// Creates the middleware recording into `metrics`
+    pub fn new(metrics: Metrics) -> Self {
+        Self { metrics }
+    }
+}
+
+impl<S> Middleware<S> for RecordMetrics {
+    type Service = RecordMetricsMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        RecordMetricsMiddleware {
+            service,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+pub struct RecordMetricsMiddleware<S> {
+    service: S,
+    metrics: Metrics,
+}
+
+impl<S, E> Service<WebRequest<E>> for RecordMetricsMiddleware<S>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let started = Instant::now();
+        let resp = ctx.call(&self.service, req).await?;
+        let elapsed = started.elapsed().as_secs_f64();
+
+        let request = resp.request();
+        let params: Vec<(&str, &str)> = request.match_info().iter().collect();
+        let status = resp.status();
+        let route = if status.as_u16() == 404 && params.is_empty() {
+            UNMATCHED_ROUTE.to_string()
+        } else {
+            route_template(request.path(), &params)
+        };
+        let method = request.method().as_str();
+
+        self.metrics
+            .requests
+            .with_label_values(&[method, &route, status.as_str()])
+            .inc();
+        self.metrics
+            .durations
+            .with_label_values(&[method, &route])
+            .observe(elapsed);
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_template_replaces_matched_segments() {
+        let params = [("id", "42")];
+        assert_eq!(
+            route_template("/api/v1/videos/42/related", &params),
+            "/api/v1/videos/{id}/related"
+        );
+        assert_eq!(route_template("/api/v1/videos", &params), "/api/v1/videos");
+    }
+
+    #[ntex::test]
+    async fn test_requests_are_counted_by_route_template() {
+        use ntex::web::{self, test, App, HttpResponse};
+
+        let metrics = Metrics::new();
+        let app = test::init_service(
+            App::new()
+                .wrap(RecordMetrics::new(metrics.clone()))
+                .route("/items/{id}", web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+        for uri in ["/items/1", "/items/2", "/nowhere"] {
+            test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        }
+
+        let text = metrics.render();
+        assert!(text.contains(r#"http_requests_total{method="GET",route="/items/{id}",status="200"} 2"#), "{}", text);
+        assert!(text.contains(r#"http_requests_total{method="GET",route="<unmatched>",status="404"} 1"#), "{}", text);
+        assert!(text.contains("http_request_duration_seconds_bucket"), "{}", text);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod access_log;
+pub mod api_key;
+pub mod compression;
+pub mod debug_sql;
+pub mod https;
+pub mod metrics;
+pub mod pre_ping;
+pub mod request_id;
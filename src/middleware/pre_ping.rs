@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{HttpResponse, WebRequest, WebResponse};
+use sea_orm::{DatabaseConnection, DbErr};
+use serde_json::json;
+use std::rc::Rc;
+
+/// Number of connection checks attempted before a request is rejected
+pub const DEFAULT_PRE_PING_ATTEMPTS: u32 = 3;
+
+/// Liveness probe path, answered without pinging the database
+pub const LIVENESS_PATH: &str = "/health";
+
+/// A lightweight liveness check run before a request touches the database
+#[async_trait(?Send)]
+pub trait ConnectionCheck {
+    async fn ping(&self) -> Result<(), DbErr>;
+}
+
+#[async_trait(?Send)]
+impl ConnectionCheck for DatabaseConnection {
+    async fn ping(&self) -> Result<(), DbErr> {
+        DatabaseConnection::ping(self).await
+    }
+}
+
+/// Middleware validating the database connection before each request
+///
+/// Mirrors SQLAlchemy's `pool_pre_ping`: a failed ping lets the pool discard
+/// the stale connection, and the check is retried on a freshly acquired one.
+/// When every attempt fails the request is answered with `503` instead of
+/// failing halfway through its first query. When disabled the request is
+/// passed through untouched. The liveness probe at [`LIVENESS_PATH`] is
+/// never checked, so a database outage doesn't get the process restarted.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::pre_ping::PrePing;
+/// use sea_orm::DatabaseConnection;
+///
+/// fn app(db: DatabaseConnection) {
+///     let app = web::App::new().wrap(PrePing::new(db, true));
+/// }
+/// ```
+pub struct PrePing<C> {
+    inner: Rc<Inner<C>>,
+}
+
+struct Inner<C> {
+    check: C,
+    enabled: bool,
+    attempts: u32,
+}
+
+impl<C> PrePing<C> {
+    /// Creates the middleware around a connection check
+    ///
+    /// # Arguments
+    /// * `check` - The connection to ping
+    /// * `enabled` - Whether requests are checked at all
+    pub fn new(check: C, enabled: bool) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                check,
+                enabled,
+                attempts: DEFAULT_PRE_PING_ATTEMPTS,
+            }),
+        }
+    }
+
+    /// Sets how many pings are attempted before giving up
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        Rc::get_mut(&mut self.inner)
+            .expect("Multiple copies exist")
+            .attempts = attempts.max(1);
+        self
+    }
+}
+
+impl<S, C> Middleware<S> for PrePing<C> {
+    type Service = PrePingMiddleware<S, C>;
+
+    fn create(&self, service: S) -> Self::Service {
+        PrePingMiddleware {
+            service,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+pub struct PrePingMiddleware<S, C> {
+    service: S,
+    inner: Rc<Inner<C>>,
+}
+
+impl<S, C, E> Service<WebRequest<E>> for PrePingMiddleware<S, C>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+    C: ConnectionCheck,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        if self.inner.enabled && req.path() != LIVENESS_PATH {
+            let mut last_error = None;
+            for _ in 0..self.inner.attempts {
+                match self.inner.check.ping().await {
+                    Ok(()) => {
+                        last_error = None;
+                        break;
+                    }
+                    Err(e) => last_error = Some(e),
+                }
+            }
+
+            if let Some(e) = last_error {
+                tracing::error!("Database pre-ping failed: {}", e);
+                let response = HttpResponse::ServiceUnavailable()
+                    .json(&json!({ "error": "database unavailable" }));
+                return Ok(req.into_response(response));
+            }
+        }
+
+        ctx.call(&self.service, req).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ntex::web::{self, test, App};
+    use std::cell::Cell;
+
+    /// Fails the first `stale` pings, like a pool handing out dropped connections
+    struct FlakyConnection {
+        stale: Cell<u32>,
+        pings: Rc<Cell<u32>>,
+    }
+
+    #[async_trait(?Send)]
+    impl ConnectionCheck for FlakyConnection {
+        async fn ping(&self) -> Result<(), DbErr> {
+            self.pings.set(self.pings.get() + 1);
+            if self.stale.get() > 0 {
+                self.stale.set(self.stale.get() - 1);
+                return Err(DbErr::Conn(sea_orm::RuntimeErr::Internal("stale connection".to_string())));
+            }
+            Ok(())
+        }
+    }
+
+    async fn call_with(enabled: bool, stale: u32) -> (u16, u32) {
+        call_path_with("/", enabled, stale).await
+    }
+
+    async fn call_path_with(path: &str, enabled: bool, stale: u32) -> (u16, u32) {
+        let pings = Rc::new(Cell::new(0));
+        let check = FlakyConnection {
+            stale: Cell::new(stale),
+            pings: pings.clone(),
+        };
+        let app = test::init_service(
+            App::new()
+                .wrap(PrePing::new(check, enabled))
+                .route("/", web::get().to(|| async { HttpResponse::Ok().finish() }))
+                .route(LIVENESS_PATH, web::get().to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let resp = test::call_service(&app, test::TestRequest::get().uri(path).to_request()).await;
+        (resp.status().as_u16(), pings.get())
+    }
+
+    #[ntex::test]
+    async fn test_disabled_pre_ping_never_pings() {
+        assert_eq!(call_with(false, 10).await, (200, 0));
+    }
+
+    #[ntex::test]
+    async fn test_pre_ping_retries_stale_connection() {
+        assert_eq!(call_with(true, 1).await, (200, 2));
+    }
+
+    #[ntex::test]
+    async fn test_pre_ping_gives_up_with_503() {
+        assert_eq!(call_with(true, 10).await, (503, DEFAULT_PRE_PING_ATTEMPTS));
+    }
+
+    #[ntex::test]
+    async fn test_liveness_probe_skips_pre_ping() {
+        assert_eq!(call_path_with(LIVENESS_PATH, true, 10).await, (200, 0));
+    }
+}
@@ -0,0 +1,132 @@
+use ntex::http::header::{HeaderName, HeaderValue};
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{HttpRequest, WebRequest, WebResponse};
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Header carrying the request id in both directions
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request id that is accepted as-is
+pub const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// The id assigned to the current request, stored in request extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// Returns the id `AssignRequestId` stored for this request, if any
+pub fn request_id(req: &HttpRequest) -> Option<String> {
+    req.extensions().get::<RequestId>().map(|id| id.0.clone())
+}
+
+/// Middleware giving every request an id for tracing
+///
+/// Reuses a well-formed incoming `X-Request-Id` (visible ASCII, at most
+/// [`MAX_REQUEST_ID_LEN`] chars) or generates a UUID. The id is stored as a
+/// [`RequestId`] extension, recorded on a `request` tracing span wrapping
+/// the rest of the chain, and echoed on the response.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex::web;
+/// use ntex_api::middleware::request_id::AssignRequestId;
+///
+/// let app = web::App::new().wrap(AssignRequestId);
+/// ```
+pub struct AssignRequestId;
+
+impl<S> Middleware<S> for AssignRequestId {
+    type Service = AssignRequestIdMiddleware<S>;
+
+    fn create(&self, service: S) -> Self::Service {
+        AssignRequestIdMiddleware { service }
+    }
+}
+
+pub struct AssignRequestIdMiddleware<S> {
+    service: S,
+}
+
+/// Returns the incoming id when it is safe to reuse
+fn incoming_id<E>(req: &WebRequest<E>) -> Option<String> {
+    let id = req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?.trim();
+    let valid = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.bytes().all(|b| b.is_ascii_graphic());
+    valid.then(|| id.to_string())
+}
+
+impl<S, E> Service<WebRequest<E>> for AssignRequestIdMiddleware<S>
+where
+    S: Service<WebRequest<E>, Response = WebResponse>,
+{
+    type Response = WebResponse;
+    type Error = S::Error;
+
+    ntex::forward_ready!(service);
+    ntex::forward_shutdown!(service);
+
+    async fn call(
+        &self,
+        req: WebRequest<E>,
+        ctx: ServiceCtx<'_, Self>,
+    ) -> Result<Self::Response, Self::Error> {
+        let id = incoming_id(&req).unwrap_or_else(|| Uuid::new_v4().to_string());
+        req.extensions_mut().insert(RequestId(id.clone()));
+
+        let span = tracing::info_span!("request", request_id = %id);
+        let mut resp = ctx.call(&self.service, req).instrument(span).await?;
+        if let Ok(value) = HeaderValue::from_str(&id) {
+            resp.headers_mut()
+                .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+        }
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use ntex::web::{self, test, App, HttpResponse};
+
+    async fn call(header: Option<&str>) -> (String, serde_json::Value) {
+        let app = test::init_service(
+            App::new().wrap(AssignRequestId).route(
+                "/",
+                web::get().to(|| async {
+                    Err::<HttpResponse, _>(AppError::NotFound("missing".to_string()))
+                }),
+            ),
+        )
+        .await;
+
+        let mut req = test::TestRequest::get().uri("/");
+        if let Some(header) = header {
+            req = req.header(REQUEST_ID_HEADER, header);
+        }
+        let resp = test::call_service(&app, req.to_request()).await;
+        let echoed = resp.headers().get(REQUEST_ID_HEADER).unwrap().to_str().unwrap().to_string();
+        let body = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        (echoed, body)
+    }
+
+    #[ntex::test]
+    async fn test_incoming_id_is_echoed_and_in_error_body() {
+        let (echoed, body) = call(Some("abc-123")).await;
+        assert_eq!(echoed, "abc-123");
+        assert_eq!(body["request_id"], "abc-123");
+        assert_eq!(body["error"], "missing");
+    }
+
+    #[ntex::test]
+    async fn test_missing_or_malformed_id_is_replaced_with_uuid() {
+        let (echoed, body) = call(None).await;
+        assert!(Uuid::parse_str(&echoed).is_ok());
+        assert_eq!(body["request_id"], echoed.as_str());
+
+        let (echoed, _) = call(Some("has spaces")).await;
+        assert!(Uuid::parse_str(&echoed).is_ok());
+    }
+}
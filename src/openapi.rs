@@ -0,0 +1,160 @@
+use utoipa::OpenApi;
+
+use crate::api::{
+    CreateVideoRequest, CursorVideoResponse, PaginatedVideoResponse, ReplaceVideoRequest, UpdateVideoRequest, VideoResponse,
+};
+use crate::entity::video::VideoStatus;
+
+/// Path served by the OpenAPI document itself
+pub const OPENAPI_PATH: &str = "/api/v1/openapi.json";
+
+/// OpenAPI 3.0 description of the video CRUD routes
+///
+/// # Example
+///
+/// ```
+/// use ntex_api::openapi::ApiDoc;
+/// use utoipa::OpenApi;
+///
+/// let json = ApiDoc::openapi().to_json().unwrap();
+/// assert!(json.contains("/api/v1/videos"));
+/// ```
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "ntex-api", description = "YouTube video catalogue"),
+    paths(
+        paths::list_videos,
+        paths::create_video,
+        paths::get_video,
+        paths::replace_video,
+        paths::patch_video,
+        paths::delete_video,
+    ),
+    components(schemas(
+        CreateVideoRequest,
+        UpdateVideoRequest,
+        ReplaceVideoRequest,
+        VideoResponse,
+        PaginatedVideoResponse,
+        CursorVideoResponse,
+        VideoStatus,
+    )),
+    tags((name = "videos", description = "Video CRUD"))
+)]
+pub struct ApiDoc;
+
+/// Route descriptions for `ApiDoc`
+///
+/// The handlers are methods on `VideoController`, which `utoipa::path`
+/// can't annotate, so each route is described by a bodiless stand-in here.
+#[allow(dead_code)]
+mod paths {
+    /// Lists live videos, paginated
+    #[utoipa::path(
+        get,
+        path = "/api/v1/videos",
+        tag = "videos",
+        params(
+            ("page" = Option<u64>, Query, description = "1-based page number"),
+            ("per_page" = Option<u64>, Query, description = "Page size, clamped to the configured maximum"),
+            ("search" = Option<String>, Query, description = "Matched against the title and youtube_id; results then carry matched_fields"),
+            ("fts" = Option<bool>, Query, description = "Match the words of `search` against the title and description by full-text index, most relevant first"),
+            ("order_by" = Option<String>, Query, description = "Comma-separated sort keys; a leading `-` sorts descending"),
+            ("order_direction" = Option<String>, Query, description = "`asc` or `desc`"),
+            ("tag" = Option<String>, Query, description = "Only videos carrying this tag"),
+            ("status" = Option<String>, Query, description = "Only videos in this state: `draft`, `published` or `archived`"),
+            ("cursor" = Option<bool>, Query, description = "Page by id instead: videos after `after`, ordered by id, with `next_cursor` but no total"),
+            ("after" = Option<String>, Query, description = "Keyset cursor, or with `cursor=true` the id of the last video already seen"),
+        ),
+        responses(
+            (status = 200, description = "A page of videos", body = PaginatedVideoResponse),
+            (status = 400, description = "Invalid query parameters"),
+        )
+    )]
+    fn list_videos() {}
+
+    /// Creates a video
+    #[utoipa::path(
+        post,
+        path = "/api/v1/videos",
+        tag = "videos",
+        request_body = CreateVideoRequest,
+        responses(
+            (status = 201, description = "The created video", body = VideoResponse),
+            (status = 400, description = "Validation failed"),
+            (status = 409, description = "The youtube_id already exists"),
+        )
+    )]
+    fn create_video() {}
+
+    /// Fetches one live video
+    #[utoipa::path(
+        get,
+        path = "/api/v1/videos/{id}",
+        tag = "videos",
+        params(("id" = String, Path, description = "Video id")),
+        responses(
+            (status = 200, description = "The video", body = VideoResponse),
+            (status = 304, description = "The `If-None-Match` ETag still matches"),
+            (status = 404, description = "No live video has this id"),
+        )
+    )]
+    fn get_video() {}
+
+    /// Replaces every writable field of a video
+    #[utoipa::path(
+        put,
+        path = "/api/v1/videos/{id}",
+        tag = "videos",
+        params(
+            ("id" = String, Path, description = "Video id"),
+            ("If-Match" = String, Header, description = "The video's current `ETag`, or `*`"),
+        ),
+        request_body = ReplaceVideoRequest,
+        responses(
+            (status = 200, description = "The updated video", body = VideoResponse),
+            (status = 400, description = "Validation failed"),
+            (status = 404, description = "No live video has this id"),
+            (status = 412, description = "The video changed since the `ETag` was read"),
+            (status = 428, description = "`If-Match` is missing"),
+        )
+    )]
+    fn replace_video() {}
+
+    /// Updates the given fields of a video
+    #[utoipa::path(
+        patch,
+        path = "/api/v1/videos/{id}",
+        tag = "videos",
+        params(
+            ("id" = String, Path, description = "Video id"),
+            ("If-Match" = String, Header, description = "The video's current `ETag`, or `*`"),
+        ),
+        request_body = UpdateVideoRequest,
+        responses(
+            (status = 200, description = "The updated video", body = VideoResponse),
+            (status = 400, description = "Validation failed"),
+            (status = 404, description = "No live video has this id"),
+            (status = 412, description = "The video changed since the `ETag` was read"),
+            (status = 428, description = "`If-Match` is missing"),
+        )
+    )]
+    fn patch_video() {}
+
+    /// Soft-deletes a video
+    #[utoipa::path(
+        delete,
+        path = "/api/v1/videos/{id}",
+        tag = "videos",
+        params(
+            ("id" = String, Path, description = "Video id"),
+            ("return" = Option<bool>, Query, description = "Answer with the deleted video instead of no content"),
+        ),
+        responses(
+            (status = 200, description = "The deleted video, with `deleted_at` set", body = VideoResponse),
+            (status = 204, description = "The video was deleted"),
+            (status = 404, description = "No live video has this id"),
+        )
+    )]
+    fn delete_video() {}
+}
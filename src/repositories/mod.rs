@@ -1 +1,2 @@
+pub mod traits;
 pub mod video_repository;
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::db::{Sort, VideoQuery};
+use crate::entity::video;
+use crate::error::AppResult;
+use crate::repositories::video_repository::{NewVideo, VideoChanges, VideoRepository};
+
+/// The video storage operations `VideoService` can run against any backend
+///
+/// `VideoRepository` implements it over sea-orm. Tests can implement it
+/// in memory to exercise the service without a database. Operations that
+/// need a database transaction stay on `VideoRepository` itself.
+///
+/// # Example
+///
+/// ```no_run
+/// use ntex_api::repositories::traits::VideoRepositoryTrait;
+/// use ntex_api::services::video_service::VideoService;
+///
+/// async fn title_of<R: VideoRepositoryTrait>(service: &VideoService<R>, id: i32) -> String {
+///     service.get_video(id).await.unwrap().title
+/// }
+/// ```
+#[async_trait]
+pub trait VideoRepositoryTrait: Send + Sync {
+    /// Inserts a video together with its tags
+    async fn create(&self, new: NewVideo) -> AppResult<video::Model>;
+
+    /// Finds a non-deleted video by its ID
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<video::Model>>;
+
+    /// Updates the given fields of a video, see [`VideoRepository::update`]
+    async fn update(
+        &self,
+        id: i32,
+        changes: VideoChanges,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<video::Model>>;
+
+    /// Soft-deletes a video, returning it with `deleted_at` set
+    async fn delete(&self, id: i32) -> AppResult<Option<video::Model>>;
+
+    /// Lists one page of non-deleted videos and the total matching count
+    async fn list(&self, query: &VideoQuery, sorts: &[Sort]) -> AppResult<(Vec<video::Model>, u64)>;
+
+    /// Returns each video's tag names, sorted; videos without tags are absent
+    async fn tags_for(&self, video_ids: &[i32]) -> AppResult<HashMap<i32, Vec<String>>>;
+}
+
+#[async_trait]
+impl VideoRepositoryTrait for VideoRepository {
+    async fn create(&self, new: NewVideo) -> AppResult<video::Model> {
+        VideoRepository::create(self, new).await
+    }
+
+    async fn find_by_id(&self, id: i32) -> AppResult<Option<video::Model>> {
+        VideoRepository::find_by_id(self, id).await
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        changes: VideoChanges,
+        expected_updated_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<video::Model>> {
+        VideoRepository::update(self, id, changes, expected_updated_at).await
+    }
+
+    async fn delete(&self, id: i32) -> AppResult<Option<video::Model>> {
+        VideoRepository::delete(self, id).await
+    }
+
+    async fn list(&self, query: &VideoQuery, sorts: &[Sort]) -> AppResult<(Vec<video::Model>, u64)> {
+        VideoRepository::list(self, query, sorts).await
+    }
+
+    async fn tags_for(&self, video_ids: &[i32]) -> AppResult<HashMap<i32, Vec<String>>> {
+        self.tags_for_in(self.connection(), video_ids).await
+    }
+}
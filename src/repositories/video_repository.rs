@@ -1,11 +1,47 @@
+use sea_orm::sea_query::{Expr, Func, LikeExpr, OnConflict, Query, SimpleExpr};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, 
-    PaginatorTrait, QueryFilter, QueryOrder, Set
+    ActiveModelBehavior, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr,
+    EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Set, SqlErr, Statement, TransactionTrait
 };
-use crate::entity::{video, video::Entity as Video};
+use crate::entity::{tag, video, video::Entity as Video, video_tag};
 use crate::error::{AppError, AppResult};
-use crate::db::VideoQuery;
+use crate::db::{SearchMode, Sort, SortDirection, SortKey, VideoQuery};
+use crate::services::cursor::Cursor;
 use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// The fields of a video about to be inserted
+#[derive(Debug, Clone)]
+pub struct NewVideo {
+    pub title: String,
+    pub youtube_id: String,
+    pub description: Option<String>,
+    pub status: video::VideoStatus,
+    /// Normalized tag names; missing tags are created
+    pub tags: Vec<String>,
+}
+
+/// The fields of a video to overwrite; `None` leaves a field as it is
+#[derive(Debug, Clone, Default)]
+pub struct VideoChanges {
+    pub title: Option<String>,
+    pub youtube_id: Option<String>,
+    /// `Some(None)` clears the description
+    pub description: Option<Option<String>>,
+    pub status: Option<video::VideoStatus>,
+}
+
+/// Full-text search index available on the connected database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FullText {
+    /// SQLite FTS5 table `videos_fts`, kept in sync by triggers
+    Fts5,
+    /// Postgres `tsvector` over the title and description
+    TsVector,
+}
+
+/// The `tsvector` expression the Postgres full-text index is built on
+const TSVECTOR: &str = r#"to_tsvector('english', coalesce("title", '') || ' ' || coalesce("description", ''))"#;
 
 /// Repository layer for video data access
 /// 
@@ -14,6 +50,9 @@ use chrono::{DateTime, Utc};
 /// - Pagination
 /// - Filtering
 /// - Sorting
+/// 
+/// Write operations also have `*_in` variants that run on a caller-supplied
+/// connection, so the service can group them inside a transaction.
 #[derive(Clone)]
 pub struct VideoRepository {
     db: DatabaseConnection,
@@ -28,32 +67,170 @@ impl VideoRepository {
         Self { db }
     }
 
+    /// Returns the underlying database connection
+    pub fn connection(&self) -> &DatabaseConnection {
+        &self.db
+    }
+
+    /// Starts a new database transaction
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If the transaction cannot be started
+    pub async fn begin(&self) -> AppResult<DatabaseTransaction> {
+        self.db.begin().await.map_err(AppError::Database)
+    }
+
     /// Creates a new video in the database
     /// 
+    /// The row, any missing tags and the links to them are written in one
+    /// transaction.
+    /// 
     /// # Arguments
-    /// * `title` - The title of the video
-    /// * `youtube_id` - The YouTube ID of the video
+    /// * `new` - The fields of the video to create
     /// 
     /// # Returns
     /// * `AppResult<video::Model>` - The created video model
     /// 
     /// # Errors
     /// * `AppError::Database` - If there's an error executing the query
-    pub async fn create(&self, title: String, youtube_id: String) -> AppResult<video::Model> {
+    pub async fn create(&self, new: NewVideo) -> AppResult<video::Model> {
+        let txn = self.begin().await?;
+        let video = self.create_in(&txn, new).await?;
+        txn.commit().await?;
+        Ok(video)
+    }
+
+    /// Creates a new video using the given connection or transaction
+    /// 
+    /// Pass a transaction when the video has tags, so a failure can't leave
+    /// it partially tagged.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn create_in<C: ConnectionTrait>(&self, conn: &C, new: NewVideo) -> AppResult<video::Model> {
         let video = video::ActiveModel {
-            title: Set(title),
-            youtube_id: Set(youtube_id),
+            title_normalized: Set(video::normalize_title_key(&new.title)),
+            title: Set(new.title),
+            youtube_id: Set(new.youtube_id),
+            description: Set(new.description),
+            status: Set(new.status),
             ..Default::default()
         };
 
-        let video = Video::insert(video)
-            .exec_with_returning(&self.db)
+        let mut video = Video::insert(video)
+            .exec_with_returning(conn)
             .await
-            .map_err(AppError::Database)?;
+            .map_err(Self::write_error)?;
+        video.position = video::append_position(conn, video.id).await.map_err(AppError::Database)?;
+        self.tag_in(conn, video.id, &new.tags).await?;
 
         Ok(video)
     }
 
+    /// Links a video to the named tags, creating tags that don't exist yet
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn tag_in<C: ConnectionTrait>(&self, conn: &C, video_id: i32, names: &[String]) -> AppResult<()> {
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        tag::Entity::insert_many(names.iter().map(|name| tag::ActiveModel {
+            name: Set(name.clone()),
+            ..Default::default()
+        }))
+        .on_conflict(OnConflict::column(tag::Column::Name).do_nothing().to_owned())
+        .exec_without_returning(conn)
+        .await
+        .map_err(AppError::Database)?;
+
+        let tag_ids: Vec<i32> = tag::Entity::find()
+            .select_only()
+            .column(tag::Column::Id)
+            .filter(tag::Column::Name.is_in(names.iter().cloned()))
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::Database)?;
+
+        video_tag::Entity::insert_many(tag_ids.into_iter().map(|tag_id| video_tag::ActiveModel {
+            video_id: Set(video_id),
+            tag_id: Set(tag_id),
+        }))
+        .on_conflict(
+            OnConflict::columns([video_tag::Column::VideoId, video_tag::Column::TagId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(conn)
+        .await
+        .map_err(AppError::Database)?;
+
+        Ok(())
+    }
+
+    /// Loads the tag names of each video, sorted by name
+    /// 
+    /// Videos without tags are absent from the map.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn tags_for_in<C: ConnectionTrait>(&self, conn: &C, video_ids: &[i32]) -> AppResult<HashMap<i32, Vec<String>>> {
+        if video_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let rows: Vec<(i32, String)> = video_tag::Entity::find()
+            .select_only()
+            .column(video_tag::Column::VideoId)
+            .column(tag::Column::Name)
+            .inner_join(tag::Entity)
+            .filter(video_tag::Column::VideoId.is_in(video_ids.iter().copied()))
+            .order_by_asc(tag::Column::Name)
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::Database)?;
+
+        let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for (video_id, name) in rows {
+            tags.entry(video_id).or_default().push(name);
+        }
+        Ok(tags)
+    }
+
+    /// Creates several videos in one transaction
+    /// 
+    /// Either every video is inserted or, if any insert fails, none are.
+    /// 
+    /// # Arguments
+    /// * `items` - The videos to create, inserted in order
+    /// 
+    /// # Returns
+    /// * `AppResult<Vec<video::Model>>` - The created videos, in input order
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn create_many(&self, items: Vec<NewVideo>) -> AppResult<Vec<video::Model>> {
+        let txn = self.begin().await?;
+        let videos = self.create_many_in(&txn, items).await?;
+        txn.commit().await?;
+        Ok(videos)
+    }
+
+    /// Creates several videos using the given connection or transaction
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn create_many_in<C: ConnectionTrait>(&self, conn: &C, items: Vec<NewVideo>) -> AppResult<Vec<video::Model>> {
+        let mut videos = Vec::with_capacity(items.len());
+        for new in items {
+            videos.push(self.create_in(conn, new).await?);
+        }
+        Ok(videos)
+    }
+
     /// Finds a video by its ID
     /// 
     /// # Arguments
@@ -65,43 +242,324 @@ impl VideoRepository {
     /// # Errors
     /// * `AppError::Database` - If there's an error executing the query
     pub async fn find_by_id(&self, id: i32) -> AppResult<Option<video::Model>> {
+        self.find_by_id_in(&self.db, id).await
+    }
+
+    /// Finds a non-deleted video by its ID using the given connection or transaction
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_by_id_in<C: ConnectionTrait>(&self, conn: &C, id: i32) -> AppResult<Option<video::Model>> {
         let video = Video::find_by_id(id)
             .filter(video::Column::DeletedAt.is_null())
-            .one(&self.db)
+            .one(conn)
             .await
             .map_err(AppError::Database)?;
 
         Ok(video)
     }
 
+    /// Finds a non-deleted video by its YouTube ID
+    /// 
+    /// # Arguments
+    /// * `youtube_id` - The YouTube ID of the video to find
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<video::Model>>` - The found video model, if any
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_by_youtube_id(&self, youtube_id: &str) -> AppResult<Option<video::Model>> {
+        self.find_by_youtube_id_in(&self.db, youtube_id).await
+    }
+
+    /// Finds a non-deleted video by its YouTube ID using the given connection or transaction
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_by_youtube_id_in<C: ConnectionTrait>(&self, conn: &C, youtube_id: &str) -> AppResult<Option<video::Model>> {
+        Video::find()
+            .filter(video::Column::YoutubeId.eq(youtube_id))
+            .filter(video::Column::DeletedAt.is_null())
+            .one(conn)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Picks one non-deleted video at random
+    /// 
+    /// Orders by the backend's random function, `RAND()` on MySQL and
+    /// `RANDOM()` elsewhere, which scans the whole table.
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<video::Model>>` - A random video, or `None` when there are none
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_random(&self) -> AppResult<Option<video::Model>> {
+        let random = match self.db.get_database_backend() {
+            DbBackend::MySql => "RAND()",
+            _ => "RANDOM()",
+        };
+
+        Video::find()
+            .filter(video::Column::DeletedAt.is_null())
+            .order_by(Expr::cust(random), Order::Asc)
+            .one(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Counts non-deleted videos, only those created at or after `since` when given
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn count_live(&self, since: Option<DateTime<Utc>>) -> AppResult<u64> {
+        let mut select = Video::find().filter(video::Column::DeletedAt.is_null());
+        if let Some(since) = since {
+            select = select.filter(video::Column::CreatedAt.gte(since));
+        }
+        select.count(&self.db).await.map_err(AppError::Database)
+    }
+
+    /// Counts soft-deleted videos
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn count_deleted(&self) -> AppResult<u64> {
+        Video::find()
+            .filter(video::Column::DeletedAt.is_not_null())
+            .count(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Returns the highest id among non-deleted videos
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn newest_id(&self) -> AppResult<Option<i32>> {
+        Video::find()
+            .select_only()
+            .column(video::Column::Id)
+            .filter(video::Column::DeletedAt.is_null())
+            .order_by_desc(video::Column::Id)
+            .into_tuple()
+            .one(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Returns the earliest `created_at` among non-deleted videos
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn oldest_created_at(&self) -> AppResult<Option<DateTime<Utc>>> {
+        Video::find()
+            .select_only()
+            .column(video::Column::CreatedAt)
+            .filter(video::Column::DeletedAt.is_null())
+            .order_by_asc(video::Column::CreatedAt)
+            .into_tuple()
+            .one(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Groups the ids of videos sharing a YouTube ID, soft-deleted ones included
+    /// 
+    /// # Returns
+    /// * `AppResult<Vec<(String, Vec<i32>)>>` - Each shared YouTube ID with its
+    ///   video ids, both in ascending order
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_duplicate_youtube_ids(&self) -> AppResult<Vec<(String, Vec<i32>)>> {
+        let youtube_ids: Vec<String> = Video::find()
+            .select_only()
+            .column(video::Column::YoutubeId)
+            .group_by(video::Column::YoutubeId)
+            .having(Expr::expr(Expr::col(video::Column::Id).count()).gt(1))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(AppError::Database)?;
+        if youtube_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(String, i32)> = Video::find()
+            .select_only()
+            .column(video::Column::YoutubeId)
+            .column(video::Column::Id)
+            .filter(video::Column::YoutubeId.is_in(youtube_ids))
+            .order_by_asc(video::Column::YoutubeId)
+            .order_by_asc(video::Column::Id)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .map_err(AppError::Database)?;
+
+        let mut groups: Vec<(String, Vec<i32>)> = Vec::new();
+        for (youtube_id, id) in rows {
+            match groups.last_mut() {
+                Some((last, ids)) if *last == youtube_id => ids.push(id),
+                _ => groups.push((youtube_id, vec![id])),
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Updates the title of the live video with `new.youtube_id`, or inserts `new`
+    /// 
+    /// An existing video keeps its other fields and tags. An insert writes
+    /// the row and its tags in one transaction.
+    /// 
+    /// # Returns
+    /// * `AppResult<(video::Model, bool)>` - The saved video, and whether it was inserted
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn upsert_by_youtube_id(&self, new: NewVideo) -> AppResult<(video::Model, bool)> {
+        let txn = self.begin().await?;
+        let result = self.upsert_by_youtube_id_in(&txn, new).await?;
+        txn.commit().await?;
+        Ok(result)
+    }
+
+    /// Upserts by YouTube ID using the given connection or transaction
+    /// 
+    /// # Returns
+    /// * `AppResult<(video::Model, bool)>` - The saved video, and whether it was inserted
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn upsert_by_youtube_id_in<C: ConnectionTrait>(&self, conn: &C, new: NewVideo) -> AppResult<(video::Model, bool)> {
+        match self.find_by_youtube_id_in(conn, &new.youtube_id).await? {
+            Some(existing) => {
+                let mut video: video::ActiveModel = existing.into();
+                video.title = Set(new.title);
+                let video = video.update(conn).await.map_err(Self::write_error)?;
+                Ok((video, false))
+            }
+            None => Ok((self.create_in(conn, new).await?, true)),
+        }
+    }
+
+    /// Finds all non-deleted videos whose ID is in `ids`
+    /// 
+    /// # Arguments
+    /// * `ids` - The IDs of the videos to find
+    /// 
+    /// # Returns
+    /// * `AppResult<Vec<video::Model>>` - The videos that were found, in no particular order
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_by_ids(&self, ids: &[i32]) -> AppResult<Vec<video::Model>> {
+        let videos = Video::find()
+            .filter(video::Column::Id.is_in(ids.iter().copied()))
+            .filter(video::Column::DeletedAt.is_null())
+            .all(&self.db)
+            .await
+            .map_err(AppError::Database)?;
+
+        Ok(videos)
+    }
+
+    /// Finds non-deleted videos other than `exclude_id` whose title contains any of `words`
+    /// 
+    /// Words are matched as whole words, case-insensitively for ASCII.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_sharing_title_words(&self, exclude_id: i32, words: &[String]) -> AppResult<Vec<video::Model>> {
+        if words.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let any_word = words.iter()
+            .fold(Condition::any(), |condition, word| condition.add(Self::title_has_word(word)));
+
+        Video::find()
+            .filter(video::Column::Id.ne(exclude_id))
+            .filter(video::Column::DeletedAt.is_null())
+            .filter(any_word)
+            .all(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
     /// Updates an existing video
     /// 
     /// # Arguments
     /// * `id` - The ID of the video to update
-    /// * `title` - The new title of the video
-    /// * `youtube_id` - The new YouTube ID of the video
+    /// * `changes` - The fields to overwrite
+    /// * `expected_updated_at` - When set, the `updated_at` the caller last
+    ///   saw; the update only applies while the row still carries it
     /// 
     /// # Returns
     /// * `AppResult<Option<video::Model>>` - The updated video model, if found
     /// 
     /// # Errors
+    /// * `AppError::PreconditionFailed` - If the row changed since `expected_updated_at`
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn update(&self, id: i32, changes: VideoChanges, expected_updated_at: Option<DateTime<Utc>>) -> AppResult<Option<video::Model>> {
+        self.update_in(&self.db, id, changes, expected_updated_at).await
+    }
+
+    /// Updates an existing video using the given connection or transaction
+    /// 
+    /// `expected_updated_at` is compared at microsecond precision, the
+    /// precision of the ETag it usually comes from. The write itself is
+    /// conditioned on the exact `updated_at` read here, so a concurrent
+    /// update landing in between is reported as a mismatch too.
+    /// 
+    /// # Errors
+    /// * `AppError::PreconditionFailed` - If the row changed since `expected_updated_at`
     /// * `AppError::Database` - If there's an error executing the query
-    pub async fn update(&self, id: i32, title: Option<String>, youtube_id: Option<String>) -> AppResult<Option<video::Model>> {
-        let video = self.find_by_id(id).await?;
+    pub async fn update_in<C: ConnectionTrait>(&self, conn: &C, id: i32, changes: VideoChanges, expected_updated_at: Option<DateTime<Utc>>) -> AppResult<Option<video::Model>> {
+        let video = self.find_by_id_in(conn, id).await?;
         
         if let Some(video) = video {
+            let read_updated_at = video.updated_at;
+            if expected_updated_at.is_some_and(|expected| expected.timestamp_micros() != read_updated_at.timestamp_micros()) {
+                return Err(Self::stale_update());
+            }
+
             let mut video: video::ActiveModel = video.into();
             
-            if let Some(title) = title {
+            if let Some(title) = changes.title {
                 video.title = Set(title);
             }
             
-            if let Some(youtube_id) = youtube_id {
+            if let Some(youtube_id) = changes.youtube_id {
                 video.youtube_id = Set(youtube_id);
             }
 
-            let updated_video = video.update(&self.db).await
-                .map_err(AppError::Database)?;
+            if let Some(description) = changes.description {
+                video.description = Set(description);
+            }
+
+            if let Some(status) = changes.status {
+                video.status = Set(status);
+            }
+
+            let updated_video = if expected_updated_at.is_some() {
+                // `Entity::update` skips the behavior hooks `ActiveModel::update` runs
+                let video = video.before_save(conn, false).await.map_err(AppError::Database)?;
+                Video::update(video)
+                    .filter(video::Column::UpdatedAt.eq(read_updated_at))
+                    .exec(conn)
+                    .await
+                    .map_err(|err| match err {
+                        DbErr::RecordNotUpdated => Self::stale_update(),
+                        err => Self::write_error(err),
+                    })?
+            } else {
+                video.update(conn).await
+                    .map_err(Self::write_error)?
+            };
 
             Ok(Some(updated_video))
         } else {
@@ -115,20 +573,555 @@ impl VideoRepository {
     /// * `id` - The ID of the video to delete
     /// 
     /// # Returns
-    /// * `AppResult<bool>` - True if the video was deleted, false if not found
+    /// * `AppResult<Option<video::Model>>` - The soft-deleted video, with
+    ///   `deleted_at` set, or None if not found
     /// 
     /// # Errors
     /// * `AppError::Database` - If there's an error executing the query
-    pub async fn delete(&self, id: i32) -> AppResult<bool> {
-        let video = self.find_by_id(id).await?;
+    pub async fn delete(&self, id: i32) -> AppResult<Option<video::Model>> {
+        self.delete_in(&self.db, id).await
+    }
+
+    /// Soft-deletes a video using the given connection or transaction
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn delete_in<C: ConnectionTrait>(&self, conn: &C, id: i32) -> AppResult<Option<video::Model>> {
+        let video = self.find_by_id_in(conn, id).await?;
         
         if let Some(video) = video {
             let mut video: video::ActiveModel = video.into();
             video.deleted_at = Set(Some(Utc::now()));
-            video.update(&self.db).await.map_err(AppError::Database)?;
-            Ok(true)
+            let deleted = video.update(conn).await.map_err(AppError::Database)?;
+            Ok(Some(deleted))
         } else {
-            Ok(false)
+            Ok(None)
+        }
+    }
+
+    /// Soft-deletes every live video whose ID is in `ids`
+    /// 
+    /// # Arguments
+    /// * `ids` - The IDs of the videos to delete
+    /// 
+    /// # Returns
+    /// * `AppResult<Vec<i32>>` - The IDs that were deleted, in no particular
+    ///   order; missing and already deleted IDs are left out
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn soft_delete_many(&self, ids: &[i32]) -> AppResult<Vec<i32>> {
+        self.soft_delete_many_in(&self.db, ids).await
+    }
+
+    /// Soft-deletes several videos using the given connection or transaction
+    /// 
+    /// Runs one select to find the live rows and one update to mark them.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn soft_delete_many_in<C: ConnectionTrait>(&self, conn: &C, ids: &[i32]) -> AppResult<Vec<i32>> {
+        let live: Vec<i32> = Video::find()
+            .select_only()
+            .column(video::Column::Id)
+            .filter(video::Column::Id.is_in(ids.iter().copied()))
+            .filter(video::Column::DeletedAt.is_null())
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::Database)?;
+
+        if !live.is_empty() {
+            Video::update_many()
+                .col_expr(video::Column::DeletedAt, Expr::value(Some(Utc::now())))
+                .filter(video::Column::Id.is_in(live.iter().copied()))
+                .exec(conn)
+                .await
+                .map_err(AppError::Database)?;
+        }
+
+        Ok(live)
+    }
+
+    /// Adds one view to a live video
+    /// 
+    /// Runs a single `UPDATE ... SET view_count = view_count + 1 ...
+    /// RETURNING view_count`, so concurrent views are never lost and
+    /// `updated_at` is left untouched.
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the viewed video
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<i64>>` - The new count, or `None` if no live
+    ///   video has this ID
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn increment_view_count(&self, id: i32) -> AppResult<Option<i64>> {
+        let stmt = Query::update()
+            .table(Video)
+            .value(video::Column::ViewCount, Expr::col(video::Column::ViewCount).add(1))
+            .and_where(video::Column::Id.eq(id))
+            .and_where(video::Column::DeletedAt.is_null())
+            .returning_col(video::Column::ViewCount)
+            .to_owned();
+
+        let row = self.db
+            .query_one(self.db.get_database_backend().build(&stmt))
+            .await
+            .map_err(AppError::Database)?;
+        row.map(|row| row.try_get("", "view_count"))
+            .transpose()
+            .map_err(AppError::Database)
+    }
+
+    /// Permanently removes a video, whether or not it was soft-deleted
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to remove
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<video::Model>>` - The removed row, or None if not found
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn hard_delete(&self, id: i32) -> AppResult<Option<video::Model>> {
+        let txn = self.begin().await?;
+        let video = self.hard_delete_in(&txn, id).await?;
+        txn.commit().await?;
+        Ok(video)
+    }
+
+    /// Permanently removes a video using the given connection or transaction
+    /// 
+    /// The row is read before it is removed, so callers can report what was
+    /// deleted; pass a transaction to keep the two statements together.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn hard_delete_in<C: ConnectionTrait>(&self, conn: &C, id: i32) -> AppResult<Option<video::Model>> {
+        let Some(video) = Video::find_by_id(id).one(conn).await.map_err(AppError::Database)? else {
+            return Ok(None);
+        };
+        Video::delete_by_id(id)
+            .exec(conn)
+            .await
+            .map_err(AppError::Database)?;
+
+        Ok(Some(video))
+    }
+
+    /// Permanently removes every video, soft-deleted ones included, and every tag
+    /// 
+    /// Tag links, tags and videos are deleted in one transaction, without
+    /// relying on the database to cascade.
+    /// 
+    /// # Returns
+    /// * `AppResult<u64>` - The number of videos removed
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn delete_all(&self) -> AppResult<u64> {
+        let txn = self.begin().await?;
+        video_tag::Entity::delete_many().exec(&txn).await.map_err(AppError::Database)?;
+        tag::Entity::delete_many().exec(&txn).await.map_err(AppError::Database)?;
+        let result = Video::delete_many()
+            .exec(&txn)
+            .await
+            .map_err(AppError::Database)?;
+        txn.commit().await?;
+
+        Ok(result.rows_affected)
+    }
+
+    /// Clears `deleted_at` on a soft-deleted video
+    /// 
+    /// The video goes back at the end of the manual ordering, and live
+    /// positions are renumbered so they stay contiguous.
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to restore
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<video::Model>>` - The restored video, or `None` if
+    ///   no soft-deleted video has this ID
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn restore(&self, id: i32) -> AppResult<Option<video::Model>> {
+        let txn = self.begin().await?;
+        let video = self.restore_in(&txn, id).await?;
+        txn.commit().await?;
+        Ok(video)
+    }
+
+    /// Restores a video using the given connection or transaction, see [`Self::restore`]
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn restore_in<C: ConnectionTrait>(&self, conn: &C, id: i32) -> AppResult<Option<video::Model>> {
+        let video = Video::find_by_id(id).one(conn).await.map_err(AppError::Database)?;
+
+        match video {
+            Some(video) if video.deleted_at.is_some() => {
+                let mut video: video::ActiveModel = video.into();
+                video.deleted_at = Set(None);
+                video.update(conn).await.map_err(AppError::Database)?;
+                video::append_position(conn, id).await.map_err(AppError::Database)?;
+
+                let order = Self::live_order_in(conn).await?;
+                Self::renumber_in(conn, order).await?;
+                self.find_by_id_in(conn, id).await
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Moves a live video to `position` in the manual ordering
+    /// 
+    /// Live videos are renumbered 1, 2, 3, ... in one transaction, so the
+    /// videos between the old and new place shift by one and positions stay
+    /// contiguous. `position` is clamped to the number of live videos.
+    /// Only rows whose position changes are written, and their `updated_at`
+    /// is bumped so cached ETags are invalidated.
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to move
+    /// * `position` - The 1-based place to move it to
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<video::Model>>` - The moved video, or `None` if no live video has this ID
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn reorder(&self, id: i32, position: i32) -> AppResult<Option<video::Model>> {
+        let txn = self.begin().await?;
+        let video = self.reorder_in(&txn, id, position).await?;
+        txn.commit().await?;
+        Ok(video)
+    }
+
+    /// Moves a live video using the given connection or transaction, see [`Self::reorder`]
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn reorder_in<C: ConnectionTrait>(&self, conn: &C, id: i32, position: i32) -> AppResult<Option<video::Model>> {
+        let mut order = Self::live_order_in(conn).await?;
+
+        let Some(index) = order.iter().position(|(video_id, _)| *video_id == id) else {
+            return Ok(None);
+        };
+        let moved = order.remove(index);
+        let target = usize::try_from(position.max(1) - 1).unwrap_or(0).min(order.len());
+        order.insert(target, moved);
+        Self::renumber_in(conn, order).await?;
+
+        self.find_by_id_in(conn, id).await
+    }
+
+    /// Exchanges the manual positions of two live videos
+    /// 
+    /// Both rows are read and written in one transaction, which is rolled
+    /// back when either is missing. Their `updated_at` is bumped so cached
+    /// ETags are invalidated.
+    /// 
+    /// # Arguments
+    /// * `a` - The ID of one video
+    /// * `b` - The ID of the other
+    /// 
+    /// # Returns
+    /// * `AppResult<Option<(video::Model, video::Model)>>` - Both videos with
+    ///   their new positions, or `None` if either has no live row
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn swap_positions(&self, a: i32, b: i32) -> AppResult<Option<(video::Model, video::Model)>> {
+        let txn = self.begin().await?;
+        let swapped = self.swap_positions_in(&txn, a, b).await?;
+        if swapped.is_some() {
+            txn.commit().await?;
+        } else {
+            txn.rollback().await?;
+        }
+        Ok(swapped)
+    }
+
+    /// Swaps two positions using the given connection or transaction, see [`Self::swap_positions`]
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn swap_positions_in<C: ConnectionTrait>(&self, conn: &C, a: i32, b: i32) -> AppResult<Option<(video::Model, video::Model)>> {
+        let (Some(first), Some(second)) = (self.find_by_id_in(conn, a).await?, self.find_by_id_in(conn, b).await?) else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        for (id, position) in [(a, second.position), (b, first.position)] {
+            Video::update_many()
+                .col_expr(video::Column::Position, Expr::value(position))
+                .col_expr(video::Column::UpdatedAt, Expr::value(now))
+                .filter(video::Column::Id.eq(id))
+                .exec(conn)
+                .await
+                .map_err(AppError::Database)?;
+        }
+
+        match (self.find_by_id_in(conn, a).await?, self.find_by_id_in(conn, b).await?) {
+            (Some(first), Some(second)) => Ok(Some((first, second))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns `(id, position)` of every live video in manual order
+    async fn live_order_in<C: ConnectionTrait>(conn: &C) -> AppResult<Vec<(i32, i32)>> {
+        Video::find()
+            .select_only()
+            .column(video::Column::Id)
+            .column(video::Column::Position)
+            .filter(video::Column::DeletedAt.is_null())
+            .order_by_asc(video::Column::Position)
+            .order_by_asc(video::Column::Id)
+            .into_tuple()
+            .all(conn)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Numbers `order` 1, 2, 3, ..., writing only the rows whose position changes
+    /// 
+    /// Their `updated_at` is bumped so cached ETags are invalidated.
+    async fn renumber_in<C: ConnectionTrait>(conn: &C, order: Vec<(i32, i32)>) -> AppResult<()> {
+        let now = Utc::now();
+        for (place, (video_id, old)) in (1..).zip(order) {
+            if place == old {
+                continue;
+            }
+            Video::update_many()
+                .col_expr(video::Column::Position, Expr::value(place))
+                .col_expr(video::Column::UpdatedAt, Expr::value(now))
+                .filter(video::Column::Id.eq(video_id))
+                .exec(conn)
+                .await
+                .map_err(AppError::Database)?;
+        }
+        Ok(())
+    }
+
+    /// Finds a video by its ID whether or not it has been soft-deleted
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_by_id_including_deleted(&self, id: i32) -> AppResult<Option<video::Model>> {
+        self.find_by_id_with_deleted(id, true).await
+    }
+
+    /// Finds a video by its ID, skipping soft-deleted rows unless `include_deleted` is set
+    /// 
+    /// A soft-deleted row is returned with its `deleted_at` populated.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_by_id_with_deleted(&self, id: i32, include_deleted: bool) -> AppResult<Option<video::Model>> {
+        let mut query = Video::find_by_id(id);
+        if !include_deleted {
+            query = query.filter(video::Column::DeletedAt.is_null());
+        }
+        query.one(&self.db).await.map_err(AppError::Database)
+    }
+
+    /// Finds up to `limit` videos with an ID greater than `after_id`, in ID order
+    /// 
+    /// Soft-deleted videos are included so maintenance passes cover every row.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn find_batch_after_in<C: ConnectionTrait>(&self, conn: &C, after_id: i32, limit: u64) -> AppResult<Vec<video::Model>> {
+        Video::find()
+            .filter(video::Column::Id.gt(after_id))
+            .order_by_asc(video::Column::Id)
+            .limit(limit)
+            .all(conn)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Stores a regenerated `title_normalized` key using the given connection or transaction
+    /// 
+    /// Bypasses the entity hooks, so `updated_at` (and with it the ETag) is
+    /// left alone: the video's own fields don't change.
+    /// 
+    /// # Errors
+    /// * `AppError::Conflict` - If unique titles are enforced and another video has the same key
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn set_title_normalized_in<C: ConnectionTrait>(&self, conn: &C, id: i32, key: String) -> AppResult<()> {
+        Video::update_many()
+            .col_expr(video::Column::TitleNormalized, Expr::value(key))
+            .filter(video::Column::Id.eq(id))
+            .exec(conn)
+            .await
+            .map_err(Self::write_error)?;
+        Ok(())
+    }
+
+    /// Maps an insert or update failure, reporting unique violations as conflicts
+    fn write_error(err: DbErr) -> AppError {
+        match err.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(detail)) if detail.contains("title_normalized") => {
+                AppError::Conflict("a video with this title already exists".to_string())
+            }
+            Some(SqlErr::UniqueConstraintViolation(detail)) if detail.contains("youtube_id") => {
+                AppError::Conflict("youtube_id already exists".to_string())
+            }
+            Some(SqlErr::UniqueConstraintViolation(_)) => {
+                AppError::Conflict("video conflicts with an existing one".to_string())
+            }
+            _ => AppError::Database(err),
+        }
+    }
+
+    /// Reports a write whose expected version no longer matches the stored row
+    fn stale_update() -> AppError {
+        AppError::PreconditionFailed("video was modified since it was read".to_string())
+    }
+
+    /// Builds a condition matching `word` as a whole word of the title
+    /// 
+    /// Punctuation in the title is treated as a word separator, and LIKE
+    /// wildcards in `word` are escaped so they match literally.
+    fn title_has_word(word: &str) -> SimpleExpr {
+        const SEPARATORS: [char; 10] = [',', '.', '!', '?', ':', ';', '-', '(', ')', '"'];
+
+        let mut title = String::from("title");
+        for separator in SEPARATORS {
+            title = format!("REPLACE({}, '{}', ' ')", title, separator);
+        }
+
+        // Bound through `LikeExpr`: a `?` placeholder in this custom SQL was
+        // left unrewritten on Postgres, next to the quoted '?' separator
+        Expr::expr(Expr::cust(format!("(' ' || {} || ' ')", title)))
+            .like(LikeExpr::new(format!("% {} %", Self::escape_like(word))).escape('\\'))
+    }
+
+    /// Escapes LIKE wildcards in `value` so they match literally with `ESCAPE '\'`
+    fn escape_like(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    }
+
+    /// Builds the condition for `search`, honouring the search options
+    /// 
+    /// The term is matched against the title and the `youtube_id`.
+    /// `whole_word` takes precedence over `search_mode` and only applies to
+    /// the title, as YouTube IDs have no words.
+    fn search_matches(query: &VideoQuery, search: &str) -> Condition {
+        if query.whole_word.unwrap_or(false) {
+            return Condition::all().add(Self::title_has_word(search));
+        }
+
+        Condition::any()
+            .add(Self::column_matches(video::Column::Title, query, search))
+            .add(Self::column_matches(video::Column::YoutubeId, query, search))
+    }
+
+    /// Decides whether `query` is answered by the full-text index
+    /// 
+    /// Only when `fts` is set and `search` has words to match. SQLite needs
+    /// the `videos_fts` table, which is missing when its build lacks FTS5;
+    /// MySQL has no supported index. Without one, the regular search applies.
+    async fn full_text(&self, query: &VideoQuery) -> AppResult<Option<FullText>> {
+        let has_words = query.search.as_deref().is_some_and(|search| !Self::fts5_terms(search).is_empty());
+        if !query.fts.unwrap_or(false) || !has_words {
+            return Ok(None);
+        }
+
+        match self.db.get_database_backend() {
+            DbBackend::Postgres => Ok(Some(FullText::TsVector)),
+            DbBackend::Sqlite => {
+                let table = self.db
+                    .query_one(Statement::from_string(
+                        DbBackend::Sqlite,
+                        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'videos_fts'",
+                    ))
+                    .await
+                    .map_err(AppError::Database)?;
+                Ok(table.map(|_| FullText::Fts5))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Quotes each word of `search` for an FTS5 `MATCH`, so every word must match
+    /// 
+    /// Quoting keeps FTS5 operators and punctuation in the input from being
+    /// parsed as query syntax.
+    fn fts5_terms(search: &str) -> String {
+        search
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{}\"", word))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Builds the condition matching `search` through the full-text index
+    /// 
+    /// Words are stemmed, so `running` also finds `run`, and the title and
+    /// description are searched rather than the `youtube_id`.
+    fn full_text_matches(full_text: FullText, search: &str) -> SimpleExpr {
+        match full_text {
+            FullText::Fts5 => Expr::cust_with_values(
+                r#""videos"."id" IN (SELECT rowid FROM "videos_fts" WHERE "videos_fts" MATCH ?)"#,
+                [Self::fts5_terms(search)],
+            ),
+            FullText::TsVector => Expr::cust_with_values(
+                format!("{} @@ plainto_tsquery('english', $1)", TSVECTOR),
+                [search.to_string()],
+            ),
+        }
+    }
+
+    /// Orders rows matched by `full_text_matches`, most relevant first
+    fn order_by_relevance(select: Select<Video>, full_text: FullText, search: &str) -> Select<Video> {
+        match full_text {
+            // FTS5's `rank` is a bm25 score where lower is better
+            FullText::Fts5 => select.order_by(
+                Expr::cust_with_values(
+                    r#"(SELECT rank FROM "videos_fts" WHERE "videos_fts" MATCH ? AND rowid = "videos"."id")"#,
+                    [Self::fts5_terms(search)],
+                ),
+                Order::Asc,
+            ),
+            FullText::TsVector => select.order_by(
+                Expr::cust_with_values(
+                    format!("ts_rank({}, plainto_tsquery('english', $1))", TSVECTOR),
+                    [search.to_string()],
+                ),
+                Order::Desc,
+            ),
+        }
+    }
+
+    /// Builds the `search_mode` condition on one column
+    /// 
+    /// With `case_insensitive`, both the column and the search term are lowercased.
+    fn column_matches(column: video::Column, query: &VideoQuery, search: &str) -> SimpleExpr {
+        let (value, search) = if query.case_insensitive.unwrap_or(false) {
+            (Expr::expr(Func::lower(Expr::col(column))), search.to_lowercase())
+        } else {
+            (Expr::col(column), search.to_string())
+        };
+
+        match query.search_mode.unwrap_or_default() {
+            SearchMode::Contains => {
+                value.like(LikeExpr::new(format!("%{}%", Self::escape_like(&search))).escape('\\'))
+            }
+            SearchMode::Prefix => {
+                value.like(LikeExpr::new(format!("{}%", Self::escape_like(&search))).escape('\\'))
+            }
+            SearchMode::Exact => value.eq(search),
         }
     }
 
@@ -136,26 +1129,154 @@ impl VideoRepository {
     /// 
     /// # Arguments
     /// * `query` - Query parameters for filtering and pagination
+    /// * `sorts` - The resolved ordering, tie-broken by ID
     /// 
     /// # Returns
     /// * `AppResult<(Vec<video::Model>, u64)>` - Tuple of videos and total count
     /// 
     /// # Errors
     /// * `AppError::Database` - If there's an error executing the query
-    pub async fn list(&self, query: &VideoQuery) -> AppResult<(Vec<video::Model>, u64)> {
-        let page = query.page.unwrap_or(1);
-        let per_page = query.per_page.unwrap_or(10);
-        
-        let mut db_query = Video::find()
+    pub async fn list(&self, query: &VideoQuery, sorts: &[Sort]) -> AppResult<(Vec<video::Model>, u64)> {
+        let full_text = self.full_text(query).await?;
+        let db_query = Self::filtered(query, full_text)
             .filter(video::Column::DeletedAt.is_null());
 
-        if let Some(search) = &query.search {
-            db_query = db_query.filter(video::Column::Title.contains(search));
+        self.paginated(db_query, query, sorts, full_text).await
+    }
+
+    /// Lists soft-deleted videos with the same pagination and filtering as `list`
+    /// 
+    /// # Arguments
+    /// * `query` - Query parameters for filtering and pagination
+    /// * `sorts` - The resolved ordering, tie-broken by ID
+    /// 
+    /// # Returns
+    /// * `AppResult<(Vec<video::Model>, u64)>` - Tuple of deleted videos and total count
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn list_deleted(&self, query: &VideoQuery, sorts: &[Sort]) -> AppResult<(Vec<video::Model>, u64)> {
+        let full_text = self.full_text(query).await?;
+        let db_query = Self::filtered(query, full_text)
+            .filter(video::Column::DeletedAt.is_not_null());
+
+        self.paginated(db_query, query, sorts, full_text).await
+    }
+
+    /// Lists up to `limit` videos on one side of a keyset cursor
+    /// 
+    /// Rows are ordered by `(created_at, id)` in `direction`. Going forward
+    /// returns rows after `cursor` in that order; going `backward` returns the
+    /// rows before it, nearest first, so callers reverse them for display.
+    /// 
+    /// # Returns
+    /// * `AppResult<(Vec<video::Model>, u64)>` - Tuple of videos and the total count of matching rows
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn list_keyset(
+        &self,
+        query: &VideoQuery,
+        direction: SortDirection,
+        cursor: Option<Cursor>,
+        backward: bool,
+        limit: u64,
+    ) -> AppResult<(Vec<video::Model>, u64)> {
+        let full_text = self.full_text(query).await?;
+        let filtered = Self::filtered(query, full_text)
+            .filter(video::Column::DeletedAt.is_null());
+        let total = filtered.clone().count(&self.db).await.map_err(AppError::Database)?;
+
+        let descending = (direction == SortDirection::Desc) != backward;
+        let mut db_query = filtered;
+        if let Some(cursor) = cursor {
+            let (created_at, id) = (video::Column::CreatedAt, video::Column::Id);
+            db_query = db_query.filter(if descending {
+                Condition::any()
+                    .add(created_at.lt(cursor.created_at))
+                    .add(Condition::all().add(created_at.eq(cursor.created_at)).add(id.lt(cursor.id)))
+            } else {
+                Condition::any()
+                    .add(created_at.gt(cursor.created_at))
+                    .add(Condition::all().add(created_at.eq(cursor.created_at)).add(id.gt(cursor.id)))
+            });
+        }
+
+        let direction = if descending { SortDirection::Desc } else { SortDirection::Asc };
+        let videos = Sort::apply(db_query, &[Sort { key: SortKey::CreatedAt, direction }])
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(AppError::Database)?;
+
+        Ok((videos, total))
+    }
+
+    /// Lists up to `limit` live videos with an id greater than `after`, ordered by id
+    ///
+    /// Unlike `list` this runs no count, so each page costs one query however
+    /// deep the client has paged. `None` starts from the first video.
+    ///
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn list_after_id(
+        &self,
+        query: &VideoQuery,
+        after: Option<i32>,
+        limit: u64,
+    ) -> AppResult<Vec<video::Model>> {
+        let full_text = self.full_text(query).await?;
+        let mut db_query = Self::filtered(query, full_text)
+            .filter(video::Column::DeletedAt.is_null());
+        if let Some(after) = after {
+            db_query = db_query.filter(video::Column::Id.gt(after));
         }
 
-        let paginator = db_query
-            .order_by_desc(video::Column::CreatedAt)
-            .paginate(&self.db, per_page);
+        db_query
+            .order_by_asc(video::Column::Id)
+            .limit(limit)
+            .all(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Counts videos matching the query's filters, soft-deleted ones included
+    /// 
+    /// # Arguments
+    /// * `query` - Query parameters whose filters are applied
+    /// 
+    /// # Returns
+    /// * `AppResult<u64>` - Number of matching rows regardless of `deleted_at`
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn count_including_deleted(&self, query: &VideoQuery) -> AppResult<u64> {
+        let full_text = self.full_text(query).await?;
+        Self::filtered(query, full_text)
+            .count(&self.db)
+            .await
+            .map_err(AppError::Database)
+    }
+
+    /// Orders `select` and fetches the query's page, returning it with the total row count
+    /// 
+    /// Full-text matches are ranked by relevance first, with `sorts` breaking ties.
+    async fn paginated(
+        &self,
+        select: Select<Video>,
+        query: &VideoQuery,
+        sorts: &[Sort],
+        full_text: Option<FullText>,
+    ) -> AppResult<(Vec<video::Model>, u64)> {
+        let page = query.page.unwrap_or(1);
+        let per_page = query.per_page.unwrap_or(10);
+
+        // Relevance comes first; the requested ordering breaks ties
+        let select = match (full_text, &query.search) {
+            (Some(full_text), Some(search)) => Self::order_by_relevance(select, full_text, search),
+            _ => select,
+        };
+        let paginator = Sort::apply(select, sorts).paginate(&self.db, per_page);
 
         let total = paginator.num_items().await.map_err(AppError::Database)?;
         let videos = paginator
@@ -165,4 +1286,55 @@ impl VideoRepository {
 
         Ok((videos, total))
     }
+
+    /// Applies the query's search and date filters, without the soft-delete filter
+    fn filtered(query: &VideoQuery, full_text: Option<FullText>) -> Select<Video> {
+        let mut db_query = Video::find();
+
+        if let Some(search) = &query.search {
+            db_query = match full_text {
+                Some(full_text) => db_query.filter(Self::full_text_matches(full_text, search)),
+                None => db_query.filter(Self::search_matches(query, search)),
+            };
+        }
+
+        if let Some(created_after) = query.created_after {
+            db_query = db_query.filter(video::Column::CreatedAt.gte(created_after.instant()));
+        }
+
+        if let Some(created_before) = query.created_before {
+            db_query = db_query.filter(video::Column::CreatedAt.lte(created_before.instant()));
+        }
+
+        if let Some(ids) = &query.ids {
+            db_query = db_query.filter(video::Column::Id.is_in(ids.iter().copied()));
+        }
+
+        if let Some(youtube_ids) = &query.youtube_ids {
+            db_query = db_query.filter(video::Column::YoutubeId.is_in(youtube_ids.iter().cloned()));
+        }
+
+        if let Some(status) = query.status {
+            db_query = db_query.filter(video::Column::Status.eq(status));
+        }
+
+        if let Some(name) = &query.tag {
+            db_query = db_query.filter(
+                video::Column::Id.in_subquery(
+                    Query::select()
+                        .column(video_tag::Column::VideoId)
+                        .from(video_tag::Entity)
+                        .inner_join(
+                            tag::Entity,
+                            Expr::col((tag::Entity, tag::Column::Id))
+                                .equals((video_tag::Entity, video_tag::Column::TagId)),
+                        )
+                        .and_where(Expr::col((tag::Entity, tag::Column::Name)).eq(tag::normalize_name(name)))
+                        .to_owned(),
+                ),
+            );
+        }
+
+        db_query
+    }
 }
@@ -0,0 +1,56 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+
+use crate::error::{AppError, AppResult};
+
+/// Position of a video in the `(created_at, id)` list ordering
+///
+/// Serialized as an opaque URL-safe token so clients treat it as a bookmark
+/// rather than something to construct themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: i32,
+}
+
+impl Cursor {
+    /// Encodes the cursor into its public token form
+    pub fn encode(&self) -> String {
+        let nanos = self.created_at.timestamp_nanos_opt().unwrap_or_default();
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", nanos, self.id))
+    }
+
+    /// Decodes a token produced by `encode`
+    ///
+    /// # Errors
+    /// * `AppError::BadRequest` - If the token is malformed
+    pub fn decode(token: &str) -> AppResult<Self> {
+        let invalid = || AppError::BadRequest("invalid cursor".to_string());
+
+        let raw = URL_SAFE_NO_PAD.decode(token).map_err(|_| invalid())?;
+        let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+        let (nanos, id) = raw.split_once(':').ok_or_else(invalid)?;
+        let nanos: i64 = nanos.parse().map_err(|_| invalid())?;
+
+        Ok(Self {
+            created_at: DateTime::from_timestamp_nanos(nanos),
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trips() {
+        let cursor = Cursor {
+            created_at: DateTime::from_timestamp_nanos(1_715_860_800_123_456_789),
+            id: 42,
+        };
+        assert_eq!(Cursor::decode(&cursor.encode()).unwrap(), cursor);
+        assert!(matches!(Cursor::decode("not a cursor"), Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,112 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::api::VideoResponse;
+
+/// How many events a slow subscriber may fall behind before it starts missing them
+pub const EVENT_BUFFER: usize = 256;
+
+/// Kind of change announced to event subscribers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A committed change to a single video
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoEvent {
+    #[serde(rename = "type")]
+    pub kind: EventKind,
+    pub video: VideoResponse,
+}
+
+/// Broadcast channel announcing committed video changes, for live dashboards
+///
+/// Publishing never blocks: events sent while nobody is subscribed are
+/// dropped, and a subscriber more than `EVENT_BUFFER` events behind skips
+/// the oldest ones. Clones share the same channel.
+#[derive(Clone)]
+pub struct VideoEvents {
+    sender: broadcast::Sender<VideoEvent>,
+}
+
+impl VideoEvents {
+    /// Creates a channel buffering `EVENT_BUFFER` events per subscriber
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        Self { sender }
+    }
+
+    /// Announces a committed change to every current subscriber
+    pub fn publish(&self, kind: EventKind, video: &VideoResponse) {
+        if !self.has_subscribers() {
+            return;
+        }
+        let _ = self.sender.send(VideoEvent { kind, video: video.clone() });
+    }
+
+    /// Returns true when at least one subscriber is listening
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    /// Returns a receiver for the events published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<VideoEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for VideoEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::entity::video::VideoStatus;
+
+    fn video(id: i32) -> VideoResponse {
+        VideoResponse {
+            id,
+            title: "Title".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: VideoStatus::Draft,
+            position: id,
+            view_count: 0,
+            tags: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            matched_fields: None,
+        }
+    }
+
+    #[test]
+    fn test_subscribers_receive_events_published_after_subscribing() {
+        let events = VideoEvents::new();
+        events.publish(EventKind::Created, &video(1));
+
+        let mut rx = events.subscribe();
+        events.publish(EventKind::Deleted, &video(2));
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.kind, EventKind::Deleted);
+        assert_eq!(event.video.id, 2);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_event_serializes_with_type_tag() {
+        let event = VideoEvent { kind: EventKind::Updated, video: video(3) };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["type"], "updated");
+        assert_eq!(value["video"]["id"], 3);
+    }
+}
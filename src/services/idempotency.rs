@@ -0,0 +1,107 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{AppError, AppResult};
+
+/// Outcome of claiming an `Idempotency-Key`
+#[derive(Debug, PartialEq, Eq)]
+pub enum Claim {
+    /// The key is new; the caller performs the create and then completes or releases it
+    Proceed,
+    /// A create with this key already succeeded, producing the video with this id
+    Replay(i32),
+}
+
+enum Entry {
+    InFlight,
+    Done { video_id: i32, expires_at: DateTime<Utc> },
+}
+
+/// In-memory map of `Idempotency-Key` → created video id
+///
+/// Keys are remembered for `ttl` after the create completes; a restart
+/// forgets them all. Clones share the same map.
+#[derive(Clone)]
+pub struct IdempotencyKeys {
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl IdempotencyKeys {
+    /// Creates a store remembering completed keys for `ttl_secs` seconds
+    pub fn new(ttl_secs: u64) -> Self {
+        Self {
+            ttl: Duration::seconds(i64::try_from(ttl_secs).unwrap_or(i64::MAX / 1000)),
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Claims `key` for a create starting at `now`
+    ///
+    /// # Errors
+    /// * `AppError::Conflict` - If another request with the same key hasn't finished yet
+    pub fn claim(&self, key: &str, now: DateTime<Utc>) -> AppResult<Claim> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Forget expired keys so the map doesn't grow forever
+        entries.retain(|_, entry| match entry {
+            Entry::InFlight => true,
+            Entry::Done { expires_at, .. } => *expires_at > now,
+        });
+
+        match entries.get(key) {
+            Some(Entry::InFlight) => Err(AppError::Conflict(
+                "a request with this Idempotency-Key is still in progress".to_string(),
+            )),
+            Some(Entry::Done { video_id, .. }) => Ok(Claim::Replay(*video_id)),
+            None => {
+                entries.insert(key.to_string(), Entry::InFlight);
+                Ok(Claim::Proceed)
+            }
+        }
+    }
+
+    /// Records that the create claimed under `key` produced `video_id`
+    pub fn complete(&self, key: &str, video_id: i32, now: DateTime<Utc>) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.insert(key.to_string(), Entry::Done { video_id, expires_at: now + self.ttl });
+    }
+
+    /// Releases `key` after a failed create so the client may retry with it
+    pub fn release(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if matches!(entries.get(key), Some(Entry::InFlight)) {
+            entries.remove(key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_completed_key_replays_until_expiry() {
+        let keys = IdempotencyKeys::new(60);
+        let now = Utc.with_ymd_and_hms(2024, 5, 16, 12, 0, 0).unwrap();
+
+        assert_eq!(keys.claim("abc", now).unwrap(), Claim::Proceed);
+        assert!(matches!(keys.claim("abc", now), Err(AppError::Conflict(_))));
+
+        keys.complete("abc", 7, now);
+        assert_eq!(keys.claim("abc", now + Duration::seconds(59)).unwrap(), Claim::Replay(7));
+        assert_eq!(keys.claim("abc", now + Duration::seconds(61)).unwrap(), Claim::Proceed);
+    }
+
+    #[test]
+    fn test_released_key_can_be_claimed_again() {
+        let keys = IdempotencyKeys::new(60);
+        let now = Utc.with_ymd_and_hms(2024, 5, 16, 12, 0, 0).unwrap();
+
+        assert_eq!(keys.claim("abc", now).unwrap(), Claim::Proceed);
+        keys.release("abc");
+        assert_eq!(keys.claim("abc", now).unwrap(), Claim::Proceed);
+    }
+}
@@ -1 +1,7 @@
+pub mod cursor;
+pub mod events;
+pub mod idempotency;
+pub mod quota;
+pub mod recent_ops;
+pub mod validation;
 pub mod video_service;
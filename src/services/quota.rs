@@ -0,0 +1,118 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{AppError, AppResult};
+
+/// Per-IP daily counter limiting how many videos a client may create
+///
+/// Counters live in memory and reset at midnight UTC, so a restart also
+/// resets every client's allowance. Clones share the same counters.
+#[derive(Clone)]
+pub struct CreateQuota {
+    limit: u32,
+    usage: Arc<Mutex<HashMap<IpAddr, (NaiveDate, u32)>>>,
+}
+
+impl CreateQuota {
+    /// Creates a quota allowing `limit` creates per IP per UTC day
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            usage: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Consumes one create from `ip`'s allowance for the day containing `now`
+    ///
+    /// # Errors
+    /// * `AppError::TooManyRequests` - If the allowance is used up, carrying the reset time
+    pub fn acquire(&self, ip: IpAddr, now: DateTime<Utc>) -> AppResult<()> {
+        self.acquire_many(ip, now, 1)
+    }
+
+    /// Consumes `count` creates at once, or none if they don't all fit
+    ///
+    /// # Errors
+    /// * `AppError::TooManyRequests` - If the remaining allowance is smaller than `count`
+    pub fn acquire_many(&self, ip: IpAddr, now: DateTime<Utc>, count: u32) -> AppResult<()> {
+        let today = now.date_naive();
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Forget counters from previous days so the map doesn't grow forever
+        usage.retain(|_, (day, _)| *day == today);
+
+        let (_, used) = usage.entry(ip).or_insert((today, 0));
+        if used.saturating_add(count) > self.limit {
+            let reset_at = Utc.from_utc_datetime(&(today + Duration::days(1)).and_time(NaiveTime::MIN));
+            return Err(AppError::TooManyRequests {
+                message: format!("daily create quota of {} exceeded", self.limit),
+                reset_at,
+            });
+        }
+
+        *used += count;
+        Ok(())
+    }
+
+    /// Gives back `count` creates taken for a request that ended up writing nothing
+    ///
+    /// A counter from an earlier day has already been reset, so nothing is
+    /// returned to it.
+    pub fn release(&self, ip: IpAddr, now: DateTime<Utc>, count: u32) {
+        let mut usage = self.usage.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((day, used)) = usage.get_mut(&ip) {
+            if *day == now.date_naive() {
+                *used = used.saturating_sub(count);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quota_resets_at_midnight_utc() {
+        let quota = CreateQuota::new(1);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let evening = Utc.with_ymd_and_hms(2024, 5, 16, 23, 59, 0).unwrap();
+
+        assert!(quota.acquire(ip, evening).is_ok());
+        match quota.acquire(ip, evening) {
+            Err(AppError::TooManyRequests { reset_at, .. }) => {
+                assert_eq!(reset_at, Utc.with_ymd_and_hms(2024, 5, 17, 0, 0, 0).unwrap());
+            }
+            other => panic!("expected quota error, got {:?}", other),
+        }
+
+        let next_day = Utc.with_ymd_and_hms(2024, 5, 17, 0, 0, 1).unwrap();
+        assert!(quota.acquire(ip, next_day).is_ok());
+    }
+
+    #[test]
+    fn test_acquire_many_is_all_or_nothing() {
+        let quota = CreateQuota::new(3);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 5, 16, 12, 0, 0).unwrap();
+
+        assert!(quota.acquire_many(ip, now, 2).is_ok());
+        assert!(quota.acquire_many(ip, now, 2).is_err());
+        assert!(quota.acquire(ip, now).is_ok());
+        assert!(quota.acquire(ip, now).is_err());
+    }
+
+    #[test]
+    fn test_release_returns_allowance() {
+        let quota = CreateQuota::new(2);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let now = Utc.with_ymd_and_hms(2024, 5, 16, 12, 0, 0).unwrap();
+
+        assert!(quota.acquire_many(ip, now, 2).is_ok());
+        quota.release(ip, now, 2);
+        assert!(quota.acquire_many(ip, now, 2).is_ok());
+        assert!(quota.acquire(ip, now).is_err());
+    }
+}
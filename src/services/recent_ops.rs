@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Kind of write recorded in the recent operations ring
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpKind {
+    Create,
+    Update,
+    Delete,
+    Restore,
+}
+
+/// A single committed write
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentOp {
+    pub op: OpKind,
+    pub id: i32,
+    pub at: DateTime<Utc>,
+}
+
+/// Bounded in-memory log of the most recent writes, for debugging
+///
+/// Holds at most `capacity` entries, dropping the oldest first. Nothing is
+/// persisted, so the log starts empty after a restart. A capacity of zero
+/// disables recording. Clones share the same log.
+#[derive(Clone)]
+pub struct RecentOps {
+    capacity: usize,
+    ops: Arc<Mutex<VecDeque<RecentOp>>>,
+}
+
+impl RecentOps {
+    /// Creates a ring keeping the last `capacity` operations
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ops: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    /// Records a committed write
+    pub fn record(&self, op: OpKind, id: i32) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut ops = self.ops.lock().unwrap_or_else(|e| e.into_inner());
+        if ops.len() == self.capacity {
+            ops.pop_front();
+        }
+        ops.push_back(RecentOp { op, id, at: Utc::now() });
+    }
+
+    /// Returns the recorded operations, oldest first
+    pub fn snapshot(&self) -> Vec<RecentOp> {
+        self.ops.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_drops_oldest_when_full() {
+        let ring = RecentOps::new(2);
+        ring.record(OpKind::Create, 1);
+        ring.record(OpKind::Update, 1);
+        ring.record(OpKind::Delete, 1);
+
+        let kinds: Vec<OpKind> = ring.snapshot().iter().map(|op| op.op).collect();
+        assert_eq!(kinds, vec![OpKind::Update, OpKind::Delete]);
+    }
+
+    #[test]
+    fn test_zero_capacity_records_nothing() {
+        let ring = RecentOps::new(0);
+        ring.record(OpKind::Create, 1);
+        assert!(ring.snapshot().is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use validator::{Validate, ValidationErrors};
+
+use crate::api::{CreateVideoRequest, UpdateVideoRequest};
+use crate::config::Config;
+use crate::error::{field_messages, AppError, AppResult, VALIDATION_FAILED};
+
+/// The write operation a validation step is inspecting
+#[derive(Debug, Clone, Copy)]
+pub enum VideoWrite<'a> {
+    Create(&'a CreateVideoRequest),
+    Update { id: i32, req: &'a UpdateVideoRequest },
+}
+
+/// A single rule run by `VideoService` before a video is persisted
+///
+/// Steps run in the order they were registered and the first error aborts
+/// the write, so cheap checks should be registered before expensive ones.
+///
+/// # Example
+///
+/// ```no_run
+/// use async_trait::async_trait;
+/// use ntex_api::error::{AppError, AppResult};
+/// use ntex_api::services::validation::{ValidationStep, VideoWrite};
+///
+/// struct NoPlaceholderTitles;
+///
+/// #[async_trait]
+/// impl ValidationStep for NoPlaceholderTitles {
+///     async fn validate(&self, write: &VideoWrite<'_>) -> AppResult<()> {
+///         let title = match write {
+///             VideoWrite::Create(req) => Some(req.title.as_str()),
+///             VideoWrite::Update { req, .. } => req.title.as_deref(),
+///         };
+///         if title.is_some_and(|t| t.eq_ignore_ascii_case("untitled")) {
+///             return Err(AppError::Validation("title must not be a placeholder".to_string()));
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait ValidationStep: Send + Sync {
+    /// Checks the pending write, returning an error to reject it
+    async fn validate(&self, write: &VideoWrite<'_>) -> AppResult<()>;
+}
+
+/// Runs the `validator` derive rules declared on the request structs
+///
+/// With `echo_max_chars` set, errors carry each offending field's received
+/// value, truncated to that many characters, to help debug clients.
+#[derive(Default)]
+pub struct FieldRules {
+    pub echo_max_chars: Option<usize>,
+}
+
+impl FieldRules {
+    fn error(&self, errors: ValidationErrors) -> AppError {
+        let Some(max_chars) = self.echo_max_chars else {
+            return errors.into();
+        };
+
+        let received: BTreeMap<String, String> = errors
+            .field_errors()
+            .into_iter()
+            .filter_map(|(field, errors)| {
+                let value = errors.iter().find_map(|e| e.params.get("value"))?;
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                Some((field.to_string(), value.chars().take(max_chars).collect()))
+            })
+            .collect();
+
+        AppError::InvalidValue {
+            message: VALIDATION_FAILED.to_string(),
+            fields: field_messages(&errors),
+            received,
+        }
+    }
+}
+
+#[async_trait]
+impl ValidationStep for FieldRules {
+    async fn validate(&self, write: &VideoWrite<'_>) -> AppResult<()> {
+        let result = match write {
+            VideoWrite::Create(req) => req.validate(),
+            VideoWrite::Update { req, .. } => req.validate(),
+        };
+        result.map_err(|e| self.error(e))
+    }
+}
+
+/// The validation chain every `VideoService` starts with
+pub fn default_steps(config: &Config) -> Vec<Arc<dyn ValidationStep>> {
+    vec![Arc::new(FieldRules {
+        echo_max_chars: config.validation_echo_max_chars,
+    })]
+}
@@ -1,9 +1,115 @@
-use crate::api::{CreateVideoRequest, UpdateVideoRequest, VideoResponse, PaginatedVideoResponse};
-use crate::db::VideoQuery;
+use crate::api::{
+    BatchDeleteResponse, CreateVideoRequest, CursorVideoResponse, DuplicateGroup, DuplicatesResponse, UpdateVideoRequest, VideoResponse, PaginatedVideoResponse,
+    FetchVideosRequest, FetchVideosResponse, MoveVideoRequest, ReindexDbResponse, ReindexQuery, ReindexResponse, ResetResponse, SwapVideosResponse, VideoStatsResponse,
+    ImportResponse, ImportRowError, ImportValidationResponse, RelatedQuery, RelatedVideosResponse, ViewCountResponse,
+};
+use crate::config::Config;
+use crate::db::{Period, Sort, SortDirection, SortKey, VideoQuery};
 use crate::error::{AppError, AppResult};
-use crate::repositories::video_repository::VideoRepository;
+use crate::entity::{tag, video};
+use crate::repositories::traits::VideoRepositoryTrait;
+use crate::repositories::video_repository::{NewVideo, VideoChanges, VideoRepository};
+use crate::services::cursor::Cursor;
+use crate::services::events::{EventKind, VideoEvent, VideoEvents};
+use crate::services::recent_ops::{OpKind, RecentOp, RecentOps};
+use crate::services::validation::{self, ValidationStep, VideoWrite};
+use chrono::{DateTime, Utc};
+use migration::{indexes, SchemaManager};
+use sea_orm::{ConnectionTrait, DbBackend};
+use std::sync::Arc;
 use validator::Validate;
 
+/// Number of rows each reindex transaction processes
+pub const REINDEX_BATCH_SIZE: u64 = 100;
+
+/// Returns the video id from a raw id or a YouTube URL
+///
+/// Accepts `youtube.com/watch?v=`, `youtu.be/` and `youtube.com/shorts/`
+/// links, with or without scheme, `www.`/`m.` prefix and extra query
+/// parameters. Returns `None` when `input` is neither a well-formed id nor
+/// one of those URLs.
+///
+/// # Example
+///
+/// ```
+/// use ntex_api::services::video_service::extract_youtube_id;
+///
+/// let id = extract_youtube_id("https://youtu.be/dQw4w9WgXcQ?t=42");
+/// assert_eq!(id.as_deref(), Some("dQw4w9WgXcQ"));
+/// ```
+pub fn extract_youtube_id(input: &str) -> Option<String> {
+    let is_id = |id: &str| {
+        id.len() == 11 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+
+    let input = input.trim();
+    if is_id(input) {
+        return Some(input.to_string());
+    }
+
+    let rest = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(input);
+    let rest = rest
+        .strip_prefix("www.")
+        .or_else(|| rest.strip_prefix("m."))
+        .unwrap_or(rest);
+
+    let candidate = if let Some(path) = rest.strip_prefix("youtu.be/") {
+        path.split(['?', '#', '/']).next()
+    } else if let Some(path) = rest.strip_prefix("youtube.com/shorts/") {
+        path.split(['?', '#', '/']).next()
+    } else if let Some(query) = rest.strip_prefix("youtube.com/watch?") {
+        query
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("v="))
+    } else {
+        None
+    };
+
+    candidate.filter(|id| is_id(id)).map(str::to_string)
+}
+
+/// Names the fields of `video` containing `search`, ignoring case
+fn matched_fields(video: &VideoResponse, search: &str) -> Vec<String> {
+    let search = search.to_lowercase();
+    [("title", &video.title), ("youtube_id", &video.youtube_id)]
+        .into_iter()
+        .filter(|(_, value)| value.to_lowercase().contains(&search))
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+/// Largest number of videos a single batch create may carry
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Number of related videos returned when the request doesn't ask for a limit
+pub const DEFAULT_RELATED_LIMIT: u64 = 5;
+
+/// Largest number of related videos a single request may ask for
+pub const MAX_RELATED_LIMIT: u64 = 50;
+
+/// Number of rows each export query fetches
+pub const EXPORT_PAGE_SIZE: u64 = 500;
+
+/// Words too common to make two titles related
+const RELATED_STOP_WORDS: [&str; 8] = ["the", "and", "for", "with", "from", "this", "that", "you"];
+
+/// Splits a title into the lowercase words considered when ranking related videos
+///
+/// Words shorter than three characters and common stop words are ignored.
+fn relatedness_words(title: &str) -> std::collections::BTreeSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 3 && !RELATED_STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
 /// Service layer for handling video business logic
 /// 
 /// This service implements the business logic for video operations, including:
@@ -25,12 +131,21 @@ use validator::Validate;
 ///     let service = VideoService::new(repo);
 /// }
 /// ```
+/// 
+/// Generic over its repository so tests can run it against an in-memory
+/// [`VideoRepositoryTrait`] implementation. Constructors, configuration and
+/// `get_video` work with any repository; everything else runs inside
+/// database transactions and needs the sea-orm `VideoRepository`.
 #[derive(Clone)]
-pub struct VideoService {
-    repository: VideoRepository,
+pub struct VideoService<R = VideoRepository> {
+    repository: R,
+    config: Config,
+    validation_steps: Vec<Arc<dyn ValidationStep>>,
+    recent_ops: RecentOps,
+    events: VideoEvents,
 }
 
-impl VideoService {
+impl<R: VideoRepositoryTrait> VideoService<R> {
     /// Creates a new instance of VideoService
     /// 
     /// # Arguments
@@ -48,58 +163,61 @@ impl VideoService {
     ///     let service = VideoService::new(repo);
     /// }
     /// ```
-    pub fn new(repository: VideoRepository) -> Self {
-        Self { repository }
+    pub fn new(repository: R) -> Self {
+        Self::with_config(repository, Config::default())
     }
 
-    /// Creates a new video
+    /// Creates a new instance of VideoService with an explicit configuration
     /// 
     /// # Arguments
-    /// * `req` - The video creation request containing title and youtube_id
-    /// 
-    /// # Returns
-    /// * `AppResult<VideoResponse>` - The created video on success
-    /// 
-    /// # Errors
-    /// * `AppError::Validation` - If the input data is invalid
-    /// * `AppError::Database` - If there's an error saving to the database
+    /// * `repository` - The video repository instance for data access
+    /// * `config` - Application configuration driving optional behaviour
     /// 
     /// # Example
     /// 
     /// ```no_run
-    /// use ntex_api::api::CreateVideoRequest;
+    /// use ntex_api::config::Config;
     /// use ntex_api::services::video_service::VideoService;
     /// use ntex_api::repositories::video_repository::VideoRepository;
     /// use sea_orm::DatabaseConnection;
     /// 
-    /// async fn create_video(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+    /// async fn setup(db: DatabaseConnection) {
     ///     let repo = VideoRepository::new(db);
-    ///     let service = VideoService::new(repo);
-    /// 
-    ///     let request = CreateVideoRequest {
-    ///         title: "My Awesome Video".to_string(),
-    ///         youtube_id: "dQw4w9WgXcQ".to_string(),
-    ///     };
-    /// 
-    ///     let video = service.create_video(request).await?;
-    ///     assert_eq!(video.title, "My Awesome Video");
-    ///     Ok(())
+    ///     let service = VideoService::with_config(repo, Config::from_env().expect("invalid configuration"));
     /// }
     /// ```
-    pub async fn create_video(&self, req: CreateVideoRequest) -> AppResult<VideoResponse> {
-        if let Err(e) = req.validate() {
-            return Err(AppError::Validation(e.to_string()));
+    pub fn with_config(repository: R, config: Config) -> Self {
+        Self {
+            repository,
+            validation_steps: validation::default_steps(&config),
+            recent_ops: RecentOps::new(config.recent_ops_capacity),
+            events: VideoEvents::new(),
+            config,
         }
+    }
 
-        let video = self.repository.create(req.title, req.youtube_id).await?;
-        Ok(VideoResponse {
-            id: video.id,
-            title: video.title,
-            youtube_id: video.youtube_id,
-            created_at: video.created_at,
-            updated_at: video.updated_at,
-            deleted_at: video.deleted_at,
-        })
+    /// Appends a step to the validation chain run before every create and update
+    /// 
+    /// # Arguments
+    /// * `step` - The validation step to run after the already registered ones
+    pub fn with_validation_step(mut self, step: impl ValidationStep + 'static) -> Self {
+        self.validation_steps.push(Arc::new(step));
+        self
+    }
+
+    /// Returns the most recent committed writes, oldest first
+    pub fn recent_ops(&self) -> Vec<RecentOp> {
+        self.recent_ops.snapshot()
+    }
+
+    /// Subscribes to the changes committed through this service from now on
+    pub fn subscribe_events(&self) -> tokio::sync::broadcast::Receiver<VideoEvent> {
+        self.events.subscribe()
+    }
+
+    /// Returns the configuration this service was built with
+    pub fn config(&self) -> &Config {
+        &self.config
     }
 
     /// Retrieves a video by ID
@@ -134,237 +252,2049 @@ impl VideoService {
         let video = self.repository.find_by_id(id).await?
             .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
             
+        let mut tags = self.repository.tags_for(&[video.id]).await?;
         Ok(VideoResponse {
-            id: video.id,
-            title: video.title,
-            youtube_id: video.youtube_id,
-            created_at: video.created_at,
-            updated_at: video.updated_at,
-            deleted_at: video.deleted_at,
+            tags: tags.remove(&video.id).unwrap_or_default(),
+            ..VideoResponse::from(video)
         })
     }
+}
 
-    /// Updates an existing video
+impl VideoService {
+    /// Runs the validation chain in order, stopping at the first rejection
+    async fn validate_write(&self, write: VideoWrite<'_>) -> AppResult<()> {
+        for step in &self.validation_steps {
+            step.validate(&write).await?;
+        }
+        Ok(())
+    }
+
+    /// Creates a new video
     /// 
     /// # Arguments
-    /// * `id` - The ID of the video to update
-    /// * `req` - The video update request containing new title and youtube_id
+    /// * `req` - The video creation request containing title and youtube_id
     /// 
     /// # Returns
-    /// * `AppResult<VideoResponse>` - The updated video on success
+    /// * `AppResult<VideoResponse>` - The created video on success
     /// 
     /// # Errors
-    /// * `AppError::NotFound` - If the video doesn't exist
-    /// * `AppError::Validation` - If the input data is invalid
-    /// * `AppError::Database` - If there's an error updating the database
+    /// * `AppError::Validation` - If the input data fails the validation chain
+    /// * `AppError::Database` - If there's an error saving to the database
     /// 
     /// # Example
     /// 
     /// ```no_run
-    /// use ntex_api::api::UpdateVideoRequest;
+    /// use ntex_api::api::CreateVideoRequest;
     /// use ntex_api::services::video_service::VideoService;
     /// use ntex_api::repositories::video_repository::VideoRepository;
     /// use sea_orm::DatabaseConnection;
     /// 
-    /// async fn update_video(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+    /// async fn create_video(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
     ///     let repo = VideoRepository::new(db);
     ///     let service = VideoService::new(repo);
     /// 
-    ///     let request = UpdateVideoRequest {
-    ///         title: Some("Updated Video Title".to_string()),
-    ///         youtube_id: Some("dQw4w9WgXcQ".to_string()),
+    ///     let request = CreateVideoRequest {
+    ///         title: "My Awesome Video".to_string(),
+    ///         youtube_id: "dQw4w9WgXcQ".to_string(),
+    ///         description: None,
+    ///         status: None,
+    ///         tags: vec!["music".to_string()],
     ///     };
     /// 
-    ///     let video = service.update_video(1, request).await?;
-    ///     assert_eq!(video.title, "Updated Video Title");
+    ///     let video = service.create_video(request).await?;
+    ///     assert_eq!(video.title, "My Awesome Video");
     ///     Ok(())
     /// }
     /// ```
-    pub async fn update_video(&self, id: i32, req: UpdateVideoRequest) -> AppResult<VideoResponse> {
-        if let Err(e) = req.validate() {
-            return Err(AppError::Validation(e.to_string()));
+    pub async fn create_video(&self, req: CreateVideoRequest) -> AppResult<VideoResponse> {
+        let txn = self.repository.begin().await?;
+        let video = self.create_video_in(&txn, req).await?;
+        txn.commit().await?;
+        self.recent_ops.record(OpKind::Create, video.id);
+        self.events.publish(EventKind::Created, &video);
+        Ok(video)
+    }
+
+    /// Runs `create_video` inside a transaction that is always rolled back
+    /// 
+    /// The returned video has the shape a real create would produce, but no
+    /// row is persisted.
+    /// 
+    /// # Errors
+    /// * `AppError::Validation` - If the input data fails the validation chain
+    /// * `AppError::Database` - If there's an error talking to the database
+    pub async fn create_video_dry_run(&self, req: CreateVideoRequest) -> AppResult<VideoResponse> {
+        let txn = self.repository.begin().await?;
+        let result = self.create_video_in(&txn, req).await;
+        txn.rollback().await?;
+        result
+    }
+
+    async fn create_video_in<C: ConnectionTrait>(&self, conn: &C, req: CreateVideoRequest) -> AppResult<VideoResponse> {
+        let req = with_extracted_youtube_id(req);
+        self.validate_write(VideoWrite::Create(&req)).await?;
+        self.ensure_youtube_id_free(conn, &req.youtube_id, None).await?;
+
+        let video = self.repository.create_in(conn, new_video(req)).await?;
+        self.with_tag(conn, video).await
+    }
+
+    /// Announces committed changes to videos known only by id
+    /// 
+    /// The videos are loaded only when someone is subscribed. A failed load
+    /// is logged rather than returned, since the write already committed.
+    async fn publish_ids(&self, kind: EventKind, ids: &[i32]) {
+        if !self.events.has_subscribers() {
+            return;
         }
 
-        let video = self.repository.update(id, req.title, req.youtube_id).await?
-            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
-            
-        Ok(VideoResponse {
-            id: video.id,
-            title: video.title,
-            youtube_id: video.youtube_id,
-            created_at: video.created_at,
-            updated_at: video.updated_at,
-            deleted_at: video.deleted_at,
-        })
+        for id in ids {
+            let video = match self.repository.find_by_id_including_deleted(*id).await {
+                Ok(Some(video)) => self.with_tag(self.repository.connection(), video).await,
+                Ok(None) => continue,
+                Err(e) => Err(e),
+            };
+            match video {
+                Ok(video) => self.events.publish(kind, &video),
+                Err(e) => tracing::warn!("Could not load video {} for its {:?} event: {}", id, kind, e),
+            }
+        }
     }
 
-    /// Deletes a video
+    /// Converts a video into a response carrying its tag names
+    async fn with_tag<C: ConnectionTrait>(&self, conn: &C, video: video::Model) -> AppResult<VideoResponse> {
+        let mut videos = self.with_tags(conn, vec![video]).await?;
+        Ok(videos.remove(0))
+    }
+
+    /// Converts videos into responses carrying their tag names, in one query
+    async fn with_tags<C: ConnectionTrait>(&self, conn: &C, videos: Vec<video::Model>) -> AppResult<Vec<VideoResponse>> {
+        let ids: Vec<i32> = videos.iter().map(|video| video.id).collect();
+        let mut tags = self.repository.tags_for_in(conn, &ids).await?;
+
+        Ok(videos
+            .into_iter()
+            .map(|video| VideoResponse {
+                tags: tags.remove(&video.id).unwrap_or_default(),
+                ..VideoResponse::from(video)
+            })
+            .collect())
+    }
+
+    /// Creates several videos atomically
+    /// 
+    /// Full YouTube URLs are reduced to their ID, as for a single create.
+    /// Every item is validated before anything is written; the first
+    /// rejection is reported with its index in `reqs`. The inserts then run
+    /// in a single transaction, so a failure part-way rolls back the batch.
     /// 
     /// # Arguments
-    /// * `id` - The ID of the video to delete
+    /// * `reqs` - The videos to create, at most `MAX_BATCH_SIZE`
     /// 
     /// # Returns
-    /// * `AppResult<bool>` - Success indicator
+    /// * `AppResult<Vec<VideoResponse>>` - The created videos, in request order
     /// 
     /// # Errors
-    /// * `AppError::NotFound` - If the video doesn't exist
-    /// * `AppError::Database` - If there's an error deleting from the database
+    /// * `AppError::Validation` - If the batch is empty, too large, or an item is invalid
+    /// * `AppError::Database` - If there's an error saving to the database
+    pub async fn create_videos_batch(&self, reqs: Vec<CreateVideoRequest>) -> AppResult<Vec<VideoResponse>> {
+        let items = self.validate_batch(reqs).await?;
+        let videos = self.repository.create_many(items).await?;
+        for video in &videos {
+            self.recent_ops.record(OpKind::Create, video.id);
+        }
+
+        let videos = self.with_tags(self.repository.connection(), videos).await?;
+        for video in &videos {
+            self.events.publish(EventKind::Created, video);
+        }
+        Ok(videos)
+    }
+
+    /// Runs `create_videos_batch` inside a transaction that is always rolled back
     /// 
-    /// # Example
+    /// # Errors
+    /// * `AppError::Validation` - If the batch is empty, too large, or an item is invalid
+    /// * `AppError::Database` - If there's an error talking to the database
+    pub async fn create_videos_batch_dry_run(&self, reqs: Vec<CreateVideoRequest>) -> AppResult<Vec<VideoResponse>> {
+        let items = self.validate_batch(reqs).await?;
+        let txn = self.repository.begin().await?;
+        let result = match self.repository.create_many_in(&txn, items).await {
+            Ok(videos) => self.with_tags(&txn, videos).await,
+            Err(e) => Err(e),
+        };
+        txn.rollback().await?;
+        result
+    }
+
+    /// Imports videos, updating the title of those whose YouTube ID exists
     /// 
-    /// ```no_run
-    /// use ntex_api::services::video_service::VideoService;
-    /// use ntex_api::repositories::video_repository::VideoRepository;
-    /// use sea_orm::DatabaseConnection;
+    /// Items are validated like `create_videos_batch`, and a YouTube ID may
+    /// appear only once per import. Full YouTube URLs are reduced to their ID
+    /// first, as for a single create. Every insert and update runs in one
+    /// transaction, so a failure part-way rolls back the whole import.
     /// 
-    /// async fn delete_video(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
-    ///     let repo = VideoRepository::new(db);
-    ///     let service = VideoService::new(repo);
+    /// # Arguments
+    /// * `reqs` - The videos to import, at most `MAX_BATCH_SIZE`
     /// 
-    ///     service.delete_video(1).await?;
+    /// # Returns
+    /// * `AppResult<ImportResponse>` - How many videos were inserted and updated
     /// 
-    ///     // Verify deletion
-    ///     let result = service.get_video(1).await;
-    ///     assert!(result.is_err());
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn delete_video(&self, id: i32) -> AppResult<bool> {
-        let deleted = self.repository.delete(id).await?;
-        if !deleted {
-            return Err(AppError::NotFound(format!("Video with id {} not found", id)));
+    /// # Errors
+    /// * `AppError::Validation` - If the import is empty, too large, or an item is invalid
+    /// * `AppError::Conflict` - If a YouTube ID appears twice in the import
+    /// * `AppError::Database` - If there's an error saving to the database
+    pub async fn import_videos(&self, reqs: Vec<CreateVideoRequest>) -> AppResult<ImportResponse> {
+        let reqs: Vec<_> = reqs.into_iter().map(with_extracted_youtube_id).collect();
+        self.validate_batch_items(&reqs).await?;
+
+        let txn = self.repository.begin().await?;
+        let mut ops = Vec::with_capacity(reqs.len());
+        for req in reqs {
+            let (video, inserted) = self.repository.upsert_by_youtube_id_in(&txn, new_video(req)).await?;
+            ops.push((if inserted { OpKind::Create } else { OpKind::Update }, video.id));
         }
-        Ok(true)
+        txn.commit().await?;
+
+        let inserted = ops.iter().filter(|(op, _)| *op == OpKind::Create).count();
+        for (op, id) in ops.iter() {
+            self.recent_ops.record(*op, *id);
+        }
+        for (op, kind) in [(OpKind::Create, EventKind::Created), (OpKind::Update, EventKind::Updated)] {
+            let ids: Vec<i32> = ops.iter().filter(|(o, _)| *o == op).map(|(_, id)| *id).collect();
+            self.publish_ids(kind, &ids).await;
+        }
+        Ok(ImportResponse {
+            inserted,
+            updated: ops.len() - inserted,
+        })
     }
 
-    /// Lists videos based on query parameters
+    async fn validate_batch(&self, reqs: Vec<CreateVideoRequest>) -> AppResult<Vec<NewVideo>> {
+        let reqs: Vec<_> = reqs.into_iter().map(with_extracted_youtube_id).collect();
+        self.validate_batch_items(&reqs).await?;
+
+        for (index, req) in reqs.iter().enumerate() {
+            if self.repository.find_by_youtube_id(&req.youtube_id).await?.is_some() {
+                return Err(AppError::Conflict(format!("item {}: youtube_id already exists", index)));
+            }
+        }
+
+        Ok(reqs.into_iter().map(new_video).collect())
+    }
+
+    /// Validates each item of a batch and rejects YouTube IDs repeated within it
+    async fn validate_batch_items(&self, reqs: &[CreateVideoRequest]) -> AppResult<()> {
+        if reqs.is_empty() || reqs.len() > MAX_BATCH_SIZE {
+            return Err(AppError::Validation(format!(
+                "a batch must contain between 1 and {} videos",
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        for (index, req) in reqs.iter().enumerate() {
+            self.validate_write(VideoWrite::Create(req)).await.map_err(|e| match e {
+                AppError::Validation(message) => {
+                    AppError::Validation(format!("item {}: {}", index, message))
+                }
+                AppError::ValidationDetailed { message, fields } => AppError::ValidationDetailed {
+                    message: format!("item {}: {}", index, message),
+                    fields,
+                },
+                AppError::InvalidValue { message, fields, received } => AppError::InvalidValue {
+                    message: format!("item {}: {}", index, message),
+                    fields,
+                    received,
+                },
+                other => other,
+            })?;
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for (index, req) in reqs.iter().enumerate() {
+            if !seen.insert(req.youtube_id.as_str()) {
+                return Err(AppError::Conflict(format!("item {}: youtube_id already exists", index)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a YouTube ID already used by a live video other than `except_id`
+    /// 
+    /// The database's unique index remains the backstop for concurrent
+    /// writes; the repository maps its violation to the same error.
+    async fn ensure_youtube_id_free<C: ConnectionTrait>(&self, conn: &C, youtube_id: &str, except_id: Option<i32>) -> AppResult<()> {
+        match self.repository.find_by_youtube_id_in(conn, youtube_id).await? {
+            Some(existing) if Some(existing.id) != except_id => {
+                Err(AppError::Conflict("youtube_id already exists".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Retrieves a video by its YouTube ID
     /// 
     /// # Arguments
-    /// * `query` - Query parameters for filtering and pagination
+    /// * `youtube_id` - The 11 character YouTube ID of the video
+    /// 
+    /// # Errors
+    /// * `AppError::Validation` - If `youtube_id` isn't exactly 11 characters
+    /// * `AppError::NotFound` - If no live video has this YouTube ID
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn get_video_by_youtube_id(&self, youtube_id: &str) -> AppResult<VideoResponse> {
+        if youtube_id.chars().count() != 11 {
+            return Err(AppError::Validation("youtube_id must be exactly 11 characters".to_string()));
+        }
+
+        let video = self.repository.find_by_youtube_id(youtube_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with youtube_id {} not found", youtube_id)))?;
+
+        self.with_tag(self.repository.connection(), video).await
+    }
+
+    /// Summarizes the stored videos with one small query per figure
+    /// 
+    /// "Today" starts at midnight UTC.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn stats(&self) -> AppResult<VideoStatsResponse> {
+        let today = Period::Today.start(Utc::now());
+
+        Ok(VideoStatsResponse {
+            total: self.repository.count_live(None).await?,
+            deleted: self.repository.count_deleted().await?,
+            created_today: self.repository.count_live(Some(today)).await?,
+            newest_id: self.repository.newest_id().await?,
+            oldest_created_at: self.repository.oldest_created_at().await?,
+        })
+    }
+
+    /// Lists groups of videos sharing a YouTube ID, soft-deleted ones included
+    /// 
+    /// The unique index on `youtube_id` normally prevents these; groups show
+    /// up when it was dropped or rows were restored around it.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn find_duplicates(&self) -> AppResult<DuplicatesResponse> {
+        let groups = self.repository.find_duplicate_youtube_ids().await?
+            .into_iter()
+            .map(|(youtube_id, ids)| DuplicateGroup { youtube_id, ids })
+            .collect();
+
+        Ok(DuplicatesResponse { groups })
+    }
+
+    /// Retrieves one non-deleted video chosen at random
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If there are no live videos
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn get_random_video(&self) -> AppResult<VideoResponse> {
+        let video = self.repository.find_random().await?
+            .ok_or_else(|| AppError::NotFound("No videos found".to_string()))?;
+
+        self.with_tag(self.repository.connection(), video).await
+    }
+
+    /// Retrieves a video by its ID, including soft-deleted ones
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If no video has this ID
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn get_video_including_deleted(&self, id: i32) -> AppResult<VideoResponse> {
+        let video = self.repository.find_by_id_including_deleted(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+
+        self.with_tag(self.repository.connection(), video).await
+    }
+
+    /// Retrieves several videos by ID, reporting which IDs did not resolve
+    /// 
+    /// # Arguments
+    /// * `req` - The fetch request containing the IDs to look up
     /// 
     /// # Returns
-    /// * `AppResult<PaginatedVideoResponse>` - The paginated list of videos on success
+    /// * `AppResult<FetchVideosResponse>` - Found videos in request order, plus
+    ///   the IDs that are missing or soft-deleted
     /// 
     /// # Errors
+    /// * `AppError::Validation` - If no IDs or more than 100 IDs are requested
     /// * `AppError::Database` - If there's an error accessing the database
     /// 
     /// # Example
     /// 
     /// ```no_run
-    /// use ntex_api::db::VideoQuery;
+    /// use ntex_api::api::FetchVideosRequest;
     /// use ntex_api::services::video_service::VideoService;
     /// use ntex_api::repositories::video_repository::VideoRepository;
     /// use sea_orm::DatabaseConnection;
     /// 
-    /// async fn list_videos(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+    /// async fn fetch_videos(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
     ///     let repo = VideoRepository::new(db);
     ///     let service = VideoService::new(repo);
     /// 
-    ///     let query = VideoQuery {
-    ///         page: Some(1),
-    ///         per_page: Some(10),
-    ///         search: Some("awesome".to_string()),
-    ///         order_by: Some("created_at".to_string()),
-    ///         order_direction: Some("desc".to_string()),
-    ///     };
-    /// 
-    ///     let videos = service.list_videos(query).await?;
-    ///     assert_eq!(videos.page, 1);
+    ///     let result = service.fetch_videos(FetchVideosRequest { ids: vec![1, 2, 3] }).await?;
+    ///     println!("missing: {:?}", result.missing);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn list_videos(&self, query: VideoQuery) -> AppResult<PaginatedVideoResponse> {
-        let (videos, total) = self.repository.list(&query).await?;
-        let page = query.page.unwrap_or(1);
-        let per_page = query.per_page.unwrap_or(10);
-        let total_pages = (total as f64 / per_page as f64).ceil() as u64;
+    pub async fn fetch_videos(&self, req: FetchVideosRequest) -> AppResult<FetchVideosResponse> {
+        req.validate()?;
 
-        let videos = videos.into_iter()
-            .map(|v| VideoResponse {
-                id: v.id,
-                title: v.title,
-                youtube_id: v.youtube_id,
-                created_at: v.created_at,
-                updated_at: v.updated_at,
-                deleted_at: v.deleted_at,
-            })
+        let mut ids = req.ids;
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+
+        let mut videos: std::collections::HashMap<i32, _> = self.repository.find_by_ids(&ids).await?
+            .into_iter()
+            .map(|video| (video.id, video))
             .collect();
 
-        Ok(PaginatedVideoResponse {
-            videos,
-            total,
-            page,
-            per_page,
-            total_pages,
-        })
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+        for id in ids {
+            match videos.remove(&id) {
+                Some(video) => found.push(video),
+                None => missing.push(id),
+            }
+        }
+        let found = self.with_tags(self.repository.connection(), found).await?;
+
+        Ok(FetchVideosResponse { found, missing })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use sea_orm::{Database, Schema, DatabaseConnection, DatabaseBackend, ConnectionTrait};
-    use crate::entity::video;
+    /// Validates a CSV import without writing anything
+    /// 
+    /// The file must start with a `title,youtube_id` header. Every row is
+    /// parsed and run through the validation chain, and a `youtube_id`
+    /// repeated within the file is reported on each later occurrence.
+    /// Line numbers count the header as line 1.
+    /// 
+    /// # Errors
+    /// * `AppError::BadRequest` - If the header row can't be read
+    pub async fn validate_import(&self, csv: &[u8]) -> AppResult<ImportValidationResponse> {
+        let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(csv);
+        let headers = reader.headers()
+            .map_err(|e| AppError::BadRequest(format!("invalid CSV header: {}", e)))?
+            .clone();
 
-    async fn setup_database(db: &DatabaseConnection) {
-        let schema = Schema::new(DatabaseBackend::Sqlite);
-        let stmt = schema.create_table_from_entity(video::Entity);
-        db.execute(db.get_database_backend().build(&stmt)).await.unwrap();
-    }
+        let mut valid_rows = 0;
+        let mut errors = Vec::new();
+        let mut first_seen: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
 
-    #[ntex::test]
-    async fn test_create_video() {
-        let db = Database::connect("sqlite::memory:").await.unwrap();
-        setup_database(&db).await;
-        let repo = VideoRepository::new(db);
-        let service = VideoService::new(repo);
+        for record in reader.records() {
+            let (line, row) = match record {
+                Ok(record) => {
+                    let line = record.position().map_or(0, |p| p.line());
+                    (line, record.deserialize::<CreateVideoRequest>(Some(&headers)).map_err(|e| e.to_string()))
+                }
+                Err(e) => (e.position().map_or(0, |p| p.line()), Err(e.to_string())),
+            };
 
-        let request = CreateVideoRequest {
-            title: "Test Video".to_string(),
-            youtube_id: "dQw4w9WgXcQ".to_string(), // 11 characters
-        };
+            let result = match row {
+                Ok(row) => match first_seen.get(&row.youtube_id) {
+                    Some(first) => Err(format!(
+                        "duplicate youtube_id {} (first seen on line {})",
+                        row.youtube_id, first
+                    )),
+                    None => {
+                        first_seen.insert(row.youtube_id.clone(), line);
+                        self.validate_write(VideoWrite::Create(&row)).await
+                            .map_err(|e| e.to_string())
+                    }
+                },
+                Err(error) => Err(error),
+            };
 
-        let result = service.create_video(request).await;
+            match result {
+                Ok(()) => valid_rows += 1,
+                Err(error) => errors.push(ImportRowError { line, error }),
+            }
+        }
+
+        Ok(ImportValidationResponse { valid_rows, errors })
+    }
+
+    /// Lists other videos related to a video by shared title words
+    /// 
+    /// Candidates are ranked by how many distinct title words they share with
+    /// the source, newest first among equals. Videos sharing no word are not
+    /// returned, so a video with no overlap gets an empty list.
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the source video
+    /// * `query` - Optional `limit`, defaulting to `DEFAULT_RELATED_LIMIT` and capped at `MAX_RELATED_LIMIT`
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If the source video doesn't exist
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn related_videos(&self, id: i32, query: RelatedQuery) -> AppResult<RelatedVideosResponse> {
+        let limit = query.limit.unwrap_or(DEFAULT_RELATED_LIMIT).clamp(1, MAX_RELATED_LIMIT);
+        let source = self.repository.find_by_id(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+
+        let words = relatedness_words(&source.title);
+        let candidates = self.repository
+            .find_sharing_title_words(id, &words.iter().cloned().collect::<Vec<_>>())
+            .await?;
+
+        let mut ranked: Vec<_> = candidates
+            .into_iter()
+            .map(|video| (relatedness_words(&video.title).intersection(&words).count(), video))
+            .filter(|(shared, _)| *shared > 0)
+            .collect();
+        ranked.sort_by(|(a_shared, a), (b_shared, b)| {
+            b_shared.cmp(a_shared)
+                .then(b.created_at.cmp(&a.created_at))
+                .then(b.id.cmp(&a.id))
+        });
+
+        let videos = ranked
+            .into_iter()
+            .take(limit as usize)
+            .map(|(_, video)| video)
+            .collect();
+        let videos = self.with_tags(self.repository.connection(), videos).await?;
+
+        Ok(RelatedVideosResponse { videos })
+    }
+
+    /// Updates an existing video
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to update
+    /// * `req` - The video update request containing new title and youtube_id
+    /// * `expected_updated_at` - When set, the update only applies if the
+    ///   video still carries this `updated_at`
+    /// 
+    /// # Returns
+    /// * `AppResult<VideoResponse>` - The updated video on success
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If the video doesn't exist
+    /// * `AppError::PreconditionFailed` - If the video changed since `expected_updated_at`
+    /// * `AppError::Validation` - If the input data is invalid
+    /// * `AppError::BadRequest` - If no field is given, or `youtube_id` is given but `allow_youtube_id_update` is off
+    /// * `AppError::Database` - If there's an error updating the database
+    /// 
+    /// # Example
+    /// 
+    /// ```no_run
+    /// use ntex_api::api::UpdateVideoRequest;
+    /// use ntex_api::services::video_service::VideoService;
+    /// use ntex_api::repositories::video_repository::VideoRepository;
+    /// use sea_orm::DatabaseConnection;
+    /// 
+    /// async fn update_video(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let repo = VideoRepository::new(db);
+    ///     let service = VideoService::new(repo);
+    /// 
+    ///     let request = UpdateVideoRequest {
+    ///         title: Some("Updated Video Title".to_string()),
+    ///         youtube_id: Some("dQw4w9WgXcQ".to_string()),
+    ///         description: None,
+    ///         status: None,
+    ///     };
+    /// 
+    ///     let video = service.update_video(1, request, None).await?;
+    ///     assert_eq!(video.title, "Updated Video Title");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn update_video(&self, id: i32, req: UpdateVideoRequest, expected_updated_at: Option<DateTime<Utc>>) -> AppResult<VideoResponse> {
+        let video = self.update_video_in(self.repository.connection(), id, req, expected_updated_at).await?;
+        self.recent_ops.record(OpKind::Update, id);
+        self.events.publish(EventKind::Updated, &video);
+        Ok(video)
+    }
+
+    /// Runs `update_video` inside a transaction that is always rolled back
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If the video doesn't exist
+    /// * `AppError::PreconditionFailed` - If the video changed since `expected_updated_at`
+    /// * `AppError::Validation` - If the input data fails the validation chain
+    /// * `AppError::Database` - If there's an error talking to the database
+    pub async fn update_video_dry_run(&self, id: i32, req: UpdateVideoRequest, expected_updated_at: Option<DateTime<Utc>>) -> AppResult<VideoResponse> {
+        let txn = self.repository.begin().await?;
+        let result = self.update_video_in(&txn, id, req, expected_updated_at).await;
+        txn.rollback().await?;
+        result
+    }
+
+    async fn update_video_in<C: ConnectionTrait>(&self, conn: &C, id: i32, mut req: UpdateVideoRequest, expected_updated_at: Option<DateTime<Utc>>) -> AppResult<VideoResponse> {
+        if req.title.is_none() && req.youtube_id.is_none() && req.description.is_none() && req.status.is_none() {
+            return Err(AppError::BadRequest("no fields to update".to_string()));
+        }
+        if let Some(youtube_id) = req.youtube_id.as_deref().and_then(extract_youtube_id) {
+            req.youtube_id = Some(youtube_id);
+        }
+        if let Some(youtube_id) = &req.youtube_id {
+            if !self.config.allow_youtube_id_update {
+                let current = self.repository.find_by_id_in(conn, id).await?;
+                if current.is_some_and(|video| &video.youtube_id != youtube_id) {
+                    return Err(AppError::BadRequest("youtube_id is immutable".to_string()));
+                }
+            }
+        }
+
+        self.validate_write(VideoWrite::Update { id, req: &req }).await?;
+        if let Some(youtube_id) = &req.youtube_id {
+            self.ensure_youtube_id_free(conn, youtube_id, Some(id)).await?;
+        }
+
+        let video = self.repository.update_in(conn, id, VideoChanges {
+            title: req.title,
+            youtube_id: req.youtube_id,
+            description: req.description,
+            status: req.status,
+        }, expected_updated_at).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+            
+        self.with_tag(conn, video).await
+    }
+
+    /// Deletes a video
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to delete
+    /// 
+    /// # Returns
+    /// * `AppResult<VideoResponse>` - The soft-deleted video, `deleted_at` included
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If the video doesn't exist
+    /// * `AppError::Database` - If there's an error deleting from the database
+    /// 
+    /// # Example
+    /// 
+    /// ```no_run
+    /// use ntex_api::services::video_service::VideoService;
+    /// use ntex_api::repositories::video_repository::VideoRepository;
+    /// use sea_orm::DatabaseConnection;
+    /// 
+    /// async fn delete_video(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let repo = VideoRepository::new(db);
+    ///     let service = VideoService::new(repo);
+    /// 
+    ///     service.delete_video(1).await?;
+    /// 
+    ///     // Verify deletion
+    ///     let result = service.get_video(1).await;
+    ///     assert!(result.is_err());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_video(&self, id: i32) -> AppResult<VideoResponse> {
+        let deleted = self.delete_video_in(self.repository.connection(), id).await?;
+        self.recent_ops.record(OpKind::Delete, id);
+        self.events.publish(EventKind::Deleted, &deleted);
+        Ok(deleted)
+    }
+
+    /// Runs `delete_video` inside a transaction that is always rolled back
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If the video doesn't exist
+    /// * `AppError::Database` - If there's an error talking to the database
+    pub async fn delete_video_dry_run(&self, id: i32) -> AppResult<VideoResponse> {
+        let txn = self.repository.begin().await?;
+        let result = self.delete_video_in(&txn, id).await;
+        txn.rollback().await?;
+        result
+    }
+
+    async fn delete_video_in<C: ConnectionTrait>(&self, conn: &C, id: i32) -> AppResult<VideoResponse> {
+        let video = self.repository.delete_in(conn, id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        self.with_tag(conn, video).await
+    }
+
+    /// Soft-deletes several videos in one transaction
+    /// 
+    /// IDs that don't exist or are already deleted are reported in
+    /// `not_found` and don't abort the batch; only a database error rolls
+    /// the transaction back. Duplicate IDs are reported once.
+    /// 
+    /// # Arguments
+    /// * `ids` - The IDs of the videos to delete, at most `MAX_BATCH_SIZE`
+    /// 
+    /// # Errors
+    /// * `AppError::Validation` - If the batch is empty or too large
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn delete_videos_batch(&self, ids: Vec<i32>) -> AppResult<BatchDeleteResponse> {
+        let txn = self.repository.begin().await?;
+        let result = self.delete_videos_batch_in(&txn, ids).await?;
+        txn.commit().await?;
+
+        for id in &result.deleted {
+            self.recent_ops.record(OpKind::Delete, *id);
+        }
+        self.publish_ids(EventKind::Deleted, &result.deleted).await;
+        Ok(result)
+    }
+
+    /// Runs `delete_videos_batch` inside a transaction that is always rolled back
+    /// 
+    /// # Errors
+    /// * `AppError::Validation` - If the batch is empty or too large
+    /// * `AppError::Database` - If there's an error talking to the database
+    pub async fn delete_videos_batch_dry_run(&self, ids: Vec<i32>) -> AppResult<BatchDeleteResponse> {
+        let txn = self.repository.begin().await?;
+        let result = self.delete_videos_batch_in(&txn, ids).await;
+        txn.rollback().await?;
+        result
+    }
+
+    async fn delete_videos_batch_in<C: ConnectionTrait>(&self, conn: &C, mut ids: Vec<i32>) -> AppResult<BatchDeleteResponse> {
+        if ids.is_empty() || ids.len() > MAX_BATCH_SIZE {
+            return Err(AppError::Validation(format!(
+                "a batch must contain between 1 and {} ids",
+                MAX_BATCH_SIZE
+            )));
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        ids.retain(|id| seen.insert(*id));
+
+        let deleted: std::collections::HashSet<i32> = self.repository
+            .soft_delete_many_in(conn, &ids)
+            .await?
+            .into_iter()
+            .collect();
+        let (deleted, not_found) = ids.into_iter().partition(|id| deleted.contains(id));
+
+        Ok(BatchDeleteResponse { deleted, not_found })
+    }
+
+    /// Permanently removes a video, including one that was already soft-deleted
+    /// 
+    /// Subscribers are sent a `deleted` event carrying the row as it was
+    /// just before removal.
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to remove
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If no row has this ID
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn hard_delete_video(&self, id: i32) -> AppResult<bool> {
+        let txn = self.repository.begin().await?;
+        let deleted = self.hard_delete_video_in(&txn, id).await?;
+        txn.commit().await?;
+        self.recent_ops.record(OpKind::Delete, id);
+        self.events.publish(EventKind::Deleted, &deleted);
+        Ok(true)
+    }
+
+    /// Runs `hard_delete_video` inside a transaction that is always rolled back
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If no row has this ID
+    /// * `AppError::Database` - If there's an error talking to the database
+    pub async fn hard_delete_video_dry_run(&self, id: i32) -> AppResult<bool> {
+        let txn = self.repository.begin().await?;
+        let result = self.hard_delete_video_in(&txn, id).await;
+        txn.rollback().await?;
+        result.map(|_| true)
+    }
+
+    /// Removes the row, returning it with its tags as they were before the delete
+    async fn hard_delete_video_in<C: ConnectionTrait>(&self, conn: &C, id: i32) -> AppResult<VideoResponse> {
+        let tags = self.repository.tags_for_in(conn, &[id]).await?;
+        let video = self.repository.hard_delete_in(conn, id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        let mut deleted = VideoResponse::from(video);
+        deleted.tags = tags.into_values().next().unwrap_or_default();
+        Ok(deleted)
+    }
+
+    /// Removes all stored data, for resetting test and development environments
+    /// 
+    /// # Errors
+    /// * `AppError::Forbidden` - If `allow_data_reset` is off or running in production
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn reset_data(&self) -> AppResult<ResetResponse> {
+        if self.config.is_production() {
+            return Err(AppError::Forbidden("data reset is not available in production".to_string()));
+        }
+        if !self.config.allow_data_reset {
+            return Err(AppError::Forbidden("data reset is disabled".to_string()));
+        }
+
+        let removed = self.repository.delete_all().await?;
+        tracing::warn!("Data reset removed {} videos", removed);
+        Ok(ResetResponse { removed })
+    }
+
+    /// Restores a soft-deleted video
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the video to restore
+    /// 
+    /// # Returns
+    /// * `AppResult<VideoResponse>` - The video with `deleted_at` cleared
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If no video has this ID
+    /// * `AppError::Conflict` - If the video isn't deleted
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn restore_video(&self, id: i32) -> AppResult<VideoResponse> {
+        let existing = self.repository.find_by_id_including_deleted(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        if existing.deleted_at.is_none() {
+            return Err(AppError::Conflict(format!("Video with id {} is not deleted", id)));
+        }
+
+        let video = self.repository.restore(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        self.recent_ops.record(OpKind::Restore, id);
+
+        let video = self.with_tag(self.repository.connection(), video).await?;
+        self.events.publish(EventKind::Updated, &video);
+        Ok(video)
+    }
+
+    /// Moves a live video to the `published` state
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the video to publish
+    ///
+    /// # Returns
+    /// * `AppResult<VideoResponse>` - The video with `status` set to `published`
+    ///
+    /// # Errors
+    /// * `AppError::NotFound` - If no live video has this ID
+    /// * `AppError::Conflict` - If the video is already published
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn publish_video(&self, id: i32) -> AppResult<VideoResponse> {
+        let existing = self.repository.find_by_id(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        if existing.status == video::VideoStatus::Published {
+            return Err(AppError::Conflict(format!("Video with id {} is already published", id)));
+        }
+
+        let video = self.repository.update(id, VideoChanges {
+            status: Some(video::VideoStatus::Published),
+            ..Default::default()
+        }, None).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        self.recent_ops.record(OpKind::Update, id);
+
+        let video = self.with_tag(self.repository.connection(), video).await?;
+        self.events.publish(EventKind::Updated, &video);
+        Ok(video)
+    }
+
+    /// Moves a live video within the manual ordering
+    ///
+    /// # Arguments
+    /// * `id` - The ID of the video to move
+    /// * `req` - The 1-based position to move it to
+    ///
+    /// # Returns
+    /// * `AppResult<VideoResponse>` - The moved video with its new `position`
+    ///
+    /// # Errors
+    /// * `AppError::NotFound` - If no live video has this ID
+    /// * `AppError::Validation` - If `position` is less than 1
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn move_video(&self, id: i32, req: MoveVideoRequest) -> AppResult<VideoResponse> {
+        req.validate()?;
+
+        let video = self.repository.reorder(id, req.position).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+        self.recent_ops.record(OpKind::Update, id);
+
+        let video = self.with_tag(self.repository.connection(), video).await?;
+        self.events.publish(EventKind::Updated, &video);
+        Ok(video)
+    }
+
+    /// Exchanges the manual positions of two live videos in one transaction
+    ///
+    /// # Arguments
+    /// * `a` - The ID of one video
+    /// * `b` - The ID of the other
+    ///
+    /// # Returns
+    /// * `AppResult<SwapVideosResponse>` - Both videos, `a` first, with their new positions
+    ///
+    /// # Errors
+    /// * `AppError::NotFound` - If either ID has no live video; nothing is changed
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn swap_positions(&self, a: i32, b: i32) -> AppResult<SwapVideosResponse> {
+        let (first, second) = self.repository.swap_positions(a, b).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} or {} not found", a, b)))?;
+        self.recent_ops.record(OpKind::Update, a);
+        self.recent_ops.record(OpKind::Update, b);
+
+        let videos = self.with_tags(self.repository.connection(), vec![first, second]).await?;
+        for video in &videos {
+            self.events.publish(EventKind::Updated, video);
+        }
+        Ok(SwapVideosResponse { videos })
+    }
+
+    /// Records one view of a live video
+    /// 
+    /// # Arguments
+    /// * `id` - The ID of the viewed video
+    /// 
+    /// # Returns
+    /// * `AppResult<ViewCountResponse>` - The video's view count after this view
+    /// 
+    /// # Errors
+    /// * `AppError::NotFound` - If no live video has this ID
+    /// * `AppError::Database` - If there's an error updating the database
+    pub async fn record_view(&self, id: i32) -> AppResult<ViewCountResponse> {
+        let view_count = self.repository.increment_view_count(id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Video with id {} not found", id)))?;
+
+        Ok(ViewCountResponse { id, view_count })
+    }
+
+    /// Fetches one page of the full export: live videos in `Config::default_sort` order
+    /// 
+    /// The ordering is resolved the same way as for `list_videos`, so rows
+    /// come in the order the list endpoint uses when `order_by` is omitted.
+    /// Pages hold `EXPORT_PAGE_SIZE` rows; a shorter page is the last one.
+    /// Tags are not loaded.
+    /// 
+    /// # Arguments
+    /// * `page` - 1-based page number
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn export_page(&self, page: u64) -> AppResult<Vec<VideoResponse>> {
+        let query = VideoQuery {
+            page: Some(page),
+            per_page: Some(EXPORT_PAGE_SIZE),
+            order_by: None,
+            order_direction: None,
+            ..Default::default()
+        };
+        let sorts = self.resolve_sorts(&query)?;
+        let (videos, _) = self.repository.list(&query, &sorts).await?;
+        Ok(videos.into_iter().map(VideoResponse::from).collect())
+    }
+
+    /// Lists videos based on query parameters
+    /// 
+    /// `per_page` defaults to and is clamped by the bounds in `Config`;
+    /// `per_page=0` and `page=0` are rejected. A
+    /// `period` is resolved into a `created_after` bound at request time;
+    /// `created_after`/`created_before` bound an inclusive window and combine
+    /// with `search`.
+    /// Omitted `order_by`/`order_direction` fall back to `Config::default_sort`.
+    /// `order_by` may list several keys, as in `title,-created_at`, where a
+    /// leading `-` sorts that key descending.
+    /// With `include_deleted_total` one extra count fills in
+    /// `total_including_deleted`.
+    /// 
+    /// Passing `after` or `before` switches to keyset pagination: the page is
+    /// taken relative to that cursor and the response carries
+    /// `next_cursor`/`prev_cursor`. An empty `after` starts at the first page.
+    /// 
+    /// # Arguments
+    /// * `query` - Query parameters for filtering and pagination
+    /// 
+    /// # Returns
+    /// * `AppResult<PaginatedVideoResponse>` - The paginated list of videos on success
+    /// 
+    /// # Errors
+    /// * `AppError::Validation` - If `period` and `created_after` are both given, `page` is mixed with a cursor,
+    ///   or the ordering is unknown
+    /// * `AppError::BadRequest` - If a cursor is malformed
+    /// * `AppError::Database` - If there's an error accessing the database
+    /// 
+    /// # Example
+    /// 
+    /// ```no_run
+    /// use ntex_api::db::VideoQuery;
+    /// use ntex_api::services::video_service::VideoService;
+    /// use ntex_api::repositories::video_repository::VideoRepository;
+    /// use sea_orm::DatabaseConnection;
+    /// 
+    /// async fn list_videos(db: DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let repo = VideoRepository::new(db);
+    ///     let service = VideoService::new(repo);
+    /// 
+    ///     let query = VideoQuery {
+    ///         page: Some(1),
+    ///         per_page: Some(10),
+    ///         search: Some("awesome".to_string()),
+    ///         order_by: Some("created_at".to_string()),
+    ///         order_direction: Some("desc".to_string()),
+    ///         ..Default::default()
+    ///     };
+    /// 
+    ///     let videos = service.list_videos(query).await?;
+    ///     assert_eq!(videos.page, 1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_videos(&self, mut query: VideoQuery) -> AppResult<PaginatedVideoResponse> {
+        let (per_page, sorts) = self.resolve_list_query(&mut query)?;
+
+        let keyset = query.after.is_some() || query.before.is_some();
+        if keyset && (sorts.len() != 1 || sorts[0].key != SortKey::CreatedAt) {
+            return Err(AppError::Validation(
+                "after and before require ordering by created_at".to_string(),
+            ));
+        }
+        if keyset && query.page.is_some() {
+            return Err(AppError::Validation(
+                "page cannot be combined with after or before".to_string(),
+            ));
+        }
+        if query.after.is_some() && query.before.is_some() {
+            return Err(AppError::Validation(
+                "after cannot be combined with before".to_string(),
+            ));
+        }
+
+        let (videos, total, next_cursor, prev_cursor) = if keyset {
+            self.list_keyset(&query, sorts[0].direction, per_page).await?
+        } else {
+            let (videos, total) = self.repository.list(&query, &sorts).await?;
+            (videos, total, None, None)
+        };
+        let total_including_deleted = if query.include_deleted_total.unwrap_or(false) {
+            Some(self.repository.count_including_deleted(&query).await?)
+        } else {
+            None
+        };
+        let page = query.page.unwrap_or(1);
+        let total_pages = (total as f64 / per_page as f64).ceil() as u64;
+
+        let mut videos = self.with_tags(self.repository.connection(), videos).await?;
+        // Stemmed full-text matches needn't contain the term, so they aren't annotated
+        if let Some(search) = query.search.as_ref().filter(|_| !query.fts.unwrap_or(false)) {
+            for video in &mut videos {
+                video.matched_fields = Some(matched_fields(video, search));
+            }
+        }
+
+        Ok(PaginatedVideoResponse {
+            videos,
+            total,
+            page,
+            per_page,
+            total_pages,
+            total_including_deleted,
+            next_cursor,
+            prev_cursor,
+        })
+    }
+
+    /// Lists live videos with an id greater than `after`, ordered by id ascending
+    ///
+    /// Backs `GET /videos?cursor=true`. Takes the same filters and page size as
+    /// `list_videos`, but no total is counted and the order is fixed, so
+    /// `page`, `before` and `order_by` are rejected. One extra row is requested
+    /// to tell whether `next_cursor` should be set.
+    ///
+    /// # Errors
+    /// * `AppError::Validation` - If the query mixes cursor paging with page-based parameters
+    /// * `AppError::Database` - If there's an error executing the query
+    pub async fn list_videos_after_id(&self, mut query: VideoQuery, after: Option<i32>) -> AppResult<CursorVideoResponse> {
+        if query.page.is_some() || query.before.is_some() {
+            return Err(AppError::Validation(
+                "cursor cannot be combined with page or before".to_string(),
+            ));
+        }
+        if query.order_by.is_some() || query.order_direction.is_some() {
+            return Err(AppError::Validation(
+                "cursor pages are ordered by id; order_by cannot be combined with cursor".to_string(),
+            ));
+        }
+        let (per_page, _) = self.resolve_list_query(&mut query)?;
+
+        let mut videos = self.repository.list_after_id(&query, after, per_page + 1).await?;
+        let has_more = videos.len() as u64 > per_page;
+        videos.truncate(per_page as usize);
+        let next_cursor = videos.last().map(|video| video.id).filter(|_| has_more);
+
+        let mut videos = self.with_tags(self.repository.connection(), videos).await?;
+        if let Some(search) = query.search.as_ref().filter(|_| !query.fts.unwrap_or(false)) {
+            for video in &mut videos {
+                video.matched_fields = Some(matched_fields(video, search));
+            }
+        }
+
+        Ok(CursorVideoResponse { videos, per_page, next_cursor })
+    }
+
+    /// Clamps the page size and resolves `period` and ordering shared by the list endpoints
+    fn resolve_list_query(&self, query: &mut VideoQuery) -> AppResult<(u64, Vec<Sort>)> {
+        if query.per_page == Some(0) {
+            return Err(AppError::Validation(format!(
+                "per_page must be between 1 and {}",
+                self.config.max_per_page.max(self.config.min_per_page)
+            )));
+        }
+        if query.page == Some(0) {
+            return Err(AppError::Validation("page must be at least 1".to_string()));
+        }
+
+        let per_page = self.config.clamp_per_page(query.per_page);
+        query.per_page = Some(per_page);
+
+        if let Some(period) = query.period.take() {
+            if query.created_after.is_some() {
+                return Err(AppError::Validation(
+                    "period cannot be combined with created_after".to_string(),
+                ));
+            }
+            query.created_after = Some(period.start(chrono::Utc::now()).into());
+        }
+        if let (Some(after), Some(before)) = (query.created_after, query.created_before) {
+            if after.instant() > before.instant() {
+                return Err(AppError::Validation(
+                    "created_after must not be later than created_before".to_string(),
+                ));
+            }
+        }
+
+        Ok((per_page, self.resolve_sorts(query)?))
+    }
+
+    /// Resolves the query's ordering, falling back to `Config::default_sort`
+    fn resolve_sorts(&self, query: &VideoQuery) -> AppResult<Vec<Sort>> {
+        Sort::resolve_list(
+            query.order_by.as_deref(),
+            query.order_direction.as_deref(),
+            self.config.default_sort(),
+        )
+        .map_err(AppError::Validation)
+    }
+
+    /// Lists soft-deleted videos, shaped like `list_videos`
+    /// 
+    /// Takes the same page size, search, date and ordering parameters as
+    /// `list_videos`. Keyset cursors are not supported here.
+    /// 
+    /// # Errors
+    /// * `AppError::Validation` - If the ordering is invalid or `after`/`before` is given
+    /// * `AppError::Database` - If there's an error accessing the database
+    pub async fn list_deleted_videos(&self, mut query: VideoQuery) -> AppResult<PaginatedVideoResponse> {
+        let (per_page, sorts) = self.resolve_list_query(&mut query)?;
+        if query.after.is_some() || query.before.is_some() {
+            return Err(AppError::Validation(
+                "after and before are not supported on the trash".to_string(),
+            ));
+        }
+
+        let (videos, total) = self.repository.list_deleted(&query, &sorts).await?;
+        let total_pages = (total as f64 / per_page as f64).ceil() as u64;
+
+        Ok(PaginatedVideoResponse {
+            videos: self.with_tags(self.repository.connection(), videos).await?,
+            total,
+            page: query.page.unwrap_or(1),
+            per_page,
+            total_pages,
+            total_including_deleted: None,
+            next_cursor: None,
+            prev_cursor: None,
+        })
+    }
+
+    /// Creates or drops the unique title index to match `unique_normalized_titles`
+    /// 
+    /// The index makes the check safe under concurrent writes: the database
+    /// rejects the second of two racing inserts, which surfaces as
+    /// `AppError::Conflict`. Soft-deleted videos keep their title reserved.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If the index can't be changed, e.g. because
+    ///   stored titles already collide
+    pub async fn sync_title_uniqueness(&self) -> AppResult<()> {
+        let manager = SchemaManager::new(self.repository.connection());
+        indexes::sync_title_normalized_unique(&manager, self.config.unique_normalized_titles).await?;
+        Ok(())
+    }
+
+    /// Creates any missing indexes defined by the migrations and refreshes statistics
+    /// 
+    /// Index creation is idempotent. `ANALYZE` is run on every supported
+    /// backend; MySQL analyzes the `videos` table explicitly.
+    /// 
+    /// # Errors
+    /// * `AppError::Database` - If an index can't be inspected or created
+    pub async fn ensure_indexes(&self) -> AppResult<ReindexDbResponse> {
+        let db = self.repository.connection();
+        let manager = SchemaManager::new(db);
+        let outcomes = indexes::ensure_video_indexes(&manager).await?;
+
+        let analyze = match db.get_database_backend() {
+            DbBackend::MySql => "ANALYZE TABLE videos",
+            DbBackend::Postgres | DbBackend::Sqlite => "ANALYZE",
+        };
+        db.execute_unprepared(analyze).await?;
+
+        Ok(ReindexDbResponse {
+            created: outcomes.iter().filter(|o| o.created).map(|o| o.name.clone()).collect(),
+            present: outcomes.into_iter().map(|o| o.name).collect(),
+            analyzed: true,
+        })
+    }
+
+    /// Fetches one keyset page around the query's `after` or `before` cursor
+    /// 
+    /// One extra row is requested to tell whether another page exists in the
+    /// direction of travel.
+    async fn list_keyset(
+        &self,
+        query: &VideoQuery,
+        direction: SortDirection,
+        per_page: u64,
+    ) -> AppResult<(Vec<video::Model>, u64, Option<String>, Option<String>)> {
+        let (cursor, backward) = match (query.after.as_deref(), query.before.as_deref()) {
+            (Some(""), _) => (None, false),
+            (Some(after), _) => (Some(Cursor::decode(after)?), false),
+            (None, Some(before)) => (Some(Cursor::decode(before)?), true),
+            (None, None) => (None, false),
+        };
+
+        let (mut videos, total) = self.repository
+            .list_keyset(query, direction, cursor, backward, per_page + 1)
+            .await?;
+        let has_more = videos.len() as u64 > per_page;
+        videos.truncate(per_page as usize);
+        if backward {
+            videos.reverse();
+        }
+
+        let cursor_of = |video: &video::Model| Cursor { created_at: video.created_at, id: video.id }.encode();
+        let first = videos.first().map(cursor_of);
+        let last = videos.last().map(cursor_of);
+        let (next_cursor, prev_cursor) = if backward {
+            (last, first.filter(|_| has_more))
+        } else {
+            (last.filter(|_| has_more), first.filter(|_| cursor.is_some()))
+        };
+
+        Ok((videos, total, next_cursor, prev_cursor))
+    }
+
+    /// Regenerates the derived `title_normalized` key, walking the table in ID order
+    /// 
+    /// Titles themselves are never rewritten. Each batch of
+    /// `REINDEX_BATCH_SIZE` rows is processed in its own transaction. When
+    /// `max_batches` stops the sweep early, `next_cursor` holds the last
+    /// processed ID to pass back as `after_id`; it is `None` once the end of
+    /// the table is reached. Rows whose key is current are left untouched, so
+    /// the pass is safe to re-run.
+    /// 
+    /// # Errors
+    /// * `AppError::Conflict` - If unique titles are enforced and two regenerated keys collide
+    /// * `AppError::Database` - If a batch can't be read or written; earlier batches stay committed
+    pub async fn reindex(&self, query: ReindexQuery) -> AppResult<ReindexResponse> {
+        let mut cursor = query.after_id.unwrap_or(0);
+        let mut processed = 0;
+        let mut updated = 0;
+        let mut batches = 0;
+
+        loop {
+            if query.max_batches.is_some_and(|max| batches >= max) {
+                return Ok(ReindexResponse { processed, updated, next_cursor: Some(cursor) });
+            }
+
+            let txn = self.repository.begin().await?;
+            let rows = self.repository.find_batch_after_in(&txn, cursor, REINDEX_BATCH_SIZE).await?;
+            let Some(last) = rows.last() else {
+                txn.rollback().await?;
+                return Ok(ReindexResponse { processed, updated, next_cursor: None });
+            };
+            cursor = last.id;
+            let exhausted = (rows.len() as u64) < REINDEX_BATCH_SIZE;
+
+            for video in rows {
+                processed += 1;
+                let key = video::normalize_title_key(&video.title);
+                if key != video.title_normalized {
+                    self.repository.set_title_normalized_in(&txn, video.id, key).await?;
+                    updated += 1;
+                }
+            }
+            txn.commit().await?;
+            batches += 1;
+
+            if exhausted {
+                return Ok(ReindexResponse { processed, updated, next_cursor: None });
+            }
+        }
+    }
+}
+
+/// Replaces a full YouTube URL given as the `youtube_id` with the ID it points at
+fn with_extracted_youtube_id(mut req: CreateVideoRequest) -> CreateVideoRequest {
+    if let Some(youtube_id) = extract_youtube_id(&req.youtube_id) {
+        req.youtube_id = youtube_id;
+    }
+    req
+}
+
+/// Builds the repository input for a create, normalizing and deduplicating tags
+fn new_video(req: CreateVideoRequest) -> NewVideo {
+    let mut tags: Vec<String> = Vec::with_capacity(req.tags.len());
+    for name in req.tags.iter().map(|name| tag::normalize_name(name)) {
+        if !tags.contains(&name) {
+            tags.push(name);
+        }
+    }
+
+    NewVideo {
+        title: req.title,
+        youtube_id: req.youtube_id,
+        description: req.description,
+        status: req.status.unwrap_or_default(),
+        tags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use sea_orm::{Database, Schema, DatabaseConnection, DatabaseBackend, ConnectionTrait, EntityTrait};
+    use crate::entity::{video, video_tag};
+
+    async fn setup_database(db: &DatabaseConnection) {
+        let schema = Schema::new(DatabaseBackend::Sqlite);
+        for stmt in [
+            schema.create_table_from_entity(video::Entity),
+            schema.create_table_from_entity(tag::Entity),
+            schema.create_table_from_entity(video_tag::Entity),
+        ] {
+            db.execute(db.get_database_backend().build(&stmt)).await.unwrap();
+        }
+    }
+
+    #[ntex::test]
+    async fn test_create_video() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo);
+
+        let request = CreateVideoRequest {
+            title: "Test Video".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(), // 11 characters
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        };
+
+        let result = service.create_video(request).await;
+        assert!(result.is_ok());
+    }
+
+    #[ntex::test]
+    async fn test_get_video() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo);
+
+        let request = CreateVideoRequest {
+            title: "Test Video".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(), // 11 characters
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        };
+
+        let video = service.create_video(request).await.unwrap();
+        let result = service.get_video(video.id).await;
+        assert!(result.is_ok());
+    }
+
+    #[ntex::test]
+    async fn test_list_videos() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo);
+
+        // 创建一些测试视频
+        for i in 0..3 {
+            let request = CreateVideoRequest {
+                title: format!("Test Video {}", i),
+                youtube_id: format!("dQw4w9WgXc{}", i), // 11 characters
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            };
+            service.create_video(request).await.unwrap();
+        }
+
+        let result = service.list_videos(VideoQuery::default()).await;
         assert!(result.is_ok());
+        let videos = result.unwrap();
+        assert_eq!(videos.videos.len(), 3);
+    }
+
+    #[ntex::test]
+    async fn test_fetch_videos_reports_missing() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo);
+
+        let mut ids = Vec::new();
+        for i in 0..3 {
+            let request = CreateVideoRequest {
+                title: format!("Test Video {}", i),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            };
+            ids.push(service.create_video(request).await.unwrap().id);
+        }
+        service.delete_video(ids[2]).await.unwrap();
+
+        let all_found = service.fetch_videos(FetchVideosRequest { ids: vec![ids[1], ids[0]] }).await.unwrap();
+        assert_eq!(all_found.found.iter().map(|v| v.id).collect::<Vec<_>>(), vec![ids[1], ids[0]]);
+        assert!(all_found.missing.is_empty());
+
+        let some_missing = service.fetch_videos(FetchVideosRequest { ids: vec![ids[0], ids[2], 999] }).await.unwrap();
+        assert_eq!(some_missing.found.iter().map(|v| v.id).collect::<Vec<_>>(), vec![ids[0]]);
+        assert_eq!(some_missing.missing, vec![ids[2], 999]);
+
+        let all_missing = service.fetch_videos(FetchVideosRequest { ids: vec![998, 999] }).await.unwrap();
+        assert!(all_missing.found.is_empty());
+        assert_eq!(all_missing.missing, vec![998, 999]);
+    }
+
+    #[ntex::test]
+    async fn test_custom_validation_step_runs_and_rejects() {
+        use crate::services::validation::{ValidationStep, VideoWrite};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct RejectTitle(Arc<AtomicUsize>);
+
+        #[async_trait::async_trait]
+        impl ValidationStep for RejectTitle {
+            async fn validate(&self, write: &VideoWrite<'_>) -> AppResult<()> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                match write {
+                    VideoWrite::Create(req) if req.title == "Forbidden" => {
+                        Err(AppError::Validation("title is forbidden".to_string()))
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let service = VideoService::new(VideoRepository::new(db))
+            .with_validation_step(RejectTitle(calls.clone()));
+
+        let accepted = service.create_video(CreateVideoRequest {
+            title: "Allowed".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await;
+        assert!(accepted.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let rejected = service.create_video(CreateVideoRequest {
+            title: "Forbidden".to_string(),
+            youtube_id: "dQw4w9WgXcA".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await;
+        assert!(matches!(rejected, Err(AppError::Validation(msg)) if msg == "title is forbidden"));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Built-in field rules run before custom steps
+        let invalid = service.create_video(CreateVideoRequest {
+            title: "Forbidden".to_string(),
+            youtube_id: "short".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await;
+        assert!(matches!(invalid, Err(AppError::ValidationDetailed { .. })));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let listed = service.list_videos(VideoQuery::default()).await.unwrap();
+        assert_eq!(listed.total, 1);
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_whole_word_search() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo);
+
+        let titles = ["Birthday party", "Modern art, explained", "Art", "100%_art"];
+        for (i, title) in titles.iter().enumerate() {
+            let request = CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            };
+            service.create_video(request).await.unwrap();
+        }
+
+        let substring = service.list_videos(VideoQuery {
+            search: Some("art".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(substring.total, 4);
+
+        let whole_word = service.list_videos(VideoQuery {
+            search: Some("art".to_string()),
+            whole_word: Some(true),
+            ..Default::default()
+        }).await.unwrap();
+        let mut matched: Vec<_> = whole_word.videos.iter().map(|v| v.title.as_str()).collect();
+        matched.sort();
+        assert_eq!(matched, vec!["Art", "Modern art, explained"]);
+
+        let wildcard = service.list_videos(VideoQuery {
+            search: Some("%_art".to_string()),
+            whole_word: Some(true),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(wildcard.total, 0);
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_search_modes() {
+        use crate::db::SearchMode;
+
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo);
+
+        let titles = ["Art", "Art history", "Modern art", "Party", "50% art"];
+        for (i, title) in titles.iter().enumerate() {
+            let request = CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            };
+            service.create_video(request).await.unwrap();
+        }
+
+        let search = |term: &str, mode: Option<SearchMode>, case_insensitive: Option<bool>| VideoQuery {
+            search: Some(term.to_string()),
+            search_mode: mode,
+            case_insensitive,
+            ..Default::default()
+        };
+        let titles_of = |page: PaginatedVideoResponse| {
+            let mut titles: Vec<_> = page.videos.into_iter().map(|v| v.title).collect();
+            titles.sort();
+            titles
+        };
+
+        let contains = service.list_videos(search("art", None, Some(true))).await.unwrap();
+        assert_eq!(titles_of(contains), vec!["50% art", "Art", "Art history", "Modern art", "Party"]);
+
+        let prefix = service.list_videos(search("art", Some(SearchMode::Prefix), Some(true))).await.unwrap();
+        assert_eq!(titles_of(prefix), vec!["Art", "Art history"]);
+
+        let exact = service.list_videos(search("art", Some(SearchMode::Exact), None)).await.unwrap();
+        assert_eq!(exact.total, 0);
+        let exact = service.list_videos(search("art", Some(SearchMode::Exact), Some(true))).await.unwrap();
+        assert_eq!(titles_of(exact), vec!["Art"]);
+
+        let wildcard = service.list_videos(search("0%", Some(SearchMode::Contains), None)).await.unwrap();
+        assert_eq!(titles_of(wildcard), vec!["50% art"]);
+        let wildcard = service.list_videos(search("%art", Some(SearchMode::Prefix), None)).await.unwrap();
+        assert_eq!(wildcard.total, 0);
     }
 
     #[ntex::test]
-    async fn test_get_video() {
+    async fn test_list_videos_reports_matched_fields() {
         let db = Database::connect("sqlite::memory:").await.unwrap();
         setup_database(&db).await;
-        let repo = VideoRepository::new(db);
+        let service = VideoService::new(VideoRepository::new(db));
+
+        for (title, youtube_id) in [("Cooking basics", "Xk9cooK0001"), ("Gardening", "Zq8plant001")] {
+            service.create_video(CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: youtube_id.to_string(),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+        }
+
+        let page = service.list_videos(VideoQuery {
+            search: Some("plant".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.videos[0].title, "Gardening");
+        assert_eq!(page.videos[0].matched_fields, Some(vec!["youtube_id".to_string()]));
+
+        let page = service.list_videos(VideoQuery {
+            search: Some("cook".to_string()),
+            case_insensitive: Some(true),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(
+            page.videos[0].matched_fields,
+            Some(vec!["title".to_string(), "youtube_id".to_string()])
+        );
+
+        let page = service.list_videos(VideoQuery::default()).await.unwrap();
+        assert!(page.videos.iter().all(|video| video.matched_fields.is_none()));
+        let json = serde_json::to_value(&page.videos[0]).unwrap();
+        assert!(json.get("matched_fields").is_none());
+    }
+
+    /// In-memory `VideoRepositoryTrait` for exercising the service without a database
+    #[derive(Default)]
+    struct FakeRepository {
+        videos: std::sync::Mutex<Vec<(video::Model, Vec<String>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl VideoRepositoryTrait for FakeRepository {
+        async fn create(&self, new: NewVideo) -> AppResult<video::Model> {
+            let mut videos = self.videos.lock().unwrap();
+            let now = chrono::Utc::now();
+            let video = video::Model {
+                id: videos.len() as i32 + 1,
+                title_normalized: video::normalize_title_key(&new.title),
+                title: new.title,
+                youtube_id: new.youtube_id,
+                description: new.description,
+                view_count: 0,
+                status: new.status,
+                position: videos.len() as i32 + 1,
+                created_at: now,
+                updated_at: now,
+                deleted_at: None,
+            };
+            let mut tags = new.tags;
+            tags.sort();
+            videos.push((video.clone(), tags));
+            Ok(video)
+        }
+
+        async fn find_by_id(&self, id: i32) -> AppResult<Option<video::Model>> {
+            let videos = self.videos.lock().unwrap();
+            Ok(videos.iter().map(|(video, _)| video).find(|video| video.id == id && video.deleted_at.is_none()).cloned())
+        }
+
+        async fn update(
+            &self,
+            id: i32,
+            changes: VideoChanges,
+            _expected_updated_at: Option<DateTime<Utc>>,
+        ) -> AppResult<Option<video::Model>> {
+            let mut videos = self.videos.lock().unwrap();
+            let Some((video, _)) = videos.iter_mut().find(|(video, _)| video.id == id && video.deleted_at.is_none()) else {
+                return Ok(None);
+            };
+            if let Some(title) = changes.title {
+                video.title_normalized = video::normalize_title_key(&title);
+                video.title = title;
+            }
+            video.youtube_id = changes.youtube_id.unwrap_or(video.youtube_id.clone());
+            video.description = changes.description.unwrap_or(video.description.take());
+            video.status = changes.status.unwrap_or(video.status);
+            video.updated_at = chrono::Utc::now();
+            Ok(Some(video.clone()))
+        }
+
+        async fn delete(&self, id: i32) -> AppResult<Option<video::Model>> {
+            let mut videos = self.videos.lock().unwrap();
+            let Some((video, _)) = videos.iter_mut().find(|(video, _)| video.id == id && video.deleted_at.is_none()) else {
+                return Ok(None);
+            };
+            video.deleted_at = Some(chrono::Utc::now());
+            Ok(Some(video.clone()))
+        }
+
+        async fn list(&self, _query: &VideoQuery, _sorts: &[Sort]) -> AppResult<(Vec<video::Model>, u64)> {
+            let videos = self.videos.lock().unwrap();
+            let live: Vec<_> = videos.iter().map(|(video, _)| video.clone()).filter(|video| video.deleted_at.is_none()).collect();
+            let total = live.len() as u64;
+            Ok((live, total))
+        }
+
+        async fn tags_for(&self, video_ids: &[i32]) -> AppResult<std::collections::HashMap<i32, Vec<String>>> {
+            let videos = self.videos.lock().unwrap();
+            Ok(videos
+                .iter()
+                .filter(|(video, tags)| video_ids.contains(&video.id) && !tags.is_empty())
+                .map(|(video, tags)| (video.id, tags.clone()))
+                .collect())
+        }
+    }
+
+    #[ntex::test]
+    async fn test_get_video_against_fake_repository() {
+        let repo = FakeRepository::default();
+        let kept = repo.create(NewVideo {
+            title: "Kept".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: video::VideoStatus::Draft,
+            tags: vec!["rust".to_string(), "music".to_string()],
+        }).await.unwrap();
+        let removed = repo.create(NewVideo {
+            title: "Removed".to_string(),
+            youtube_id: "xQc9WgXw4Qd".to_string(),
+            description: None,
+            status: video::VideoStatus::Draft,
+            tags: Vec::new(),
+        }).await.unwrap();
+        repo.delete(removed.id).await.unwrap();
         let service = VideoService::new(repo);
 
-        let request = CreateVideoRequest {
-            title: "Test Video".to_string(),
-            youtube_id: "dQw4w9WgXcQ".to_string(), // 11 characters
+        let video = service.get_video(kept.id).await.unwrap();
+        assert_eq!(video.title, "Kept");
+        assert_eq!(video.tags, vec!["music", "rust"]);
+
+        assert!(matches!(service.get_video(removed.id).await, Err(AppError::NotFound(_))));
+        assert!(matches!(service.get_video(99).await, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_extract_youtube_id() {
+        for input in [
+            "dQw4w9WgXcQ",
+            " dQw4w9WgXcQ ",
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://www.youtube.com/watch?feature=share&v=dQw4w9WgXcQ&t=42s",
+            "http://m.youtube.com/watch?v=dQw4w9WgXcQ#comments",
+            "youtube.com/watch?v=dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://youtu.be/dQw4w9WgXcQ?si=abc&t=10",
+            "https://www.youtube.com/shorts/dQw4w9WgXcQ",
+            "https://youtube.com/shorts/dQw4w9WgXcQ?feature=share",
+        ] {
+            assert_eq!(extract_youtube_id(input).as_deref(), Some("dQw4w9WgXcQ"), "{input}");
+        }
+
+        for input in [
+            "",
+            "short",
+            "dQw4w9WgXc!",
+            "https://www.youtube.com/watch?list=PL123",
+            "https://youtu.be/tooshort",
+            "https://example.com/watch?v=dQw4w9WgXcQ",
+        ] {
+            assert_eq!(extract_youtube_id(input), None, "{input}");
+        }
+    }
+
+    #[ntex::test]
+    async fn test_create_and_update_accept_youtube_urls() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let video = service.create_video(CreateVideoRequest {
+            title: "From a link".to_string(),
+            youtube_id: "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42s".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await.unwrap();
+        assert_eq!(video.youtube_id, "dQw4w9WgXcQ");
+
+        let updated = service.update_video(video.id, UpdateVideoRequest {
+            title: None,
+            youtube_id: Some("https://youtu.be/dQw4w9WgXcQ".to_string()),
+            description: None,
+            status: None,
+        }, None).await.unwrap();
+        assert_eq!(updated.youtube_id, "dQw4w9WgXcQ");
+    }
+
+    #[ntex::test]
+    async fn test_batch_and_import_accept_youtube_urls() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+        let linked = |title: &str, youtube_id: &str| CreateVideoRequest {
+            title: title.to_string(),
+            youtube_id: youtube_id.to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
         };
 
-        let video = service.create_video(request).await.unwrap();
-        let result = service.get_video(video.id).await;
-        assert!(result.is_ok());
+        let videos = service.create_videos_batch(vec![
+            linked("Watch link", "https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+            linked("Short link", "https://youtu.be/xQc9WgXw4Qd?t=7"),
+        ]).await.unwrap();
+        let youtube_ids: Vec<_> = videos.iter().map(|v| v.youtube_id.as_str()).collect();
+        assert_eq!(youtube_ids, vec!["dQw4w9WgXcQ", "xQc9WgXw4Qd"]);
+
+        // The URL resolves to an existing ID, so it conflicts like the bare ID would
+        let duplicate = service.create_videos_batch(vec![
+            linked("Again", "https://youtu.be/dQw4w9WgXcQ"),
+        ]).await;
+        assert!(matches!(duplicate, Err(AppError::Conflict(_))));
+
+        let imported = service.import_videos(vec![
+            linked("Renamed", "https://youtu.be/dQw4w9WgXcQ"),
+        ]).await.unwrap();
+        assert_eq!((imported.inserted, imported.updated), (0, 1));
+    }
+
+    #[test]
+    fn test_period_start() {
+        use crate::db::Period;
+        use chrono::TimeZone;
+
+        // A Thursday afternoon
+        let now = chrono::Utc.with_ymd_and_hms(2024, 5, 16, 15, 30, 0).unwrap();
+        assert_eq!(Period::Today.start(now), chrono::Utc.with_ymd_and_hms(2024, 5, 16, 0, 0, 0).unwrap());
+        assert_eq!(Period::Week.start(now), chrono::Utc.with_ymd_and_hms(2024, 5, 13, 0, 0, 0).unwrap());
+        assert_eq!(Period::Month.start(now), chrono::Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap());
+        assert_eq!(Period::Year.start(now), chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
     }
 
     #[ntex::test]
-    async fn test_list_videos() {
+    async fn test_list_videos_by_period() {
+        use crate::db::Period;
+        use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
         let db = Database::connect("sqlite::memory:").await.unwrap();
         setup_database(&db).await;
-        let repo = VideoRepository::new(db);
+        let repo = VideoRepository::new(db.clone());
         let service = VideoService::new(repo);
 
-        // 创建一些测试视频
+        let recent = service.create_video(CreateVideoRequest {
+            title: "Recent".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await.unwrap();
+        let old = service.create_video(CreateVideoRequest {
+            title: "Old".to_string(),
+            youtube_id: "xQc9WgXw4Qd".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await.unwrap();
+
+        let mut old_row: video::ActiveModel = video::Entity::find_by_id(old.id)
+            .one(&db).await.unwrap().unwrap().into();
+        old_row.created_at = Set(chrono::Utc::now() - chrono::Duration::days(400));
+        old_row.update(&db).await.unwrap();
+
+        for period in [Period::Today, Period::Week, Period::Month, Period::Year] {
+            let result = service.list_videos(VideoQuery {
+                period: Some(period),
+                ..Default::default()
+            }).await.unwrap();
+            assert_eq!(result.total, 1, "{:?}", period);
+            assert_eq!(result.videos[0].id, recent.id);
+        }
+
+        let all = service.list_videos(VideoQuery::default()).await.unwrap();
+        assert_eq!(all.total, 2);
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_rejects_period_with_created_after() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let result = service.list_videos(VideoQuery {
+            period: Some(crate::db::Period::Week),
+            created_after: Some(chrono::Utc::now().into()),
+            ..Default::default()
+        }).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[ntex::test]
+    async fn test_youtube_id_unique_violation_maps_to_friendly_error() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        db.execute_unprepared("CREATE UNIQUE INDEX idx_videos_youtube_id_unique ON videos (youtube_id)")
+            .await
+            .unwrap();
+        let repo = VideoRepository::new(db);
+
+        repo.create(NewVideo { title: "First".to_string(), youtube_id: "dQw4w9WgXcQ".to_string(), description: None, status: video::VideoStatus::Draft, tags: Vec::new() }).await.unwrap();
+        // Bypasses the service-level check, as a racing insert would
+        match repo.create(NewVideo { title: "Second".to_string(), youtube_id: "dQw4w9WgXcQ".to_string(), description: None, status: video::VideoStatus::Draft, tags: Vec::new() }).await {
+            Err(AppError::Conflict(message)) => assert_eq!(message, "youtube_id already exists"),
+            other => panic!("expected Conflict, got {:?}", other.map(|v| v.id)),
+        }
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_by_created_window() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db.clone());
+        let service = VideoService::new(repo.clone());
+        let day = |d: u32| chrono::Utc.with_ymd_and_hms(2024, 5, d, 12, 0, 0).unwrap();
+        for (d, title) in [(1, "Rust early"), (10, "Rust middle"), (12, "Go middle"), (20, "Rust late")] {
+            let video = service.create_video(CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: format!("dQw4w9WgX{:02}", d),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+            let mut model: video::ActiveModel = repo.find_by_id(video.id).await.unwrap().unwrap().into();
+            model.created_at = sea_orm::Set(day(d));
+            sea_orm::ActiveModelTrait::update(model, &db).await.unwrap();
+        }
+
+        let window = |search: Option<&str>| VideoQuery {
+            search: search.map(str::to_string),
+            created_after: Some(day(5).into()),
+            created_before: Some(day(15).into()),
+            ..Default::default()
+        };
+        let page = service.list_videos(window(None)).await.unwrap();
+        let titles: Vec<&str> = page.videos.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(titles, vec!["Go middle", "Rust middle"]);
+
+        let page = service.list_videos(window(Some("Rust"))).await.unwrap();
+        let titles: Vec<&str> = page.videos.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(titles, vec!["Rust middle"]);
+
+        let result = service.list_videos(VideoQuery {
+            created_after: Some(day(15).into()),
+            created_before: Some(day(5).into()),
+            ..Default::default()
+        }).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_keyset_pages_forward_and_back() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+        for i in 0..5 {
+            service.create_video(CreateVideoRequest {
+                title: format!("Test Video {}", i),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+        }
+        let keyset = |after: Option<String>, before: Option<String>| VideoQuery {
+            page: None,
+            per_page: Some(2),
+            after,
+            before,
+            ..Default::default()
+        };
+        let ids = |page: &PaginatedVideoResponse| page.videos.iter().map(|v| v.id).collect::<Vec<_>>();
+
+        let first = service.list_videos(keyset(Some(String::new()), None)).await.unwrap();
+        assert_eq!(ids(&first), vec![5, 4]);
+        assert_eq!(first.prev_cursor, None);
+
+        let second = service.list_videos(keyset(first.next_cursor.clone(), None)).await.unwrap();
+        assert_eq!(ids(&second), vec![3, 2]);
+        assert!(second.prev_cursor.is_some());
+
+        let last = service.list_videos(keyset(second.next_cursor.clone(), None)).await.unwrap();
+        assert_eq!(ids(&last), vec![1]);
+        assert_eq!(last.next_cursor, None);
+        assert_eq!(last.total, 5);
+
+        let back = service.list_videos(keyset(None, second.prev_cursor.clone())).await.unwrap();
+        assert_eq!(ids(&back), vec![5, 4]);
+        assert_eq!(back.prev_cursor, None);
+        assert_eq!(back.next_cursor, first.next_cursor);
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_clamps_and_rejects_per_page() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let config = Config { max_per_page: 20, ..Config::default() };
+        let service = VideoService::with_config(VideoRepository::new(db), config);
+
+        let page = service.list_videos(VideoQuery {
+            per_page: Some(1_000_000),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(page.per_page, 20);
+
+        match service.list_videos(VideoQuery { per_page: Some(0), ..Default::default() }).await {
+            Err(AppError::Validation(message)) => assert_eq!(message, "per_page must be between 1 and 20"),
+            other => panic!("expected validation error, got {:?}", other.map(|p| p.per_page)),
+        }
+        let result = service.list_videos(VideoQuery { page: Some(0), ..Default::default() }).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_offset_mode_has_no_cursors() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let page = service.list_videos(VideoQuery::default()).await.unwrap();
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(page.prev_cursor, None);
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_include_deleted_total() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let mut ids = Vec::new();
         for i in 0..3 {
             let request = CreateVideoRequest {
                 title: format!("Test Video {}", i),
-                youtube_id: format!("dQw4w9WgXc{}", i), // 11 characters
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
             };
-            service.create_video(request).await.unwrap();
+            ids.push(service.create_video(request).await.unwrap().id);
         }
+        service.delete_video(ids[0]).await.unwrap();
 
-        let result = service.list_videos(VideoQuery::default()).await;
-        assert!(result.is_ok());
-        let videos = result.unwrap();
-        assert_eq!(videos.videos.len(), 3);
+        let default = service.list_videos(VideoQuery::default()).await.unwrap();
+        assert_eq!(default.total, 2);
+        assert_eq!(default.total_including_deleted, None);
+        let json = serde_json::to_value(&default).unwrap();
+        assert!(json.get("total_including_deleted").is_none());
+
+        let with_deleted = service.list_videos(VideoQuery {
+            include_deleted_total: Some(true),
+            ..Default::default()
+        }).await.unwrap();
+        assert_eq!(with_deleted.total, 2);
+        assert_eq!(with_deleted.total_including_deleted, Some(3));
     }
 
     #[ntex::test]
@@ -377,15 +2307,20 @@ mod tests {
         let request = CreateVideoRequest {
             title: "Test Video".to_string(),
             youtube_id: "dQw4w9WgXcQ".to_string(), // 11 characters
+            description: None,
+            status: None,
+            tags: Vec::new(),
         };
 
         let video = service.create_video(request).await.unwrap();
         let update_request = UpdateVideoRequest {
             title: Some("Updated Video".to_string()),
             youtube_id: Some("xQc9WgXw4Qd".to_string()), // 11 characters
+            description: None,
+            status: None,
         };
 
-        let result = service.update_video(video.id, update_request).await;
+        let result = service.update_video(video.id, update_request, None).await;
         assert!(result.is_ok());
 
         let updated = service.get_video(video.id).await.unwrap();
@@ -393,6 +2328,342 @@ mod tests {
         assert_eq!(updated.youtube_id, "xQc9WgXw4Qd");
     }
 
+    #[ntex::test]
+    async fn test_update_without_fields_is_rejected() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let video = service.create_video(CreateVideoRequest {
+            title: "Test Video".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await.unwrap();
+
+        let result = service.update_video(video.id, UpdateVideoRequest {
+            title: None,
+            youtube_id: None,
+            description: None,
+            status: None,
+        }, None).await;
+        match result {
+            Err(AppError::BadRequest(message)) => assert_eq!(message, "no fields to update"),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+
+        let unchanged = service.get_video(video.id).await.unwrap();
+        assert_eq!(unchanged.updated_at, video.updated_at);
+    }
+
+    #[ntex::test]
+    async fn test_update_video_youtube_id_immutable() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let config = Config {
+            allow_youtube_id_update: false,
+            ..Config::default()
+        };
+        let service = VideoService::with_config(VideoRepository::new(db), config);
+
+        let video = service.create_video(CreateVideoRequest {
+            title: "Test Video".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await.unwrap();
+
+        let result = service.update_video(video.id, UpdateVideoRequest {
+            title: None,
+            youtube_id: Some("xQc9WgXw4Qd".to_string()),
+            description: None,
+            status: None,
+        }, None).await;
+        match result {
+            Err(AppError::BadRequest(message)) => assert_eq!(message, "youtube_id is immutable"),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+
+        let updated = service.update_video(video.id, UpdateVideoRequest {
+            title: Some("Updated Video".to_string()),
+            youtube_id: None,
+            description: None,
+            status: None,
+        }, None).await.unwrap();
+        assert_eq!(updated.title, "Updated Video");
+        assert_eq!(updated.youtube_id, "dQw4w9WgXcQ");
+
+        let replaced = service.update_video(video.id, UpdateVideoRequest {
+            title: Some("Replaced Video".to_string()),
+            youtube_id: Some("dQw4w9WgXcQ".to_string()),
+            description: None,
+            status: None,
+        }, None).await.unwrap();
+        assert_eq!(replaced.title, "Replaced Video");
+    }
+
+    #[ntex::test]
+    async fn test_reindex_regenerates_title_keys_and_resumes() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db.clone());
+        let service = VideoService::new(repo.clone());
+
+        let total = REINDEX_BATCH_SIZE + 5;
+        for i in 0..total {
+            repo.create(NewVideo { title: format!("  Messy   Title {} ", i), youtube_id: "dQw4w9WgXcQ".to_string(), description: None, status: video::VideoStatus::Draft, tags: Vec::new() }).await.unwrap();
+        }
+        // Leave every key stale, as rows written before the key existed would be
+        video::Entity::update_many()
+            .col_expr(video::Column::TitleNormalized, sea_orm::sea_query::Expr::col(video::Column::Title).into())
+            .exec(&db)
+            .await
+            .unwrap();
+        let before = repo.find_by_id(1).await.unwrap().unwrap();
+
+        let first = service.reindex(ReindexQuery { after_id: None, max_batches: Some(1) }).await.unwrap();
+        assert_eq!(first.processed, REINDEX_BATCH_SIZE);
+        assert_eq!(first.updated, REINDEX_BATCH_SIZE);
+        assert!(first.next_cursor.is_some());
+
+        let rest = service.reindex(ReindexQuery { after_id: first.next_cursor, max_batches: None }).await.unwrap();
+        assert_eq!(rest.processed, 5);
+        assert_eq!(rest.next_cursor, None);
+
+        let video = repo.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(video.title_normalized, "messy   title 0");
+        assert_eq!(video.title, "  Messy   Title 0 ");
+        assert_eq!(video.updated_at, before.updated_at);
+
+        let rerun = service.reindex(ReindexQuery::default()).await.unwrap();
+        assert_eq!(rerun.processed, total);
+        assert_eq!(rerun.updated, 0);
+    }
+
+    #[ntex::test]
+    async fn test_ensure_indexes_creates_missing_indexes_once() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let first = service.ensure_indexes().await.unwrap();
+        assert_eq!(first.created, first.present);
+        assert!(first.analyzed);
+
+        let second = service.ensure_indexes().await.unwrap();
+        assert!(second.created.is_empty());
+        assert_eq!(second.present, first.present);
+    }
+
+    #[ntex::test]
+    async fn test_validation_echoes_received_value_only_when_enabled() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let invalid = || CreateVideoRequest {
+            title: "Test Video".to_string(),
+            youtube_id: "way-too-long-youtube-id".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        };
+
+        let service = VideoService::new(VideoRepository::new(db.clone()));
+        assert!(matches!(service.create_video(invalid()).await, Err(AppError::ValidationDetailed { .. })));
+
+        let config = Config {
+            validation_echo_max_chars: Some(8),
+            ..Config::default()
+        };
+        let service = VideoService::with_config(VideoRepository::new(db), config);
+        match service.create_video(invalid()).await {
+            Err(AppError::InvalidValue { received, .. }) => {
+                assert_eq!(received.get("youtube_id").map(String::as_str), Some("way-too-"));
+                assert!(!received.contains_key("title"));
+            }
+            other => panic!("expected InvalidValue, got {:?}", other),
+        }
+    }
+
+    #[ntex::test]
+    async fn test_related_videos_ranked_by_shared_title_words() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+        let titles = [
+            "Rust async tutorial",
+            "Async Rust deep dive",
+            "Rust for beginners",
+            "Cooking pasta",
+            "Async Rust tutorial, part 2",
+        ];
+        for (i, title) in titles.iter().enumerate() {
+            service.create_video(CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+        }
+        service.delete_video(2).await.unwrap();
+
+        let related = service.related_videos(1, RelatedQuery::default()).await.unwrap();
+        let ids: Vec<i32> = related.videos.iter().map(|v| v.id).collect();
+        assert_eq!(ids, vec![5, 3]);
+
+        let limited = service.related_videos(1, RelatedQuery { limit: Some(1) }).await.unwrap();
+        assert_eq!(limited.videos.len(), 1);
+
+        let unrelated = service.related_videos(4, RelatedQuery::default()).await.unwrap();
+        assert!(unrelated.videos.is_empty());
+
+        assert!(matches!(
+            service.related_videos(99, RelatedQuery::default()).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[ntex::test]
+    async fn test_validate_import_reports_row_errors() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+
+        let csv = "title,youtube_id\nFirst,dQw4w9WgXcQ\n,xQc9WgXw4Qd\nSecond,short\n";
+        let result = service.validate_import(csv.as_bytes()).await.unwrap();
+        assert_eq!(result.valid_rows, 1);
+        let lines: Vec<u64> = result.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+
+        let total = service.list_videos(VideoQuery::default()).await.unwrap().total;
+        assert_eq!(total, 0);
+    }
+
+    #[ntex::test]
+    async fn test_omitted_order_is_stable_across_offset_and_keyset() {
+        use sea_orm::{sea_query::Expr, EntityTrait};
+
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db.clone()));
+        for i in 0..4 {
+            service.create_video(CreateVideoRequest {
+                title: format!("Test Video {}", i),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+        }
+        // Identical timestamps leave the id tie-breaker to decide the order
+        video::Entity::update_many()
+            .col_expr(video::Column::CreatedAt, Expr::value(chrono::Utc::now()))
+            .exec(&db)
+            .await
+            .unwrap();
+
+        let unordered = |page: Option<u64>, after: Option<String>| VideoQuery {
+            page,
+            per_page: Some(2),
+            order_by: None,
+            order_direction: None,
+            after,
+            ..Default::default()
+        };
+        let ids = |page: PaginatedVideoResponse| page.videos.iter().map(|v| v.id).collect::<Vec<_>>();
+
+        let mut offset = ids(service.list_videos(unordered(Some(1), None)).await.unwrap());
+        offset.extend(ids(service.list_videos(unordered(Some(2), None)).await.unwrap()));
+
+        let first = service.list_videos(unordered(None, Some(String::new()))).await.unwrap();
+        let next = first.next_cursor.clone();
+        let mut keyset = ids(first);
+        keyset.extend(ids(service.list_videos(unordered(None, next)).await.unwrap()));
+
+        let export: Vec<i32> = service.export_page(1).await.unwrap().iter().map(|v| v.id).collect();
+
+        assert_eq!(offset, vec![4, 3, 2, 1]);
+        assert_eq!(keyset, offset);
+        assert_eq!(export, offset);
+    }
+
+    #[ntex::test]
+    async fn test_configured_default_sort_applies_when_order_omitted() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let config = Config {
+            default_sort: Sort { key: SortKey::Title, direction: SortDirection::Asc },
+            ..Config::default()
+        };
+        let service = VideoService::with_config(VideoRepository::new(db), config);
+        for (i, title) in ["Charlie", "Alpha", "Bravo"].iter().enumerate() {
+            service.create_video(CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+        }
+
+        let page = service.list_videos(VideoQuery {
+            order_by: None,
+            order_direction: None,
+            ..Default::default()
+        }).await.unwrap();
+        let titles: Vec<_> = page.videos.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(titles, vec!["Alpha", "Bravo", "Charlie"]);
+
+        let export = service.export_page(1).await.unwrap();
+        let exported: Vec<_> = export.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(exported, titles);
+
+        let result = service.list_videos(VideoQuery {
+            order_by: Some("views".to_string()),
+            ..Default::default()
+        }).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_multi_column_sort() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+        for (i, title) in ["Bravo", "Alpha", "Bravo", "Alpha"].iter().enumerate() {
+            service.create_video(CreateVideoRequest {
+                title: title.to_string(),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            }).await.unwrap();
+        }
+
+        let page = service.list_videos(VideoQuery {
+            order_by: Some("title,-youtube_id".to_string()),
+            order_direction: Some("asc".to_string()),
+            ..Default::default()
+        }).await.unwrap();
+        let order: Vec<_> = page.videos.iter().map(|v| (v.title.as_str(), v.youtube_id.as_str())).collect();
+        assert_eq!(order, vec![
+            ("Alpha", "dQw4w9WgXc3"),
+            ("Alpha", "dQw4w9WgXc1"),
+            ("Bravo", "dQw4w9WgXc2"),
+            ("Bravo", "dQw4w9WgXc0"),
+        ]);
+
+        for order_by in ["title,views", "title,-title", "-"] {
+            let result = service.list_videos(VideoQuery {
+                order_by: Some(order_by.to_string()),
+                ..Default::default()
+            }).await;
+            assert!(matches!(result, Err(AppError::Validation(_))), "{} should be rejected", order_by);
+        }
+    }
+
     #[ntex::test]
     async fn test_delete_video() {
         let db = Database::connect("sqlite::memory:").await.unwrap();
@@ -403,6 +2674,9 @@ mod tests {
         let request = CreateVideoRequest {
             title: "Test Video".to_string(),
             youtube_id: "dQw4w9WgXcQ".to_string(), // 11 characters
+            description: None,
+            status: None,
+            tags: Vec::new(),
         };
 
         let video = service.create_video(request).await.unwrap();
@@ -412,4 +2686,82 @@ mod tests {
         let get_result = service.get_video(video.id).await;
         assert!(get_result.is_err());
     }
+
+    #[ntex::test]
+    async fn test_batch_delete_publishes_deleted_events() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+        let mut ids = Vec::new();
+        for i in 0..2 {
+            let video = service.create_video(CreateVideoRequest {
+                title: format!("Video {}", i),
+                youtube_id: format!("dQw4w9WgXc{}", i),
+                description: None,
+                status: None,
+                tags: vec!["music".to_string()],
+            }).await.unwrap();
+            ids.push(video.id);
+        }
+
+        let mut events = service.subscribe_events();
+        service.delete_videos_batch(ids.clone()).await.unwrap();
+
+        for id in ids {
+            let event = events.try_recv().unwrap();
+            assert_eq!(event.kind, EventKind::Deleted);
+            assert_eq!(event.video.id, id);
+            assert!(event.video.deleted_at.is_some());
+            assert_eq!(event.video.tags, vec!["music".to_string()]);
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[ntex::test]
+    async fn test_hard_delete_publishes_deleted_event() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let service = VideoService::new(VideoRepository::new(db));
+        let video = service.create_video(CreateVideoRequest {
+            title: "Doomed".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: vec!["music".to_string()],
+        }).await.unwrap();
+
+        let mut events = service.subscribe_events();
+        service.hard_delete_video_dry_run(video.id).await.unwrap();
+        assert!(events.try_recv().is_err());
+
+        service.hard_delete_video(video.id).await.unwrap();
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.kind, EventKind::Deleted);
+        assert_eq!(event.video.id, video.id);
+        assert_eq!(event.video.title, "Doomed");
+        assert_eq!(event.video.tags, vec!["music".to_string()]);
+        assert!(events.try_recv().is_err());
+        assert!(matches!(service.hard_delete_video(video.id).await, Err(AppError::NotFound(_))));
+    }
+
+    #[ntex::test]
+    async fn test_find_by_id_with_deleted_honours_flag() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        setup_database(&db).await;
+        let repo = VideoRepository::new(db);
+        let service = VideoService::new(repo.clone());
+        let video = service.create_video(CreateVideoRequest {
+            title: "Deleted".to_string(),
+            youtube_id: "dQw4w9WgXcQ".to_string(),
+            description: None,
+            status: None,
+            tags: Vec::new(),
+        }).await.unwrap();
+        service.delete_video(video.id).await.unwrap();
+
+        assert!(repo.find_by_id_with_deleted(video.id, false).await.unwrap().is_none());
+        let found = repo.find_by_id_with_deleted(video.id, true).await.unwrap().unwrap();
+        assert_eq!(found.id, video.id);
+        assert!(found.deleted_at.is_some());
+    }
 }
@@ -2,20 +2,20 @@ use ntex::http::StatusCode;
 use ntex::web::test::TestRequest;
 
 mod common;
-use common::{assert_body, assert_header, assert_status};
+use common::{assert_body, assert_header, assert_status, init_test_service};
 
 mod video_tests {
     use super::*;
 
     #[ntex::test]
     async fn test_videos_endpoint_returns_200() {
-        assert_status(TestRequest::get().uri("/videos"), StatusCode::OK).await;
+        assert_status(TestRequest::get().uri("/api/v1/videos"), StatusCode::OK).await;
     }
 
     #[ntex::test]
     async fn test_videos_endpoint_returns_json() {
         assert_header(
-            TestRequest::get().uri("/videos"),
+            TestRequest::get().uri("/api/v1/videos"),
             "content-type",
             "application/json",
         )
@@ -25,8 +25,443 @@ mod video_tests {
     #[ntex::test]
     async fn test_empty_videos_returns_empty_array() {
         assert_body(
-            TestRequest::get().uri("/videos"),
+            TestRequest::get().uri("/api/v1/videos"),
             b"{\"videos\":[],\"total\":0,\"page\":1,\"per_page\":10,\"total_pages\":0}"
         ).await;
     }
 }
+
+mod compression_tests {
+    use super::*;
+    use common::setup_database;
+    use ntex::web::test;
+    use ntex_api::db;
+
+    async fn content_encoding(videos: usize) -> Option<String> {
+        let db = setup_database().await;
+        for i in 0..videos {
+            db::create_video(&db, format!("Video number {}", i), format!("yt{:09}", i))
+                .await
+                .unwrap();
+        }
+        let app = init_test_service(db).await;
+        let req = TestRequest::get()
+            .uri("/api/v1/videos?per_page=100")
+            .header("accept-encoding", "gzip")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        resp.headers()
+            .get("content-encoding")
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    #[ntex::test]
+    async fn test_large_list_is_gzipped() {
+        assert_eq!(content_encoding(30).await.as_deref(), Some("gzip"));
+    }
+
+    #[ntex::test]
+    async fn test_small_list_is_not_compressed() {
+        assert_eq!(content_encoding(0).await, None);
+    }
+}
+
+mod health_tests {
+    use super::*;
+    use ntex::web::test;
+    use ntex_api::api::HealthResponse;
+    use sea_orm::{Database, DatabaseConnection};
+
+    async fn status_of(db: DatabaseConnection, uri: &str) -> (StatusCode, String) {
+        let app = init_test_service(db).await;
+        let resp = test::call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+        let status = resp.status();
+        let body: HealthResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        (status, body.status)
+    }
+
+    #[ntex::test]
+    async fn test_health_is_ok_before_schema_exists() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        assert_eq!(status_of(db.clone(), "/health").await, (StatusCode::OK, "ok".to_string()));
+        assert_eq!(status_of(db, "/health/db").await, (StatusCode::OK, "ok".to_string()));
+    }
+
+    #[ntex::test]
+    async fn test_health_db_is_503_when_database_is_unreachable() {
+        let db = DatabaseConnection::Disconnected;
+        assert_eq!(status_of(db.clone(), "/health").await, (StatusCode::OK, "ok".to_string()));
+        assert_eq!(
+            status_of(db, "/health/db").await,
+            (StatusCode::SERVICE_UNAVAILABLE, "db_unavailable".to_string())
+        );
+    }
+}
+
+mod openapi_tests {
+    use super::*;
+    use common::setup_database;
+    use ntex::web::test;
+
+    #[ntex::test]
+    async fn test_openapi_document_lists_video_routes() {
+        let app = init_test_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/openapi.json").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let doc: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(doc["openapi"].as_str().unwrap().starts_with("3.0"));
+        assert!(doc["paths"]["/api/v1/videos"]["post"].is_object());
+        assert!(doc["paths"]["/api/v1/videos/{id}"]["patch"].is_object());
+        assert!(doc["components"]["schemas"]["VideoResponse"].is_object());
+    }
+}
+
+mod metrics_tests {
+    use super::*;
+    use common::setup_database;
+    use ntex::web::test;
+
+    #[ntex::test]
+    async fn test_metrics_endpoint_reports_request_counts() {
+        let app = init_test_service(setup_database().await).await;
+        for _ in 0..2 {
+            let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/metrics").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        assert!(body.contains("http_requests_total"), "{}", body);
+        assert!(
+            body.contains(r#"http_requests_total{method="GET",route="/api/v1/videos",status="200"} 2"#),
+            "{}",
+            body
+        );
+    }
+}
+
+mod payload_limit_tests {
+    use super::*;
+    use common::{init_test_service_with_config, setup_database};
+    use ntex::web::test;
+    use ntex_api::config::Config;
+
+    #[ntex::test]
+    async fn test_oversized_json_body_is_413() {
+        let config = Config { max_json_bytes: 256, ..Config::default() };
+        let app = init_test_service_with_config(setup_database().await, config).await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": "Big", "youtube_id": "dQw4w9WgXcQ", "description": "x".repeat(1024) }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body, serde_json::json!({ "error": "payload too large", "code": "payload_too_large" }));
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": "Small", "youtube_id": "dQw4w9WgXcQ" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::CREATED);
+    }
+}
+
+mod db_tests {
+    use super::*;
+    use common::setup_database;
+    use ntex_api::db;
+
+    #[ntex::test]
+    async fn test_create_video_sets_both_timestamps() {
+        let db = setup_database().await;
+        let before = chrono::Utc::now();
+        let video = db::create_video(&db, "Raw".to_string(), "dQw4w9WgXcQ".to_string())
+            .await
+            .unwrap();
+        let after = chrono::Utc::now();
+
+        assert_eq!(video.created_at, video.updated_at);
+        assert!(before <= video.created_at && video.created_at <= after);
+    }
+}
+
+mod init_db_tests {
+    use super::*;
+    use ntex::web::test;
+    use ntex_api::api::{CreateVideoRequest, VideoResponse};
+    use ntex_api::config::Config;
+    use ntex_api::db;
+    use ntex_api::entity::video;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    #[ntex::test]
+    async fn test_init_db_schema_matches_entity() {
+        let config = Config {
+            database_url: "sqlite::memory:".to_string(),
+            ..Config::default()
+        };
+        let db = db::init_db(&config).await;
+
+        let now = chrono::Utc::now();
+        let inserted = video::ActiveModel {
+            title: Set("Migrated".to_string()),
+            youtube_id: Set("dQw4w9WgXcQ".to_string()),
+            updated_at: Set(now),
+            deleted_at: Set(Some(now)),
+            ..Default::default()
+        }
+        .insert(&db)
+        .await
+        .unwrap();
+
+        let found = video::Entity::find_by_id(inserted.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(found.deleted_at, inserted.deleted_at);
+        assert_eq!(found.updated_at, inserted.updated_at);
+    }
+
+    #[ntex::test]
+    async fn test_create_video_on_migrated_database() {
+        let config = Config {
+            database_url: "sqlite::memory:".to_string(),
+            ..Config::default()
+        };
+        let app = init_test_service(db::init_db(&config).await).await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&CreateVideoRequest {
+                title: "Test Video".to_string(),
+                youtube_id: "dQw4w9WgXcQ".to_string(),
+                description: None,
+                status: None,
+                tags: Vec::new(),
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let video: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video.title, "Test Video");
+        assert_eq!(video.youtube_id, "dQw4w9WgXcQ");
+        assert!(video.id > 0);
+        assert!(video.created_at <= chrono::Utc::now());
+        assert!(video.updated_at <= chrono::Utc::now());
+        assert!(video.deleted_at.is_none());
+    }
+
+    #[ntex::test]
+    async fn test_update_and_delete_on_migrated_database() {
+        let config = Config {
+            database_url: "sqlite::memory:".to_string(),
+            ..Config::default()
+        };
+        let app = init_test_service(db::init_db(&config).await).await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": "Drift", "youtube_id": "dQw4w9WgXcQ" }))
+            .to_request();
+        let created: VideoResponse = serde_json::from_slice(&test::read_body(test::call_service(&app, req).await).await).unwrap();
+        let uri = format!("/api/v1/videos/{}", created.id);
+
+        let req = TestRequest::patch()
+            .uri(&uri)
+            .header(ntex::http::header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Drift, revised" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let updated: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(updated.updated_at >= created.updated_at);
+
+        let req = TestRequest::delete().uri(&format!("{}?return=true", uri)).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let deleted: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(deleted.deleted_at.is_some());
+    }
+}
+
+/// Runs against `TEST_DATABASE_URL`, e.g. a scratch Postgres database, and
+/// falls back to in-memory SQLite when it's unset
+mod backend_tests {
+    use super::common::init_controller_service;
+    use ntex::http::{header, StatusCode};
+    use ntex::web::test::{self, TestRequest};
+    use ntex_api::config::Config;
+    use ntex_api::db;
+    use ntex_api::entity::{tag, video, video_tag};
+    use sea_orm::EntityTrait;
+
+    async fn titles(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        query: &str,
+    ) -> Vec<String> {
+        let resp = test::call_service(app, TestRequest::get().uri(&format!("/api/v1/videos?{}", query)).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK, "{}", query);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        body["videos"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|video| video["title"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[ntex::test]
+    async fn test_search_ordering_and_updates_work_on_the_configured_backend() {
+        let config = Config {
+            database_url: std::env::var("TEST_DATABASE_URL")
+                .ok()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| "sqlite::memory:".to_string()),
+            // Every pooled in-memory SQLite connection would get its own database
+            db_max_connections: 1,
+            ..Config::default()
+        };
+        let db = db::init_db(&config).await;
+        video_tag::Entity::delete_many().exec(&db).await.unwrap();
+        tag::Entity::delete_many().exec(&db).await.unwrap();
+        video::Entity::delete_many().exec(&db).await.unwrap();
+        let app = init_controller_service(db).await;
+
+        let mut ids = Vec::new();
+        for (title, youtube_id) in [("Rust basics", "bkd00000001"), ("Advanced Rust", "bkd00000002"), ("Python intro", "bkd00000003")] {
+            let req = TestRequest::post()
+                .uri("/api/v1/videos")
+                .set_json(&serde_json::json!({ "title": title, "youtube_id": youtube_id, "tags": ["backend"] }))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+            let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            ids.push(body["id"].clone());
+        }
+
+        assert_eq!(titles(&app, "search=Rust&order_by=title&order_direction=asc").await, vec!["Advanced Rust", "Rust basics"]);
+        assert_eq!(titles(&app, "search=rust&case_insensitive=true&order_by=title&order_direction=asc").await, vec!["Advanced Rust", "Rust basics"]);
+        assert_eq!(titles(&app, "search=Rust&whole_word=true&order_by=-title").await, vec!["Rust basics", "Advanced Rust"]);
+        assert_eq!(titles(&app, "search=Py&search_mode=prefix").await, vec!["Python intro"]);
+        assert_eq!(titles(&app, "order_by=-created_at").await, vec!["Python intro", "Advanced Rust", "Rust basics"]);
+        assert_eq!(titles(&app, "tag=backend&order_by=title&order_direction=asc&per_page=2").await, vec!["Advanced Rust", "Python intro"]);
+
+        let uri = format!("/api/v1/videos/{}", ids[0]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+        let req = TestRequest::patch()
+            .uri(&uri)
+            .header(header::IF_MATCH, etag.as_str())
+            .set_json(&serde_json::json!({ "title": "Rust basics, revised" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let req = TestRequest::delete().uri(&format!("{}?return=true", uri)).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["title"], "Rust basics, revised");
+        assert!(body["deleted_at"].is_string());
+    }
+
+    async fn sorted_titles(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        query: &str,
+    ) -> Vec<String> {
+        let mut titles = titles(app, query).await;
+        titles.sort();
+        titles
+    }
+
+    #[ntex::test]
+    async fn test_full_text_search_stems_words_on_the_configured_backend() {
+        let config = Config {
+            database_url: std::env::var("TEST_DATABASE_URL")
+                .ok()
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| "sqlite::memory:".to_string()),
+            db_max_connections: 1,
+            ..Config::default()
+        };
+        let db = db::init_db(&config).await;
+        video_tag::Entity::delete_many().exec(&db).await.unwrap();
+        tag::Entity::delete_many().exec(&db).await.unwrap();
+        video::Entity::delete_many().exec(&db).await.unwrap();
+        let app = init_controller_service(db).await;
+
+        let mut ids = Vec::new();
+        for (title, description, youtube_id) in [
+            ("Running with scissors", "A cautionary tale", "fts00000001"),
+            ("Scissors for beginners", "How to run safely", "fts00000002"),
+            ("Cooking pasta", "No scissors required", "fts00000003"),
+        ] {
+            let req = TestRequest::post()
+                .uri("/api/v1/videos")
+                .set_json(&serde_json::json!({ "title": title, "description": description, "youtube_id": youtube_id }))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+            let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            ids.push(body["id"].clone());
+        }
+
+        // LIKE looks for the literal phrase; full-text matches each stemmed word
+        assert!(titles(&app, "search=run%20scissors").await.is_empty());
+        assert_eq!(
+            sorted_titles(&app, "search=run%20scissors&fts=true").await,
+            vec!["Running with scissors", "Scissors for beginners"]
+        );
+
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", ids[2]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Running late for pasta" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+        let req = TestRequest::delete().uri(&format!("/api/v1/videos/{}", ids[1])).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        assert_eq!(
+            sorted_titles(&app, "search=run%20scissors&fts=true").await,
+            vec!["Running late for pasta", "Running with scissors"]
+        );
+    }
+}
+
+mod index_tests {
+    use super::*;
+    use common::setup_database;
+    use ntex::http::header;
+    use ntex::web::test;
+    use ntex_api::api::IndexResponse;
+
+    #[ntex::test]
+    async fn test_index_greets_plain_text_clients() {
+        let app = init_test_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/").header(header::ACCEPT, "text/plain").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "text/plain");
+        assert_eq!(test::read_body(resp).await, "Welcome to Video API!");
+    }
+
+    #[ntex::test]
+    async fn test_index_describes_routes_to_json_clients() {
+        let app = init_test_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/").header(header::ACCEPT, "application/json").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "application/json");
+
+        let index: IndexResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(index.name, "Video API");
+        assert_eq!(index.version, "1");
+        assert!(index.endpoints.contains(&"GET /health".to_string()), "{:?}", index.endpoints);
+        assert!(index.endpoints.contains(&"POST /api/v1/videos".to_string()), "{:?}", index.endpoints);
+        assert!(index.endpoints.contains(&"PATCH /api/v1/videos/{id}".to_string()), "{:?}", index.endpoints);
+    }
+}
@@ -1,18 +1,62 @@
+#![allow(dead_code)]
+
 use ntex::http::{Request, StatusCode};
 use ntex::util::Bytes;
 use ntex::web::{test, test::TestRequest, Error, WebResponse};
 use ntex::{web, Pipeline, Service};
 use ntex_api::app::config_app;
-use ntex_api::entity::video::Entity;
+use ntex_api::config::Config;
+use ntex_api::controllers::system_controller;
+use ntex_api::controllers::video_controller::{self, VideoController};
+use ntex_api::entity::{tag, video, video_tag};
+use ntex_api::middleware::compression::Compression;
+use ntex_api::middleware::metrics::{Metrics, RecordMetrics};
+use ntex_api::repositories::video_repository::VideoRepository;
+use ntex_api::services::video_service::VideoService;
 use sea_orm::{Database, DatabaseConnection, Schema, ConnectionTrait};
 
 pub async fn init_test_service(
     db: DatabaseConnection,
 ) -> Pipeline<impl Service<Request, Response = WebResponse, Error = Error> + Sized> {
+    init_test_service_with_config(db, Config::default()).await
+}
+
+// 辅助函数：使用指定配置初始化完整应用（config_app）
+pub async fn init_test_service_with_config(
+    db: DatabaseConnection,
+    config: Config,
+) -> Pipeline<impl Service<Request, Response = WebResponse, Error = Error> + Sized> {
+    let controller = VideoController::new(VideoService::with_config(VideoRepository::new(db.clone()), config.clone()));
+    let metrics = Metrics::new();
     test::init_service(
         web::App::new()
+            .wrap(Compression::default())
+            .wrap(RecordMetrics::new(metrics.clone()))
             .state(db)
-            .configure(config_app)
+            .state(metrics)
+            .configure(move |cfg| config_app(cfg, controller.clone(), &config))
+    ).await
+}
+
+// 辅助函数：初始化基于 VideoController 的 /api/v1/videos 服务
+pub async fn init_controller_service(
+    db: DatabaseConnection,
+) -> Pipeline<impl Service<Request, Response = WebResponse, Error = Error> + Sized> {
+    init_controller_service_with_config(db, Config::default()).await
+}
+
+// 辅助函数：使用指定配置初始化 VideoController 服务
+pub async fn init_controller_service_with_config(
+    db: DatabaseConnection,
+    config: Config,
+) -> Pipeline<impl Service<Request, Response = WebResponse, Error = Error> + Sized> {
+    let base_path = config.api_base_path.clone();
+    let service = VideoService::with_config(VideoRepository::new(db), config);
+    let controller = VideoController::new(service);
+    test::init_service(
+        web::App::new()
+            .configure(|cfg| system_controller::config(cfg, &base_path))
+            .configure(move |cfg| video_controller::config(cfg, controller.clone(), &base_path))
     ).await
 }
 
@@ -54,11 +98,17 @@ pub async fn setup_database() -> DatabaseConnection {
     // Initialize the database schema
     let backend = db.get_database_backend();
     let schema = Schema::new(backend);
-    let mut table = schema.create_table_from_entity(Entity);
-    let stmt = table.if_not_exists();
-    db.execute(backend.build(stmt))
-        .await
-        .expect("Failed to create table");
+    let tables = [
+        schema.create_table_from_entity(video::Entity),
+        schema.create_table_from_entity(tag::Entity),
+        schema.create_table_from_entity(video_tag::Entity),
+    ];
+    for mut table in tables {
+        let stmt = table.if_not_exists();
+        db.execute(backend.build(stmt))
+            .await
+            .expect("Failed to create table");
+    }
 
     db
 }
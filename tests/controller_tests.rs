@@ -0,0 +1,3259 @@
+use ntex::http::{header, StatusCode};
+use ntex::web::test::{self, TestRequest};
+use ntex_api::api::{PaginatedVideoResponse, VideoResponse};
+
+mod common;
+use common::{init_controller_service, init_controller_service_with_config, setup_database};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Returns a distinct, well-formed 11 character YouTube ID for each call
+fn next_youtube_id() -> String {
+    static NEXT: AtomicU32 = AtomicU32::new(0);
+    format!("yt{:09}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+async fn create_test_video(
+    app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+) -> serde_json::Value {
+    let req = TestRequest::post()
+        .uri("/api/v1/videos")
+        .set_json(&serde_json::json!({
+            "title": "Test Video",
+            "youtube_id": next_youtube_id(),
+        }))
+        .to_request();
+    let resp = test::call_service(app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    serde_json::from_slice(&test::read_body(resp).await).unwrap()
+}
+
+mod negotiation_tests {
+    use super::*;
+
+    async fn create_video(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+    ) -> VideoResponse {
+        serde_json::from_value(create_test_video(app).await).unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_get_video_as_msgpack() {
+        let app = init_controller_service(setup_database().await).await;
+        let created = create_video(&app).await;
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/videos/{}", created.id))
+            .header("accept", "application/msgpack")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap().to_str().unwrap(),
+            "application/msgpack"
+        );
+
+        let body = test::read_body(resp).await;
+        let video: VideoResponse = rmp_serde::from_slice(&body).unwrap();
+        assert_eq!(video.id, created.id);
+        assert_eq!(video.title, "Test Video");
+        assert_eq!(video.youtube_id, created.youtube_id);
+    }
+
+    #[ntex::test]
+    async fn test_list_videos_as_msgpack() {
+        let app = init_controller_service(setup_database().await).await;
+        let created = create_video(&app).await;
+
+        let req = TestRequest::get()
+            .uri("/api/v1/videos")
+            .header("accept", "application/msgpack")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = test::read_body(resp).await;
+        let page: PaginatedVideoResponse = rmp_serde::from_slice(&body).unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.videos[0].youtube_id, created.youtube_id);
+    }
+
+    #[ntex::test]
+    async fn test_get_video_defaults_to_json() {
+        let app = init_controller_service(setup_database().await).await;
+        let created = create_video(&app).await;
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/videos/{}", created.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get("content-type").unwrap().to_str().unwrap(),
+            "application/json"
+        );
+    }
+}
+
+mod hashid_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    fn hashid_config() -> Config {
+        Config {
+            hashid_salt: Some("integration salt".to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[ntex::test]
+    async fn test_hashid_mode_encodes_and_resolves_ids() {
+        let app = init_controller_service_with_config(setup_database().await, hashid_config()).await;
+        let created = create_test_video(&app).await;
+        let hashid = created["id"].as_str().expect("id should be a hashid string");
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/videos/{}", hashid))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let fetched: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(fetched["id"], hashid);
+        assert_eq!(fetched["youtube_id"], created["youtube_id"]);
+    }
+
+    #[ntex::test]
+    async fn test_invalid_hashid_returns_400() {
+        let app = init_controller_service_with_config(setup_database().await, hashid_config()).await;
+        create_test_video(&app).await;
+
+        let req = TestRequest::get()
+            .uri("/api/v1/videos/!!invalid!!")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[ntex::test]
+    async fn test_raw_mode_keeps_integer_ids() {
+        let app = init_controller_service(setup_database().await).await;
+        let created = create_test_video(&app).await;
+        assert!(created["id"].is_i64());
+    }
+}
+
+mod fetch_tests {
+    use super::*;
+    use ntex_api::config::Config;
+    use ntex_api::controllers::id_codec::IdCodec;
+
+    #[ntex::test]
+    async fn test_fetch_reports_found_and_missing() {
+        let app = init_controller_service(setup_database().await).await;
+        let created = create_test_video(&app).await;
+        let id = created["id"].as_i64().unwrap();
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/fetch")
+            .set_json(&serde_json::json!({ "ids": [id, 4242] }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["found"][0]["id"], id);
+        assert_eq!(body["missing"], serde_json::json!([4242]));
+    }
+
+    #[ntex::test]
+    async fn test_fetch_takes_and_reports_hashids() {
+        let config = Config { hashid_salt: Some("integration salt".to_string()), ..Config::default() };
+        let codec = IdCodec::from_config(&config);
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let created = create_test_video(&app).await;
+        let missing = codec.encode(4242);
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/fetch")
+            .set_json(&serde_json::json!({ "ids": [created["id"], missing] }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["found"][0]["id"], created["id"]);
+        assert_eq!(body["missing"], serde_json::json!([missing]));
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/fetch")
+            .set_json(&serde_json::json!({ "ids": [1] }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod options_tests {
+    use super::*;
+
+    async fn allow_header(uri: &str) -> String {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::with_uri(uri)
+            .method(ntex::http::Method::OPTIONS)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        resp.headers().get("allow").unwrap().to_str().unwrap().to_string()
+    }
+
+    #[ntex::test]
+    async fn test_options_on_collection() {
+        assert_eq!(allow_header("/api/v1/videos").await, "GET, POST, OPTIONS");
+    }
+
+    #[ntex::test]
+    async fn test_options_on_item() {
+        assert_eq!(allow_header("/api/v1/videos/1").await, "GET, PUT, PATCH, DELETE, OPTIONS");
+    }
+}
+
+mod dry_run_tests {
+    use super::*;
+
+    async fn total(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+    ) -> u64 {
+        let resp = test::call_service(app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        page.total
+    }
+
+    #[ntex::test]
+    async fn test_dry_run_create_does_not_persist() {
+        let app = init_controller_service(setup_database().await).await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .header("x-dry-run", "true")
+            .set_json(&serde_json::json!({
+                "title": "Dry Run",
+                "youtube_id": "dQw4w9WgXcQ",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        assert_eq!(resp.headers().get("x-dry-run").unwrap(), "applied");
+
+        let video: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video.title, "Dry Run");
+        assert_eq!(video.youtube_id, "dQw4w9WgXcQ");
+        assert!(video.id > 0);
+
+        assert_eq!(total(&app).await, 0);
+    }
+
+    #[ntex::test]
+    async fn test_dry_run_update_and_delete_roll_back() {
+        let app = init_controller_service(setup_database().await).await;
+        let created = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", created["id"]);
+
+        let req = TestRequest::patch()
+            .uri(&uri)
+            .header(header::IF_MATCH, "*")
+            .header("x-dry-run", "true")
+            .set_json(&serde_json::json!({ "title": "Renamed" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("x-dry-run").unwrap(), "applied");
+        let video: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video.title, "Renamed");
+
+        let req = TestRequest::delete()
+            .uri(&uri)
+            .header("x-dry-run", "true")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(resp.headers().get("x-dry-run").unwrap(), "applied");
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let video: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video.title, "Test Video");
+    }
+
+    #[ntex::test]
+    async fn test_regular_create_has_no_dry_run_header() {
+        let app = init_controller_service(setup_database().await).await;
+        create_test_video(&app).await;
+        assert_eq!(total(&app).await, 1);
+    }
+}
+
+mod meta_tests {
+    use super::*;
+    use ntex_api::api::MetaResponse;
+    use ntex_api::config::Config;
+
+    #[ntex::test]
+    async fn test_meta_advertises_configured_per_page_clamp() {
+        let config = Config {
+            max_per_page: 5,
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        for i in 0..7 {
+            let req = TestRequest::post()
+                .uri("/api/v1/videos")
+                .set_json(&serde_json::json!({
+                    "title": format!("Video {}", i),
+                    "youtube_id": format!("dQw4w9WgXc{}", i),
+                }))
+                .to_request();
+            test::call_service(&app, req).await;
+        }
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/meta").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let meta: MetaResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(meta.pagination.per_page.max, 5);
+        assert_eq!(meta.pagination.per_page.min, 1);
+
+        let req = TestRequest::get().uri("/api/v1/videos?per_page=50").to_request();
+        let resp = test::call_service(&app, req).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.per_page, meta.pagination.per_page.max);
+        assert_eq!(page.videos.len() as u64, meta.pagination.per_page.max);
+        assert_eq!(page.total_pages, 2);
+    }
+}
+
+mod period_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_period_filter_accepts_known_buckets() {
+        let app = init_controller_service(setup_database().await).await;
+        create_test_video(&app).await;
+
+        for period in ["today", "week", "month", "year"] {
+            let req = TestRequest::get()
+                .uri(&format!("/api/v1/videos?period={}", period))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            assert_eq!(page.total, 1);
+        }
+    }
+
+    #[ntex::test]
+    async fn test_period_with_created_after_returns_400() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get()
+            .uri("/api/v1/videos?period=week&created_after=2024-01-01T00:00:00Z")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod version_tests {
+    use super::*;
+    use ntex_api::api::VersionResponse;
+
+    #[ntex::test]
+    async fn test_version_matches_crate_version() {
+        let app = init_controller_service(setup_database().await).await;
+        let resp = test::call_service(&app, TestRequest::get().uri("/version").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let version: VersionResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        assert!(!version.git_sha.is_empty());
+    }
+}
+
+mod quota_tests {
+    use super::*;
+    use ntex::web;
+    use ntex_api::config::Config;
+    use ntex_api::controllers::video_controller::{self, VideoController};
+    use ntex_api::repositories::video_repository::VideoRepository;
+    use ntex_api::services::video_service::VideoService;
+
+    /// Serves the API over a real socket, so the quota sees a peer address (`127.0.0.1`)
+    async fn quota_server(trusted_proxies: &[&str]) -> test::TestServer {
+        let config = Config {
+            daily_create_quota: Some(2),
+            trusted_proxies: trusted_proxies.iter().map(|ip| ip.parse().unwrap()).collect(),
+            ..Config::default()
+        };
+        let service = VideoService::with_config(VideoRepository::new(setup_database().await), config);
+        let controller = VideoController::new(service);
+        test::server(move || {
+            let controller = controller.clone();
+            web::App::new().configure(move |cfg| video_controller::config(cfg, controller.clone(), "/api/v1"))
+        })
+    }
+
+    async fn create_from(srv: &test::TestServer, forwarded_for: &str, youtube_id: &str) -> ntex::http::client::ClientResponse {
+        srv.post("/api/v1/videos")
+            .header("x-forwarded-for", forwarded_for)
+            .send_json(&serde_json::json!({
+                "title": "Quota Video",
+                "youtube_id": youtube_id,
+            }))
+            .await
+            .unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_daily_quota_is_per_ip() {
+        let srv = quota_server(&["127.0.0.1"]).await;
+
+        for youtube_id in ["dQw4w9WgXc0", "dQw4w9WgXc1"] {
+            let resp = create_from(&srv, "10.0.0.1", youtube_id).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+        }
+
+        let mut resp = create_from(&srv, "10.0.0.1", "dQw4w9WgXc2").await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(resp.headers().contains_key("retry-after"));
+        let body: serde_json::Value = serde_json::from_slice(&resp.body().await.unwrap()).unwrap();
+        assert!(body["reset_at"].is_string());
+
+        let resp = create_from(&srv, "10.0.0.2", "dQw4w9WgXc3").await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+
+    #[ntex::test]
+    async fn test_spoofed_forwarded_header_does_not_reset_quota() {
+        let srv = quota_server(&[]).await;
+
+        for (spoofed, youtube_id) in [("1.1.1.1", "dQw4w9WgXc0"), ("2.2.2.2", "dQw4w9WgXc1")] {
+            let resp = create_from(&srv, spoofed, youtube_id).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+        }
+
+        let resp = create_from(&srv, "3.3.3.3", "dQw4w9WgXc2").await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[ntex::test]
+    async fn test_trusted_proxy_ignores_client_written_hops() {
+        let srv = quota_server(&["127.0.0.1"]).await;
+
+        for youtube_id in ["dQw4w9WgXc0", "dQw4w9WgXc1"] {
+            let resp = create_from(&srv, "203.0.113.7", youtube_id).await;
+            assert_eq!(resp.status(), StatusCode::CREATED);
+        }
+
+        // Only the hop the proxy appended counts; the client wrote the one before it
+        let resp = create_from(&srv, "198.51.100.1, 203.0.113.7", "dQw4w9WgXc2").await;
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[ntex::test]
+    async fn test_only_committed_creates_use_the_quota() {
+        let srv = quota_server(&[]).await;
+        let video = |youtube_id: &str| serde_json::json!({ "title": "Quota Video", "youtube_id": youtube_id });
+
+        let resp = srv.post("/api/v1/videos").header("X-Dry-Run", "true").send_json(&video("dQw4w9WgXc0")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let resp = srv.post("/api/v1/videos").send_json(&video("too-short")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let invalid_batch = serde_json::json!([video("dQw4w9WgXc1"), video("too-short")]);
+        let resp = srv.post("/api/v1/videos/batch").send_json(&invalid_batch).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let resp = srv
+            .post("/api/v1/videos/batch")
+            .header("X-Dry-Run", "true")
+            .send_json(&serde_json::json!([video("dQw4w9WgXc1"), video("dQw4w9WgXc2")]))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let resp = srv.post("/api/v1/videos").send_json(&video("dQw4w9WgXc0")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let resp = srv.post("/api/v1/videos").send_json(&video("dQw4w9WgXc0")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let resp = srv.post("/api/v1/videos").send_json(&video("dQw4w9WgXc1")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let resp = srv.post("/api/v1/videos").send_json(&video("dQw4w9WgXc2")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+}
+
+mod reindex_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::api::{ReindexDbResponse, ReindexResponse};
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    fn admin_config() -> Config {
+        Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            ..Config::default()
+        }
+    }
+
+    fn admin_post(uri: &str) -> TestRequest {
+        TestRequest::post()
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+    }
+
+    #[ntex::test]
+    async fn test_reindex_regenerates_stale_title_keys() {
+        use ntex_api::entity::video;
+        use sea_orm::{sea_query::Expr, EntityTrait};
+
+        let db = setup_database().await;
+        let app = init_controller_service_with_config(db.clone(), admin_config()).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "  Untidy    Title ",
+                "youtube_id": "dQw4w9WgXcQ",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let created: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        video::Entity::update_many()
+            .col_expr(video::Column::TitleNormalized, Expr::value("stale"))
+            .exec(&db)
+            .await
+            .unwrap();
+
+        let resp = test::call_service(&app, admin_post("/api/v1/admin/reindex").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let result: ReindexResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(result.processed, 1);
+        assert_eq!(result.updated, 1);
+        assert_eq!(result.next_cursor, None);
+
+        let id = created["id"].as_i64().unwrap() as i32;
+        let stored = video::Entity::find_by_id(id).one(&db).await.unwrap().unwrap();
+        assert_eq!(stored.title_normalized, "untidy    title");
+        assert_eq!(stored.title, "  Untidy    Title ");
+
+        let uri = format!("/api/v1/videos/{}", created["id"]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let video: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video["title"], created["title"]);
+        assert_eq!(video["updated_at"], created["updated_at"]);
+    }
+
+    #[ntex::test]
+    async fn test_reindex_db_reports_expected_indexes_on_fresh_db() {
+        let config = Config {
+            database_url: "sqlite::memory:".to_string(),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(ntex_api::db::init_db(&config).await, admin_config()).await;
+
+        let resp = test::call_service(&app, admin_post("/api/v1/admin/reindex-db").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let result: ReindexDbResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(result.created.is_empty());
+        assert_eq!(
+            result.present,
+            vec!["idx_videos_youtube_id", "idx_videos_title", "idx_videos_created_at_id"]
+        );
+        assert!(result.analyzed);
+    }
+
+    #[ntex::test]
+    async fn test_admin_endpoints_require_token() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let req = TestRequest::post().uri("/api/v1/admin/reindex-db").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let app = init_controller_service(setup_database().await).await;
+        let resp = test::call_service(&app, admin_post("/api/v1/admin/reindex-db").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
+
+mod strict_json_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    fn body_with_extra_field() -> serde_json::Value {
+        serde_json::json!({
+            "title": "Test Video",
+            "youtube_id": "dQw4w9WgXcQ",
+            "admin": true,
+        })
+    }
+
+    #[ntex::test]
+    async fn test_unknown_field_rejected_in_strict_mode() {
+        let config = Config {
+            strict_json: true,
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&body_with_extra_field())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["error"], "unknown field `admin`");
+    }
+
+    #[ntex::test]
+    async fn test_unknown_field_ignored_in_lenient_mode() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&body_with_extra_field())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+    }
+}
+
+mod etag_tests {
+    use super::*;
+    use ntex::http::header;
+
+    #[ntex::test]
+    async fn test_create_sets_location_and_etag_usable_for_conditional_get() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Test Video",
+                "youtube_id": "dQw4w9WgXcQ",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let location = resp.headers().get(header::LOCATION).unwrap().to_str().unwrap().to_string();
+        let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+        let created: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(location, format!("/api/v1/videos/{}", created["id"]));
+
+        let req = TestRequest::get()
+            .uri(&location)
+            .header(header::IF_NONE_MATCH, etag.as_str())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers().get(header::ETAG).unwrap().to_str().unwrap(), etag);
+
+        let req = TestRequest::get()
+            .uri(&location)
+            .header(header::IF_NONE_MATCH, "\"stale\"")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ntex::test]
+    async fn test_get_etag_yields_304_until_the_video_changes() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+
+        let req = TestRequest::get()
+            .uri(&uri)
+            .header(header::IF_NONE_MATCH, etag.as_str())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert!(test::read_body(resp).await.is_empty());
+
+        let req = TestRequest::patch()
+            .uri(&uri)
+            .header(header::IF_MATCH, etag.as_str())
+            .set_json(&serde_json::json!({ "title": "Changed" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let req = TestRequest::get()
+            .uri(&uri)
+            .header(header::IF_NONE_MATCH, etag.as_str())
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
+
+mod keyset_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_cursor_mode_returns_next_cursor() {
+        let app = init_controller_service(setup_database().await).await;
+        for _ in 0..3 {
+            create_test_video(&app).await;
+        }
+
+        let req = TestRequest::get().uri("/api/v1/videos?after=&per_page=2").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.videos.len(), 2);
+        let next = page.next_cursor.expect("next_cursor in cursor mode");
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/videos?after={}&per_page=2", next))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.videos.len(), 1);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[ntex::test]
+    async fn test_offset_mode_omits_cursor_fields() {
+        let app = init_controller_service(setup_database().await).await;
+        create_test_video(&app).await;
+
+        let req = TestRequest::get().uri("/api/v1/videos?page=1").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body.get("next_cursor").is_none());
+        assert!(body.get("prev_cursor").is_none());
+    }
+
+    #[ntex::test]
+    async fn test_mixing_page_and_after_returns_400() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos?page=2&after=").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[ntex::test]
+    async fn test_malformed_cursor_returns_400() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos?after=garbage").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod id_cursor_tests {
+    use super::*;
+    use ntex_api::api::CursorVideoResponse;
+    use ntex_api::config::Config;
+
+    #[ntex::test]
+    async fn test_walks_pages_in_id_order() {
+        let app = init_controller_service(setup_database().await).await;
+        let mut ids = Vec::new();
+        for _ in 0..5 {
+            ids.push(create_test_video(&app).await["id"].as_i64().unwrap() as i32);
+        }
+
+        let mut seen = Vec::new();
+        let mut uri = "/api/v1/videos?cursor=true&per_page=2".to_string();
+        loop {
+            let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            assert!(resp.headers().get("X-Total-Count").is_none());
+            let page: CursorVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            seen.extend(page.videos.iter().map(|video| video.id));
+            match page.next_cursor {
+                Some(next) => uri = format!("/api/v1/videos?cursor=true&per_page=2&after={}", next),
+                None => break,
+            }
+        }
+        assert_eq!(seen, ids);
+    }
+
+    #[ntex::test]
+    async fn test_rows_inserted_mid_walk_are_not_repeated() {
+        let app = init_controller_service(setup_database().await).await;
+        for _ in 0..3 {
+            create_test_video(&app).await;
+        }
+
+        let req = TestRequest::get().uri("/api/v1/videos?cursor=true&per_page=2").to_request();
+        let page: CursorVideoResponse =
+            serde_json::from_slice(&test::read_body(test::call_service(&app, req).await).await).unwrap();
+        let newest = create_test_video(&app).await["id"].as_i64().unwrap() as i32;
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/videos?cursor=true&per_page=2&after={}", page.next_cursor.unwrap()))
+            .to_request();
+        let next: CursorVideoResponse =
+            serde_json::from_slice(&test::read_body(test::call_service(&app, req).await).await).unwrap();
+        assert_eq!(next.videos.len(), 2);
+        assert_eq!(next.videos[1].id, newest);
+        assert!(next.videos.iter().all(|video| !page.videos.iter().any(|seen| seen.id == video.id)));
+    }
+
+    #[ntex::test]
+    async fn test_cursor_is_a_hashid_when_enabled() {
+        let config = Config { hashid_salt: Some("integration salt".to_string()), ..Config::default() };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        for _ in 0..3 {
+            create_test_video(&app).await;
+        }
+
+        let req = TestRequest::get().uri("/api/v1/videos?cursor=true&per_page=2").to_request();
+        let body: serde_json::Value =
+            serde_json::from_slice(&test::read_body(test::call_service(&app, req).await).await).unwrap();
+        let next = body["next_cursor"].as_str().expect("hashid cursor");
+        assert_eq!(body["videos"][1]["id"], body["next_cursor"]);
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/videos?cursor=true&per_page=2&after={}", next))
+            .to_request();
+        let body: serde_json::Value =
+            serde_json::from_slice(&test::read_body(test::call_service(&app, req).await).await).unwrap();
+        assert_eq!(body["videos"].as_array().unwrap().len(), 1);
+        assert!(body["next_cursor"].is_null());
+    }
+
+    #[ntex::test]
+    async fn test_cursor_with_page_or_order_by_returns_400() {
+        let app = init_controller_service(setup_database().await).await;
+        for uri in [
+            "/api/v1/videos?cursor=true&page=2",
+            "/api/v1/videos?cursor=true&order_by=title",
+            "/api/v1/videos?cursor=true&after=abc",
+        ] {
+            let resp = test::call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+}
+
+mod validation_echo_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    async fn create_invalid(config: Config) -> serde_json::Value {
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Test Video",
+                "youtube_id": "not-a-youtube-id-at-all",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        serde_json::from_slice(&test::read_body(resp).await).unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_received_value_absent_by_default() {
+        let body = create_invalid(Config::default()).await;
+        assert!(body.get("received").is_none());
+    }
+
+    #[ntex::test]
+    async fn test_field_messages_are_structured() {
+        let body = create_invalid(Config::default()).await;
+        assert_eq!(body["error"], "validation failed");
+        assert_eq!(body["fields"], serde_json::json!({ "youtube_id": ["length must be 11"] }));
+    }
+
+    #[ntex::test]
+    async fn test_received_value_echoed_when_enabled() {
+        let config = Config {
+            validation_echo_max_chars: Some(10),
+            ..Config::default()
+        };
+        let body = create_invalid(config).await;
+        assert_eq!(body["received"]["youtube_id"], "not-a-yout");
+    }
+}
+
+mod related_tests {
+    use super::*;
+
+    async fn create_titled(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        title: &str,
+    ) -> serde_json::Value {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": title, "youtube_id": next_youtube_id() }))
+            .to_request();
+        let resp = test::call_service(app, req).await;
+        serde_json::from_slice(&test::read_body(resp).await).unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_related_returns_overlapping_titles_excluding_source() {
+        let app = init_controller_service(setup_database().await).await;
+        let source = create_titled(&app, "Guitar lessons for beginners").await;
+        let related = create_titled(&app, "Advanced guitar lessons").await;
+        create_titled(&app, "Baking bread at home").await;
+
+        let uri = format!("/api/v1/videos/{}/related", source["id"]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        let ids: Vec<_> = body["videos"].as_array().unwrap().iter().map(|v| v["id"].clone()).collect();
+        assert_eq!(ids, vec![related["id"].clone()]);
+    }
+
+    #[ntex::test]
+    async fn test_related_without_overlap_is_empty() {
+        let app = init_controller_service(setup_database().await).await;
+        let source = create_titled(&app, "Baking bread at home").await;
+        create_titled(&app, "Guitar lessons").await;
+
+        let uri = format!("/api/v1/videos/{}/related", source["id"]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["videos"], serde_json::json!([]));
+    }
+}
+
+mod import_validate_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::api::ImportValidationResponse;
+
+    async fn validate(csv: &'static str) -> ImportValidationResponse {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/import/validate")
+            .header(header::CONTENT_TYPE, "text/csv")
+            .set_payload(csv)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        serde_json::from_slice(&test::read_body(resp).await).unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_clean_file_is_all_valid() {
+        let result = validate("title,youtube_id\nFirst,dQw4w9WgXcQ\nSecond,xQc9WgXw4Qd\n").await;
+        assert_eq!(result.valid_rows, 2);
+        assert!(result.errors.is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_in_file_duplicate_is_reported() {
+        let result = validate("title,youtube_id\nFirst,dQw4w9WgXcQ\nCopy,dQw4w9WgXcQ\n").await;
+        assert_eq!(result.valid_rows, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].line, 3);
+        assert!(result.errors[0].error.contains("duplicate youtube_id dQw4w9WgXcQ"));
+    }
+}
+
+mod json_ld_tests {
+    use super::*;
+    use ntex::http::header;
+
+    #[ntex::test]
+    async fn test_json_ld_describes_video_object() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let uri = format!("/api/v1/videos/{}/json-ld", video["id"]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "application/ld+json");
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["@type"], "VideoObject");
+        assert_eq!(body["name"], "Test Video");
+        assert_eq!(
+            body["embedUrl"],
+            format!("https://www.youtube.com/embed/{}", video["youtube_id"].as_str().unwrap())
+        );
+    }
+
+    #[ntex::test]
+    async fn test_json_ld_for_deleted_video_is_404() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        test::call_service(&app, TestRequest::delete().uri(&uri).to_request()).await;
+
+        let uri = format!("/api/v1/videos/{}/json-ld", video["id"]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+mod empty_list_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    fn no_content_config() -> Config {
+        Config {
+            empty_list_no_content: true,
+            ..Config::default()
+        }
+    }
+
+    #[ntex::test]
+    async fn test_empty_list_returns_204_when_enabled() {
+        let app = init_controller_service_with_config(setup_database().await, no_content_config()).await;
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(test::read_body(resp).await.is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_non_empty_list_returns_200_when_enabled() {
+        let app = init_controller_service_with_config(setup_database().await, no_content_config()).await;
+        create_test_video(&app).await;
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ntex::test]
+    async fn test_empty_list_returns_200_by_default() {
+        let app = init_controller_service(setup_database().await).await;
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
+
+mod path_error_tests {
+    use super::*;
+
+    async fn assert_invalid_id(uri: &str) {
+        let app = init_controller_service(setup_database().await).await;
+        let resp = test::call_service(&app, TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body, serde_json::json!({ "error": "invalid id", "code": "bad_request" }));
+    }
+
+    #[ntex::test]
+    async fn test_non_integer_id_returns_invalid_id() {
+        assert_invalid_id("/api/v1/videos/abc").await;
+    }
+
+    #[ntex::test]
+    async fn test_overflowing_id_returns_invalid_id() {
+        assert_invalid_id("/api/v1/videos/99999999999").await;
+        assert_invalid_id("/api/v1/videos/99999999999/related").await;
+    }
+}
+
+mod recent_ops_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    #[ntex::test]
+    async fn test_recent_ops_lists_writes_oldest_first() {
+        let config = Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            recent_ops_capacity: 2,
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::patch()
+            .uri(&uri)
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Renamed" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+        let req = TestRequest::delete().uri(&uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let req = TestRequest::get()
+            .uri("/api/v1/admin/recent-ops")
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        let ops = body["ops"].as_array().unwrap();
+        let kinds: Vec<&str> = ops.iter().map(|op| op["op"].as_str().unwrap()).collect();
+        assert_eq!(kinds, vec!["update", "delete"]);
+        assert!(ops.iter().all(|op| op["id"] == video["id"]));
+        assert!(ops[0]["at"].as_str().unwrap() <= ops[1]["at"].as_str().unwrap());
+    }
+
+    #[ntex::test]
+    async fn test_recent_ops_requires_admin_token() {
+        let config = Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+
+        let req = TestRequest::get().uri("/api/v1/admin/recent-ops").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+mod restore_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    fn admin_config() -> Config {
+        Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            ..Config::default()
+        }
+    }
+
+    fn restore(uri: &str) -> ntex::http::Request {
+        TestRequest::post()
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request()
+    }
+
+    #[ntex::test]
+    async fn test_restore_undoes_soft_delete() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::delete().uri(&uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let req = restore(&format!("{}/restore", uri));
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let restored: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(restored["id"], video["id"]);
+        assert!(restored["deleted_at"].is_null());
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ntex::test]
+    async fn test_restore_of_live_video_conflicts_and_missing_is_not_found() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+
+        let req = restore(&format!("/api/v1/videos/{}/restore", video["id"]));
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["code"], "conflict");
+
+        let req = restore("/api/v1/videos/999/restore");
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_trash_and_restore_require_admin_token() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        let req = TestRequest::delete().uri(&uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let req = TestRequest::get().uri("/api/v1/videos/trash").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+        let req = TestRequest::post().uri(&format!("{}/restore", uri)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let req = TestRequest::get()
+            .uri("/api/v1/videos/trash")
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body["videos"][0]["deleted_at"].is_string());
+    }
+
+    #[ntex::test]
+    async fn test_trash_is_forbidden_without_configured_token() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos/trash").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::FORBIDDEN);
+    }
+}
+
+mod per_page_warning_tests {
+    use super::*;
+    use ntex::http::header;
+
+    #[ntex::test]
+    async fn test_clamped_per_page_adds_warning() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos?per_page=500").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::WARNING).unwrap(),
+            "299 - \"per_page clamped to max 100\""
+        );
+    }
+
+    #[ntex::test]
+    async fn test_per_page_within_bounds_has_no_warning() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos?per_page=100").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().get(header::WARNING).is_none());
+    }
+}
+
+mod trash_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    fn admin_config() -> Config {
+        Config {
+            admin_token: Some("s3cret".to_string()),
+            ..Config::default()
+        }
+    }
+
+    fn trash() -> ntex::http::Request {
+        TestRequest::get()
+            .uri("/api/v1/videos/trash")
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .to_request()
+    }
+
+    #[ntex::test]
+    async fn test_trash_lists_only_deleted_videos() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let kept = create_test_video(&app).await;
+        let deleted = create_test_video(&app).await;
+        let req = TestRequest::delete()
+            .uri(&format!("/api/v1/videos/{}", deleted["id"]))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let resp = test::call_service(&app, trash()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.total, 1);
+        assert_eq!(body.page, 1);
+        assert_eq!(body.total_pages, 1);
+        assert_eq!(serde_json::json!(body.videos[0].id), deleted["id"]);
+        assert!(body.videos[0].deleted_at.is_some());
+        assert_ne!(kept["id"], deleted["id"]);
+    }
+
+    #[ntex::test]
+    async fn test_trash_is_empty_without_deletes() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        create_test_video(&app).await;
+
+        let resp = test::call_service(&app, trash()).await;
+        let body: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.total, 0);
+        assert!(body.videos.is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_trash_requires_admin_token() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/trash").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[ntex::test]
+    async fn test_trash_is_forbidden_without_configured_token() {
+        let app = init_controller_service(setup_database().await).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/trash").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
+
+mod public_fields_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    fn public_fields_config() -> Config {
+        Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            public_video_fields: Some(vec![
+                "title".to_string(),
+                "youtube_id".to_string(),
+                "created_at".to_string(),
+                "updated_at".to_string(),
+            ]),
+            ..Config::default()
+        }
+    }
+
+    #[ntex::test]
+    async fn test_public_scope_strips_deleted_at() {
+        let app = init_controller_service_with_config(setup_database().await, public_fields_config()).await;
+        let video = create_test_video(&app).await;
+        assert!(video.get("deleted_at").is_none());
+        assert_eq!(video["title"], "Test Video");
+
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body.get("deleted_at").is_none());
+        assert_eq!(body["id"], video["id"]);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body["videos"][0].get("deleted_at").is_none());
+        assert_eq!(body["total"], 1);
+    }
+
+    #[ntex::test]
+    async fn test_admin_scope_keeps_deleted_at() {
+        let app = init_controller_service_with_config(setup_database().await, public_fields_config()).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::get()
+            .uri(&format!("/api/v1/admin/videos/{}", video["id"]))
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body.get("deleted_at").is_some());
+        assert!(body["deleted_at"].is_null());
+    }
+}
+
+mod hard_delete_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    fn admin_config() -> Config {
+        Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            ..Config::default()
+        }
+    }
+
+    fn permanent_delete(uri: &str) -> ntex::http::Request {
+        TestRequest::delete()
+            .uri(&format!("{}?permanent=true", uri))
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request()
+    }
+
+    async fn trash_total(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+    ) -> u64 {
+        let req = TestRequest::get()
+            .uri("/api/v1/videos/trash")
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request();
+        let resp = test::call_service(app, req).await;
+        let body: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        body.total
+    }
+
+    #[ntex::test]
+    async fn test_permanent_delete_removes_live_row() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = permanent_delete(&uri);
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(trash_total(&app).await, 0);
+    }
+
+    #[ntex::test]
+    async fn test_permanent_delete_removes_soft_deleted_row() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::delete().uri(&uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+        assert_eq!(trash_total(&app).await, 1);
+
+        let req = permanent_delete(&uri);
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+        assert_eq!(trash_total(&app).await, 0);
+
+        let req = TestRequest::post()
+            .uri(&format!("{}/restore", uri))
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_delete_of_missing_row_returns_not_found_in_both_modes() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+
+        let req = permanent_delete("/api/v1/videos/999");
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+        let req = TestRequest::delete().uri("/api/v1/videos/999").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_permanent_false_keeps_soft_delete() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::delete()
+            .uri(&format!("/api/v1/videos/{}?permanent=false", video["id"]))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+        assert_eq!(trash_total(&app).await, 1);
+    }
+
+    #[ntex::test]
+    async fn test_permanent_delete_requires_admin_token() {
+        let app = init_controller_service_with_config(setup_database().await, admin_config()).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::delete().uri(&format!("{}?permanent=true", uri)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+        let req = TestRequest::delete()
+            .uri(&format!("{}?permanent=true", uri))
+            .header("X-Dry-Run", "true")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ntex::test]
+    async fn test_permanent_delete_is_forbidden_without_configured_token() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::delete().uri(&format!("{}?permanent=true", uri)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::FORBIDDEN);
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
+
+mod reset_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::api::ResetResponse;
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    fn reset_config(allow_data_reset: bool, app_env: &str) -> Config {
+        Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            allow_data_reset,
+            app_env: app_env.to_string(),
+            ..Config::default()
+        }
+    }
+
+    fn reset_request() -> ntex::http::Request {
+        TestRequest::post()
+            .uri("/api/v1/admin/reset")
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request()
+    }
+
+    #[ntex::test]
+    async fn test_reset_removes_all_rows() {
+        use ntex_api::entity::{tag, video_tag};
+        use sea_orm::{EntityTrait, PaginatorTrait};
+
+        let db = setup_database().await;
+        let app = init_controller_service_with_config(db.clone(), reset_config(true, "test")).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Tagged",
+                "youtube_id": next_youtube_id(),
+                "tags": ["music", "live"],
+            }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::CREATED);
+        let deleted = create_test_video(&app).await;
+        let req = TestRequest::delete()
+            .uri(&format!("/api/v1/videos/{}", deleted["id"]))
+            .to_request();
+        test::call_service(&app, req).await;
+
+        let resp = test::call_service(&app, reset_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let result: ResetResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(result.removed, 2);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 0);
+        assert_eq!(tag::Entity::find().count(&db).await.unwrap(), 0);
+        assert_eq!(video_tag::Entity::find().count(&db).await.unwrap(), 0);
+    }
+
+    #[ntex::test]
+    async fn test_reset_refused_when_disabled() {
+        let app = init_controller_service_with_config(setup_database().await, reset_config(false, "test")).await;
+        create_test_video(&app).await;
+
+        let resp = test::call_service(&app, reset_request()).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 1);
+    }
+
+    #[ntex::test]
+    async fn test_reset_refused_in_production_even_when_enabled() {
+        let app = init_controller_service_with_config(setup_database().await, reset_config(true, "production")).await;
+
+        let resp = test::call_service(&app, reset_request()).await;
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+}
+
+mod batch_create_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_batch_creates_all_videos() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/batch")
+            .set_json(&serde_json::json!([
+                { "title": "Part 1", "youtube_id": "dQw4w9WgXcQ" },
+                { "title": "Part 2", "youtube_id": "9bZkp7q19f0" },
+            ]))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let videos: Vec<VideoResponse> = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        let titles: Vec<&str> = videos.iter().map(|v| v.title.as_str()).collect();
+        assert_eq!(titles, vec!["Part 1", "Part 2"]);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 2);
+    }
+
+    #[ntex::test]
+    async fn test_batch_with_invalid_item_names_index_and_writes_nothing() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/batch")
+            .set_json(&serde_json::json!([
+                { "title": "Part 1", "youtube_id": "dQw4w9WgXcQ" },
+                { "title": "Part 2", "youtube_id": "short" },
+            ]))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body["error"].as_str().unwrap().starts_with("item 1:"));
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 0);
+    }
+
+    #[ntex::test]
+    async fn test_empty_batch_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/batch")
+            .set_json(&serde_json::json!([]))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod batch_delete_tests {
+    use super::*;
+    use ntex_api::api::BatchDeleteResponse;
+    use ntex_api::config::Config;
+    use ntex_api::controllers::id_codec::IdCodec;
+
+    #[ntex::test]
+    async fn test_batch_delete_reports_deleted_and_not_found() {
+        let config = Config {
+            admin_token: Some("s3cret".to_string()),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let first = create_test_video(&app).await;
+        let second = create_test_video(&app).await;
+        let first_id = first["id"].as_i64().unwrap() as i32;
+        let second_id = second["id"].as_i64().unwrap() as i32;
+
+        let req = TestRequest::delete()
+            .uri("/api/v1/videos/batch")
+            .set_json(&vec![second_id, 999, first_id, second_id])
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let result: BatchDeleteResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(result.deleted, vec![second_id, first_id]);
+        assert_eq!(result.not_found, vec![999]);
+
+        let req = TestRequest::get()
+            .uri("/api/v1/videos/trash")
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 2);
+    }
+
+    #[ntex::test]
+    async fn test_batch_delete_treats_already_deleted_as_not_found() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let id = video["id"].as_i64().unwrap() as i32;
+        let req = TestRequest::delete().uri(&format!("/api/v1/videos/{}", id)).to_request();
+        test::call_service(&app, req).await;
+
+        let req = TestRequest::delete()
+            .uri("/api/v1/videos/batch")
+            .set_json(&vec![id])
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let result: BatchDeleteResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(result.deleted.is_empty());
+        assert_eq!(result.not_found, vec![id]);
+    }
+
+    #[ntex::test]
+    async fn test_batch_delete_takes_and_reports_hashids() {
+        let config = Config { hashid_salt: Some("integration salt".to_string()), ..Config::default() };
+        let codec = IdCodec::from_config(&config);
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let video = create_test_video(&app).await;
+        let missing = codec.encode(999);
+
+        let req = TestRequest::delete()
+            .uri("/api/v1/videos/batch")
+            .set_json(&serde_json::json!([video["id"], missing]))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body, serde_json::json!({ "deleted": [video["id"]], "not_found": [missing] }));
+
+        let req = TestRequest::delete()
+            .uri("/api/v1/videos/batch")
+            .set_json(&serde_json::json!([1]))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod unique_title_tests {
+    use super::*;
+    use ntex_api::config::Config;
+    use ntex_api::repositories::video_repository::VideoRepository;
+    use ntex_api::services::video_service::VideoService;
+
+    async fn create_titled(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        title: &str,
+    ) -> ntex::web::WebResponse {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": title, "youtube_id": next_youtube_id() }))
+            .to_request();
+        test::call_service(app, req).await
+    }
+
+    async fn app_with_uniqueness(
+        enabled: bool,
+    ) -> ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>> {
+        let db = setup_database().await;
+        let config = Config {
+            unique_normalized_titles: enabled,
+            ..Config::default()
+        };
+        VideoService::with_config(VideoRepository::new(db.clone()), config.clone())
+            .sync_title_uniqueness()
+            .await
+            .unwrap();
+        init_controller_service_with_config(db, config).await
+    }
+
+    #[ntex::test]
+    async fn test_titles_differing_in_case_conflict_when_enabled() {
+        let app = app_with_uniqueness(true).await;
+        assert_eq!(create_titled(&app, "Hello").await.status(), StatusCode::CREATED);
+        assert_eq!(create_titled(&app, " hello ").await.status(), StatusCode::CONFLICT);
+
+        let other = create_titled(&app, "World").await;
+        assert_eq!(other.status(), StatusCode::CREATED);
+        let other: serde_json::Value = serde_json::from_slice(&test::read_body(other).await).unwrap();
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", other["id"]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "HELLO" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::CONFLICT);
+    }
+
+    #[ntex::test]
+    async fn test_titles_differing_in_case_coexist_when_disabled() {
+        let app = app_with_uniqueness(false).await;
+        assert_eq!(create_titled(&app, "Hello").await.status(), StatusCode::CREATED);
+        assert_eq!(create_titled(&app, "hello").await.status(), StatusCode::CREATED);
+    }
+}
+
+mod stream_threshold_tests {
+    use super::*;
+    use ntex::http::body::{BodySize, MessageBody};
+    use ntex_api::config::Config;
+
+    async fn list_with_threshold(threshold: usize, rows: usize) -> (BodySize, PaginatedVideoResponse) {
+        let config = Config {
+            stream_threshold_rows: Some(threshold),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        for _ in 0..rows {
+            create_test_video(&app).await;
+        }
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let size = resp.response().body().size();
+        let page = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        (size, page)
+    }
+
+    #[ntex::test]
+    async fn test_list_at_or_below_threshold_is_buffered() {
+        let (size, page) = list_with_threshold(2, 2).await;
+        assert!(matches!(size, BodySize::Sized(_)));
+        assert_eq!(page.videos.len(), 2);
+    }
+
+    #[ntex::test]
+    async fn test_list_above_threshold_is_streamed() {
+        let (size, page) = list_with_threshold(2, 3).await;
+        assert_eq!(size, BodySize::Stream);
+        assert_eq!(page.videos.len(), 3);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.total_pages, 1);
+    }
+}
+
+mod unique_youtube_id_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_duplicate_youtube_id_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": "Copy", "youtube_id": video["youtube_id"] }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["error"], "youtube_id already exists");
+        assert_eq!(body["code"], "conflict");
+    }
+
+    #[ntex::test]
+    async fn test_update_to_taken_youtube_id_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let first = create_test_video(&app).await;
+        let second = create_test_video(&app).await;
+
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", second["id"]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "youtube_id": first["youtube_id"] }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::CONFLICT);
+
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", first["id"]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "youtube_id": first["youtube_id"] }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+    }
+}
+
+mod by_youtube_id_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_lookup_by_youtube_id() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let uri = format!("/api/v1/videos/by-youtube/{}", video["youtube_id"].as_str().unwrap());
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["id"], video["id"]);
+    }
+
+    #[ntex::test]
+    async fn test_unknown_youtube_id_is_404() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos/by-youtube/dQw4w9WgXcQ").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_malformed_youtube_id_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos/by-youtube/short").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod replace_and_patch_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_put_replaces_every_field() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::put()
+            .uri(&format!("/api/v1/videos/{}", video["id"]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Replaced", "youtube_id": "xQc9WgXw4Qd" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.title, "Replaced");
+        assert_eq!(body.youtube_id, "xQc9WgXw4Qd");
+    }
+
+    #[ntex::test]
+    async fn test_put_with_missing_field_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::put()
+            .uri(&uri)
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Only Title" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.title, "Test Video");
+    }
+
+    #[ntex::test]
+    async fn test_patch_only_touches_provided_fields() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", video["id"]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Patched" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.title, "Patched");
+        assert_eq!(body.youtube_id, video["youtube_id"]);
+    }
+
+    #[ntex::test]
+    async fn test_empty_patch_is_rejected_without_touching_the_video() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let req = TestRequest::patch()
+            .uri(&uri)
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({}))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["error"], "no fields to update");
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["updated_at"], video["updated_at"]);
+    }
+}
+
+mod description_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_create_with_description() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Described",
+                "youtube_id": "dQw4w9WgXcQ",
+                "description": "A longer text about the video",
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let video: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video.description.as_deref(), Some("A longer text about the video"));
+
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", video.id))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "title": "Renamed" }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let video: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(video.description.as_deref(), Some("A longer text about the video"));
+    }
+
+    #[ntex::test]
+    async fn test_create_without_description() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        assert!(video["description"].is_null());
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.videos[0].description, None);
+    }
+
+    #[ntex::test]
+    async fn test_overlong_description_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Described",
+                "youtube_id": "dQw4w9WgXcQ",
+                "description": "x".repeat(5001),
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["fields"]["description"][0], "length must be <= 5000");
+    }
+
+    async fn create_described(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+    ) -> VideoResponse {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Described",
+                "youtube_id": "dQw4w9WgXcQ",
+                "description": "Old text",
+            }))
+            .to_request();
+        serde_json::from_slice(&test::read_body(test::call_service(app, req).await).await).unwrap()
+    }
+
+    async fn write(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        req: TestRequest,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let resp = test::call_service(app, req.header(header::IF_MATCH, "*").set_json(&body).to_request()).await;
+        let status = resp.status();
+        (status, serde_json::from_slice(&test::read_body(resp).await).unwrap())
+    }
+
+    #[ntex::test]
+    async fn test_put_without_description_clears_it() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_described(&app).await;
+        let uri = format!("/api/v1/videos/{}", video.id);
+
+        let (status, body) = write(
+            &app,
+            TestRequest::put().uri(&uri),
+            serde_json::json!({ "title": "Replaced", "youtube_id": "dQw4w9WgXcQ" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["description"].is_null());
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let stored: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(stored.description, None);
+    }
+
+    #[ntex::test]
+    async fn test_patch_with_null_description_clears_it() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_described(&app).await;
+        let uri = format!("/api/v1/videos/{}", video.id);
+
+        let (status, body) = write(&app, TestRequest::patch().uri(&uri), serde_json::json!({ "description": null })).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["description"].is_null());
+        assert_eq!(body["title"], "Described");
+
+        let (status, body) = write(
+            &app,
+            TestRequest::patch().uri(&uri),
+            serde_json::json!({ "description": "x".repeat(5001) }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["fields"]["description"][0], "length must be <= 5000");
+    }
+}
+
+mod view_count_tests {
+    use super::*;
+    use ntex_api::api::ViewCountResponse;
+
+    #[ntex::test]
+    async fn test_concurrent_views_are_all_counted() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        assert_eq!(video["view_count"], 0);
+        let uri = format!("/api/v1/videos/{}/view", video["id"]);
+
+        let views = (0..10).map(|_| test::call_service(&app, TestRequest::post().uri(&uri).to_request()));
+        let mut counts = Vec::new();
+        for resp in futures::future::join_all(views).await {
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body: ViewCountResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            counts.push(body.view_count);
+        }
+        counts.sort();
+        assert_eq!(counts, (1..=10).collect::<Vec<i64>>());
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&format!("/api/v1/videos/{}", video["id"])).to_request()).await;
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.view_count, 10);
+        assert_eq!(serde_json::json!(body.updated_at), video["updated_at"]);
+    }
+
+    #[ntex::test]
+    async fn test_view_of_missing_or_deleted_video_is_not_found() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post().uri("/api/v1/videos/999/view").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        let req = TestRequest::delete().uri(&uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+        let req = TestRequest::post().uri(&format!("{}/view", uri)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+mod tag_tests {
+    use super::*;
+
+    async fn create_tagged(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        youtube_id: &str,
+        tags: serde_json::Value,
+    ) -> VideoResponse {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": format!("Tagged {}", youtube_id),
+                "youtube_id": youtube_id,
+                "tags": tags,
+            }))
+            .to_request();
+        let resp = test::call_service(app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        serde_json::from_slice(&test::read_body(resp).await).unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_create_with_tags_normalizes_and_dedupes() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_tagged(&app, "dQw4w9WgXcQ", serde_json::json!(["Music", " music ", "Live"])).await;
+        assert_eq!(video.tags, vec!["live", "music"]);
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&format!("/api/v1/videos/{}", video.id)).to_request()).await;
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.tags, vec!["live", "music"]);
+    }
+
+    #[ntex::test]
+    async fn test_list_filters_by_tag() {
+        let app = init_controller_service(setup_database().await).await;
+        let music = create_tagged(&app, "aaaaaaaaaaa", serde_json::json!(["music"])).await;
+        create_tagged(&app, "bbbbbbbbbbb", serde_json::json!(["news"])).await;
+        create_test_video(&app).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos?tag=Music").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.videos[0].id, music.id);
+        assert_eq!(page.videos[0].tags, vec!["music"]);
+    }
+
+    #[ntex::test]
+    async fn test_invalid_tags_are_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Tagged",
+                "youtube_id": "dQw4w9WgXcQ",
+                "tags": ["x".repeat(51)],
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["fields"]["tags"][0], "each tag must be 1 to 50 characters");
+    }
+}
+
+mod id_list_filter_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_list_filters_by_ids() {
+        let app = init_controller_service(setup_database().await).await;
+        let mut videos = Vec::new();
+        for _ in 0..5 {
+            videos.push(create_test_video(&app).await);
+        }
+        let wanted: Vec<i64> = [0, 2, 4].iter().map(|&i| videos[i]["id"].as_i64().unwrap()).collect();
+
+        let uri = format!("/api/v1/videos?ids={},{},{}", wanted[0], wanted[1], wanted[2]);
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 3);
+        let mut ids: Vec<i64> = page.videos.iter().map(|v| v.id as i64).collect();
+        ids.sort();
+        assert_eq!(ids, wanted);
+    }
+
+    #[ntex::test]
+    async fn test_list_filters_by_youtube_ids() {
+        let app = init_controller_service(setup_database().await).await;
+        let first = create_test_video(&app).await;
+        create_test_video(&app).await;
+        let third = create_test_video(&app).await;
+
+        let uri = format!(
+            "/api/v1/videos?youtube_ids={},{}",
+            first["youtube_id"].as_str().unwrap(),
+            third["youtube_id"].as_str().unwrap()
+        );
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 2);
+        assert!(page.videos.iter().all(|v| v.id == first["id"] || v.id == third["id"]));
+    }
+
+    #[ntex::test]
+    async fn test_malformed_id_list_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::get().uri("/api/v1/videos?ids=1,abc").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod export_tests {
+    use super::*;
+    use chrono::Utc;
+    use ntex_api::config::Config;
+    use ntex_api::entity::video;
+    use sea_orm::sea_query::Expr;
+    use sea_orm::EntityTrait;
+
+    #[ntex::test]
+    async fn test_export_csv_streams_live_videos() {
+        let app = init_controller_service(setup_database().await).await;
+        let first = create_test_video(&app).await;
+        let second = create_test_video(&app).await;
+        let deleted = create_test_video(&app).await;
+        let req = TestRequest::delete().uri(&format!("/api/v1/videos/{}", deleted["id"])).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/export.csv").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get("content-type").unwrap(), "text/csv");
+        assert_eq!(
+            resp.headers().get("content-disposition").unwrap(),
+            "attachment; filename=\"videos.csv\""
+        );
+
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        let row = |video: &serde_json::Value| {
+            let video: VideoResponse = serde_json::from_value(video.clone()).unwrap();
+            format!(
+                "{},{},{},{},{}",
+                video.id,
+                video.title,
+                video.youtube_id,
+                video.created_at.to_rfc3339(),
+                video.updated_at.to_rfc3339()
+            )
+        };
+        let lines: Vec<&str> = body.lines().collect();
+        assert_eq!(lines, vec![
+            "id,title,youtube_id,created_at,updated_at",
+            row(&second).as_str(),
+            row(&first).as_str(),
+        ]);
+    }
+
+    #[ntex::test]
+    async fn test_omitted_order_matches_across_list_stream_and_export() {
+        let database = setup_database().await;
+        let buffered = init_controller_service_with_config(database.clone(), Config::default()).await;
+        let config = Config { stream_threshold_rows: Some(0), ..Config::default() };
+        let streamed = init_controller_service_with_config(database.clone(), config).await;
+        for _ in 0..4 {
+            create_test_video(&buffered).await;
+        }
+        // Identical timestamps leave the id tie-breaker to decide the order
+        video::Entity::update_many()
+            .col_expr(video::Column::CreatedAt, Expr::value(Utc::now()))
+            .exec(&database)
+            .await
+            .unwrap();
+
+        let mut orders = Vec::new();
+        for app in [&buffered, &streamed] {
+            let resp = test::call_service(app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+            let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            orders.push(page.videos.iter().map(|v| v.id.to_string()).collect::<Vec<_>>());
+        }
+        let resp = test::call_service(&buffered, TestRequest::get().uri("/api/v1/videos/export.csv").to_request()).await;
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        orders.push(body.lines().skip(1).map(|line| line.split(',').next().unwrap().to_string()).collect());
+
+        assert_eq!(orders[0], vec!["4", "3", "2", "1"]);
+        assert_eq!(orders[1], orders[0]);
+        assert_eq!(orders[2], orders[0]);
+    }
+}
+
+mod import_tests {
+    use super::*;
+    use ntex_api::api::ImportResponse;
+
+    #[ntex::test]
+    async fn test_import_upserts_by_youtube_id() {
+        let app = init_controller_service(setup_database().await).await;
+        let existing = create_test_video(&app).await;
+        let new_id = next_youtube_id();
+
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/import")
+            .set_json(&serde_json::json!([
+                { "title": "Renamed", "youtube_id": existing["youtube_id"] },
+                { "title": "Imported", "youtube_id": new_id },
+            ]))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let summary: ImportResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!((summary.inserted, summary.updated), (1, 1));
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos?order_by=id&order_direction=asc").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        let videos: Vec<_> = page.videos.iter().map(|v| (v.title.as_str(), v.youtube_id.as_str())).collect();
+        assert_eq!(videos, vec![
+            ("Renamed", existing["youtube_id"].as_str().unwrap()),
+            ("Imported", new_id.as_str()),
+        ]);
+    }
+
+    #[ntex::test]
+    async fn test_import_rolls_back_on_invalid_item() {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/import")
+            .set_json(&serde_json::json!([
+                { "title": "Imported", "youtube_id": next_youtube_id() },
+                { "title": "Duplicate", "youtube_id": "dQw4w9WgXcQ" },
+                { "title": "Duplicate again", "youtube_id": "dQw4w9WgXcQ" },
+            ]))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::CONFLICT);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 0);
+    }
+}
+
+mod link_header_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_middle_page_links_every_direction() {
+        let app = init_controller_service(setup_database().await).await;
+        for _ in 0..5 {
+            create_test_video(&app).await;
+        }
+
+        let req = TestRequest::get()
+            .uri("/api/v1/videos?per_page=2&page=2&search=Test")
+            .header("host", "example.com")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let link = resp.headers().get("link").unwrap().to_str().unwrap();
+        let base = "http://example.com/api/v1/videos?per_page=2&search=Test";
+        assert_eq!(
+            link,
+            format!(
+                "<{base}&page=1>; rel=\"prev\", <{base}&page=3>; rel=\"next\", \
+                 <{base}&page=1>; rel=\"first\", <{base}&page=3>; rel=\"last\"",
+                base = base
+            )
+        );
+    }
+}
+
+mod total_count_header_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_list_carries_total_count_and_content_range() {
+        let app = init_controller_service(setup_database().await).await;
+        for _ in 0..12 {
+            create_test_video(&app).await;
+        }
+
+        let header = |resp: &ntex::web::WebResponse, name: &str| {
+            resp.headers().get(name).unwrap().to_str().unwrap().to_string()
+        };
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos?per_page=5&page=2").to_request()).await;
+        assert_eq!(header(&resp, "x-total-count"), "12");
+        assert_eq!(header(&resp, "content-range"), "videos 5-9/12");
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.total, 12);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos?per_page=5&page=3").to_request()).await;
+        assert_eq!(header(&resp, "content-range"), "videos 10-11/12");
+    }
+}
+
+mod youtube_id_format_tests {
+    use super::*;
+
+    async fn create(app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>, youtube_id: &str) -> (StatusCode, serde_json::Value) {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": "Format", "youtube_id": youtube_id }))
+            .to_request();
+        let resp = test::call_service(app, req).await;
+        let status = resp.status();
+        (status, serde_json::from_slice(&test::read_body(resp).await).unwrap())
+    }
+
+    #[ntex::test]
+    async fn test_valid_youtube_id_is_accepted() {
+        let app = init_controller_service(setup_database().await).await;
+        assert_eq!(create(&app, "a-B_c9D0eFg").await.0, StatusCode::CREATED);
+    }
+
+    #[ntex::test]
+    async fn test_wrong_length_youtube_id_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        let (status, body) = create(&app, "short").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["fields"]["youtube_id"][0], "length must be 11");
+    }
+
+    #[ntex::test]
+    async fn test_youtube_id_outside_alphabet_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        for youtube_id in ["dQw4w9 WgXc", "dQw4w9WgXc!"] {
+            let (status, body) = create(&app, youtube_id).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", youtube_id);
+            assert_eq!(body["fields"]["youtube_id"][0], "may only contain letters, digits, '_' and '-'");
+        }
+
+        let video = create_test_video(&app).await;
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}", video["id"]))
+            .header(header::IF_MATCH, "*")
+            .set_json(&serde_json::json!({ "youtube_id": "dQw4w9WgXc!" }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod delete_return_tests {
+    use super::*;
+    use ntex::http::header;
+    use ntex_api::config::Config;
+
+    #[ntex::test]
+    async fn test_delete_returns_no_content_by_default() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::delete().uri(&format!("/api/v1/videos/{}", video["id"])).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(test::read_body(resp).await.is_empty());
+    }
+
+    #[ntex::test]
+    async fn test_delete_with_return_answers_the_deleted_video() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::delete()
+            .uri(&format!("/api/v1/videos/{}?return=true", video["id"]))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["id"], video["id"]);
+        assert_eq!(body["youtube_id"], video["youtube_id"]);
+        assert!(body["deleted_at"].is_string());
+
+        let req = TestRequest::get().uri(&format!("/api/v1/videos/{}", video["id"])).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_return_is_rejected_for_permanent_deletes() {
+        let config = Config {
+            admin_token: Some("s3cret".to_string()),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let video = create_test_video(&app).await;
+
+        let req = TestRequest::delete()
+            .uri(&format!("/api/v1/videos/{}?permanent=true&return=true", video["id"]))
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+mod if_match_tests {
+    use super::*;
+
+    async fn patch(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        uri: &str,
+        if_match: Option<&str>,
+        title: &str,
+    ) -> ntex::web::WebResponse {
+        let mut req = TestRequest::patch().uri(uri);
+        if let Some(if_match) = if_match {
+            req = req.header(header::IF_MATCH, if_match);
+        }
+        test::call_service(app, req.set_json(&serde_json::json!({ "title": title })).to_request()).await
+    }
+
+    async fn etag_of(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        uri: &str,
+    ) -> String {
+        let resp = test::call_service(app, TestRequest::get().uri(uri).to_request()).await;
+        resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string()
+    }
+
+    #[ntex::test]
+    async fn test_update_with_current_etag_succeeds_and_returns_the_new_one() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        let etag = etag_of(&app, &uri).await;
+
+        let resp = patch(&app, &uri, Some(&etag), "First writer").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let new_etag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+        assert_ne!(new_etag, etag);
+        assert_eq!(new_etag, etag_of(&app, &uri).await);
+
+        let resp = patch(&app, &uri, Some(&new_etag), "Second write").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[ntex::test]
+    async fn test_stale_etag_is_rejected_with_412() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        let etag = etag_of(&app, &uri).await;
+
+        assert_eq!(patch(&app, &uri, Some(&etag), "First writer").await.status(), StatusCode::OK);
+
+        let resp = patch(&app, &uri, Some(&etag), "Lost update").await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["code"], "precondition_failed");
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body["title"], "First writer");
+    }
+
+    #[ntex::test]
+    async fn test_missing_if_match_is_rejected_with_428() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+
+        let resp = patch(&app, &uri, None, "Unconditional").await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_REQUIRED);
+
+        let req = TestRequest::put()
+            .uri(&uri)
+            .set_json(&serde_json::json!({ "title": "Unconditional", "youtube_id": video["youtube_id"] }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::PRECONDITION_REQUIRED);
+    }
+
+    #[ntex::test]
+    async fn test_etag_of_another_video_does_not_match() {
+        let app = init_controller_service(setup_database().await).await;
+        let first = create_test_video(&app).await;
+        let second = create_test_video(&app).await;
+        let etag = etag_of(&app, &format!("/api/v1/videos/{}", first["id"])).await;
+
+        let resp = patch(&app, &format!("/api/v1/videos/{}", second["id"]), Some(&etag), "Wrong tag").await;
+        assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+    }
+}
+
+mod base_path_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    #[ntex::test]
+    async fn test_routes_are_mounted_under_the_configured_prefix() {
+        let config = Config { api_base_path: "/edge/api".to_string(), ..Config::default() };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+
+        let req = TestRequest::post()
+            .uri("/edge/api/videos")
+            .set_json(&serde_json::json!({ "title": "Prefixed", "youtube_id": next_youtube_id() }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let location = resp.headers().get(header::LOCATION).unwrap().to_str().unwrap().to_string();
+        assert!(location.starts_with("/edge/api/videos/"), "{}", location);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/edge/api/videos").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let page: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(page.videos.len(), 1);
+        assert_eq!(page.videos[0].title, "Prefixed");
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+mod random_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_random_returns_one_of_the_videos() {
+        let app = init_controller_service(setup_database().await).await;
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(create_test_video(&app).await["id"].clone());
+        }
+
+        for _ in 0..5 {
+            let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/random").to_request()).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            assert!(ids.contains(&body["id"]), "{}", body);
+        }
+    }
+
+    #[ntex::test]
+    async fn test_random_is_404_without_videos() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let req = TestRequest::delete().uri(&format!("/api/v1/videos/{}", video["id"])).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/random").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+mod duplicates_tests {
+    use super::*;
+    use ntex_api::config::Config;
+    use ntex_api::controllers::id_codec::IdCodec;
+    use ntex_api::db;
+
+    #[ntex::test]
+    async fn test_duplicates_are_grouped_by_youtube_id() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for youtube_id in ["dupAAAAAAAA", "dupAAAAAAAA", "uniqueBBBBB", "dupCCCCCCCC", "dupCCCCCCCC"] {
+            let video = db::create_video(&database, "Copy".to_string(), youtube_id.to_string()).await.unwrap();
+            ids.push(video.id);
+        }
+        db::delete_video(&database, ids[4]).await.unwrap();
+        let app = init_controller_service(database).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/duplicates").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "groups": [
+                    { "youtube_id": "dupAAAAAAAA", "ids": [ids[0], ids[1]] },
+                    { "youtube_id": "dupCCCCCCCC", "ids": [ids[3], ids[4]] },
+                ]
+            })
+        );
+    }
+
+    #[ntex::test]
+    async fn test_no_duplicates_is_an_empty_list() {
+        let app = init_controller_service(setup_database().await).await;
+        create_test_video(&app).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/duplicates").to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body, serde_json::json!({ "groups": [] }));
+    }
+
+    #[ntex::test]
+    async fn test_duplicate_ids_are_hashids_when_configured() {
+        let config = Config { hashid_salt: Some("integration salt".to_string()), ..Config::default() };
+        let codec = IdCodec::from_config(&config);
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let video = db::create_video(&database, "Copy".to_string(), "dupHHHHHHHH".to_string()).await.unwrap();
+            ids.push(video.id);
+        }
+        let app = init_controller_service_with_config(database, config).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/duplicates").to_request()).await;
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(
+            body["groups"][0]["ids"],
+            serde_json::json!([codec.encode(ids[0]), codec.encode(ids[1])])
+        );
+    }
+}
+
+mod stats_tests {
+    use super::*;
+    use ntex_api::config::Config;
+    use chrono::{Duration, Utc};
+    use ntex_api::db;
+    use ntex_api::entity::video;
+    use sea_orm::sea_query::Expr;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    #[ntex::test]
+    async fn test_stats_counts_videos_across_days() {
+        let database = setup_database().await;
+        let mut ids = Vec::new();
+        for i in 0..4 {
+            let video = db::create_video(&database, format!("Stat {}", i), format!("stat{:07}", i)).await.unwrap();
+            ids.push(video.id);
+        }
+        let last_week = Utc::now() - Duration::days(7);
+        let yesterday = Utc::now() - Duration::days(1) - Duration::hours(1);
+        for (id, created_at) in [(ids[0], last_week), (ids[1], yesterday)] {
+            video::Entity::update_many()
+                .col_expr(video::Column::CreatedAt, Expr::value(created_at))
+                .filter(video::Column::Id.eq(id))
+                .exec(&database)
+                .await
+                .unwrap();
+        }
+        db::delete_video(&database, ids[3]).await.unwrap();
+        let app = init_controller_service(database).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/stats").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let stats: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(stats["total"], 3);
+        assert_eq!(stats["deleted"], 1);
+        assert_eq!(stats["created_today"], 1);
+        assert_eq!(stats["newest_id"], ids[2]);
+        let oldest: chrono::DateTime<Utc> = serde_json::from_value(stats["oldest_created_at"].clone()).unwrap();
+        assert_eq!(oldest.timestamp_micros(), last_week.timestamp_micros());
+    }
+
+    #[ntex::test]
+    async fn test_stats_of_an_empty_table() {
+        let app = init_controller_service(setup_database().await).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/stats").to_request()).await;
+        let stats: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(
+            stats,
+            serde_json::json!({
+                "total": 0,
+                "deleted": 0,
+                "created_today": 0,
+                "newest_id": null,
+                "oldest_created_at": null,
+            })
+        );
+    }
+
+    #[ntex::test]
+    async fn test_newest_id_is_a_hashid_when_configured() {
+        let config = Config { hashid_salt: Some("integration salt".to_string()), ..Config::default() };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let created = create_test_video(&app).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos/stats").to_request()).await;
+        let stats: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(stats["newest_id"], created["id"]);
+        assert!(stats["newest_id"].is_string());
+    }
+}
+
+mod malformed_json_tests {
+    use super::*;
+
+    async fn post_raw(body: &'static str) -> (StatusCode, serde_json::Value) {
+        let app = init_controller_service(setup_database().await).await;
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .header(header::CONTENT_TYPE, "application/json")
+            .set_payload(body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let status = resp.status();
+        (status, serde_json::from_slice(&test::read_body(resp).await).unwrap())
+    }
+
+    #[ntex::test]
+    async fn test_malformed_json_is_a_json_400() {
+        let (status, body) = post_raw(r#"{"title": "Broken", "#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "bad_request");
+        assert!(body["error"].as_str().unwrap().starts_with("invalid JSON body: "), "{}", body);
+    }
+
+    #[ntex::test]
+    async fn test_missing_field_is_a_json_400() {
+        let (status, body) = post_raw(r#"{"title": "No id"}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "bad_request");
+        let error = body["error"].as_str().unwrap();
+        assert!(error.starts_with("invalid JSON body: "), "{}", error);
+        assert!(error.contains("missing field `youtube_id`"), "{}", error);
+    }
+}
+
+mod idempotency_tests {
+    use super::*;
+    use ntex::http::Request;
+    use ntex::web::{Error, WebResponse};
+    use ntex::{Pipeline, Service};
+
+    fn create_request(key: &str, youtube_id: &str) -> Request {
+        TestRequest::post()
+            .uri("/api/v1/videos")
+            .header("Idempotency-Key", key)
+            .set_json(&serde_json::json!({ "title": "Retried", "youtube_id": youtube_id }))
+            .to_request()
+    }
+
+    async fn total(app: &Pipeline<impl Service<Request, Response = WebResponse, Error = Error>>) -> u64 {
+        let resp = test::call_service(app, TestRequest::get().uri("/api/v1/videos").to_request()).await;
+        let page: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        page["total"].as_u64().unwrap()
+    }
+
+    #[ntex::test]
+    async fn test_repeated_key_replays_the_original_create() {
+        let app = init_controller_service(setup_database().await).await;
+        let youtube_id = next_youtube_id();
+
+        let first = test::call_service(&app, create_request("order-42", &youtube_id)).await;
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(first.headers().get("idempotent-replayed").is_none());
+        let location = first.headers().get(header::LOCATION).unwrap().clone();
+        let original: serde_json::Value = serde_json::from_slice(&test::read_body(first).await).unwrap();
+
+        let second = test::call_service(&app, create_request("order-42", &youtube_id)).await;
+        assert_eq!(second.status(), StatusCode::CREATED);
+        assert_eq!(second.headers().get("idempotent-replayed").unwrap(), "true");
+        assert_eq!(second.headers().get(header::LOCATION).unwrap(), &location);
+        let replayed: serde_json::Value = serde_json::from_slice(&test::read_body(second).await).unwrap();
+        assert_eq!(replayed["id"], original["id"]);
+
+        assert_eq!(total(&app).await, 1);
+    }
+
+    #[ntex::test]
+    async fn test_failed_create_releases_the_key() {
+        let app = init_controller_service(setup_database().await).await;
+
+        let invalid = test::call_service(&app, create_request("retry-me", "")).await;
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+
+        let retried = test::call_service(&app, create_request("retry-me", &next_youtube_id())).await;
+        assert_eq!(retried.status(), StatusCode::CREATED);
+        assert!(retried.headers().get("idempotent-replayed").is_none());
+        assert_eq!(total(&app).await, 1);
+    }
+}
+
+mod events_tests {
+    use super::*;
+    use ntex::time::{timeout, Millis};
+    use ntex::web::{self, ws};
+    use ntex_api::controllers::video_controller::{self, VideoController};
+    use ntex_api::repositories::video_repository::VideoRepository;
+    use ntex_api::services::video_service::VideoService;
+
+    #[ntex::test]
+    async fn test_create_is_pushed_to_websocket_subscribers() {
+        let db = setup_database().await;
+        let controller = VideoController::new(VideoService::new(VideoRepository::new(db)));
+        let srv = test::server(move || {
+            let controller = controller.clone();
+            web::App::new().configure(move |cfg| video_controller::config(cfg, controller.clone(), "/api/v1"))
+        });
+
+        let (io, codec, _) = srv.ws_at("/api/v1/videos/events").await.unwrap().into_inner();
+
+        let youtube_id = next_youtube_id();
+        let resp = srv
+            .post("/api/v1/videos")
+            .send_json(&serde_json::json!({ "title": "Live", "youtube_id": youtube_id }))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let frame = timeout(Millis(5_000), io.recv(&codec)).await.expect("no event received").unwrap().unwrap();
+        let ws::Frame::Text(text) = frame else {
+            panic!("expected a text frame, got {:?}", frame);
+        };
+        let event: serde_json::Value = serde_json::from_slice(&text).unwrap();
+        assert_eq!(event["type"], "created");
+        assert_eq!(event["video"]["title"], "Live");
+        assert_eq!(event["video"]["youtube_id"], youtube_id);
+    }
+
+    #[ntex::test]
+    async fn test_create_is_pushed_to_event_stream_subscribers() {
+        use futures::future::poll_fn;
+        use ntex::http::body::MessageBody;
+
+        let app = common::init_test_service(setup_database().await).await;
+
+        let req = TestRequest::get()
+            .uri("/api/v1/videos/stream")
+            .header(header::ACCEPT_ENCODING, "gzip")
+            .to_request();
+        let mut resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::CONTENT_TYPE).unwrap(), "text/event-stream");
+        assert!(resp.headers().get(header::CONTENT_ENCODING).is_none());
+        let mut body = resp.take_body();
+
+        let youtube_id = next_youtube_id();
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({ "title": "Streamed", "youtube_id": youtube_id }))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::CREATED);
+
+        let chunk = timeout(Millis(5_000), poll_fn(|cx| body.poll_next_chunk(cx)))
+            .await
+            .expect("no frame received")
+            .unwrap()
+            .unwrap();
+        let frame = std::str::from_utf8(&chunk).unwrap();
+        let data = frame.strip_prefix("data: ").and_then(|f| f.strip_suffix("\n\n")).expect(frame);
+        let event: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(event["type"], "created");
+        assert_eq!(event["video"]["youtube_id"], youtube_id);
+    }
+}
+
+mod include_deleted_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    const ADMIN_TOKEN: &str = "s3cret";
+
+    async fn deleted_video() -> (ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>, String) {
+        let config = Config {
+            admin_token: Some(ADMIN_TOKEN.to_string()),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}", video["id"]);
+        let req = TestRequest::delete().uri(&uri).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+        (app, uri)
+    }
+
+    #[ntex::test]
+    async fn test_deleted_video_is_hidden_by_default() {
+        let (app, uri) = deleted_video().await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let req = TestRequest::get().uri(&format!("{}?include_deleted=false", uri)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[ntex::test]
+    async fn test_include_deleted_returns_the_deleted_row_to_admins() {
+        let (app, uri) = deleted_video().await;
+
+        let req = TestRequest::get()
+            .uri(&format!("{}?include_deleted=true", uri))
+            .header(header::AUTHORIZATION, format!("Bearer {}", ADMIN_TOKEN))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert!(body.deleted_at.is_some());
+
+        let req = TestRequest::get().uri(&format!("{}?include_deleted=true", uri)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+mod page_size_header_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    async fn per_page(uri: &str, page_size: Option<&str>) -> (StatusCode, serde_json::Value) {
+        let config = Config {
+            default_per_page: 3,
+            admin_token: Some("s3cret".to_string()),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let mut req = TestRequest::get().uri(uri).header(header::AUTHORIZATION, "Bearer s3cret");
+        if let Some(size) = page_size {
+            req = req.header("X-Page-Size", size);
+        }
+        let resp = test::call_service(&app, req.to_request()).await;
+        let status = resp.status();
+        (status, serde_json::from_slice(&test::read_body(resp).await).unwrap())
+    }
+
+    #[ntex::test]
+    async fn test_explicit_per_page_wins_over_header() {
+        let (status, body) = per_page("/api/v1/videos?per_page=5", Some("7")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["per_page"], 5);
+    }
+
+    #[ntex::test]
+    async fn test_header_wins_over_config_default() {
+        let (status, body) = per_page("/api/v1/videos", Some("7")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["per_page"], 7);
+
+        let (_, body) = per_page("/api/v1/videos/trash", Some("7")).await;
+        assert_eq!(body["per_page"], 7);
+    }
+
+    #[ntex::test]
+    async fn test_config_default_applies_without_either() {
+        let (status, body) = per_page("/api/v1/videos", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["per_page"], 3);
+    }
+
+    #[ntex::test]
+    async fn test_header_is_clamped_to_max() {
+        let (status, body) = per_page("/api/v1/videos", Some("500")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["per_page"], 100);
+    }
+
+    #[ntex::test]
+    async fn test_malformed_header_is_rejected() {
+        let (status, body) = per_page("/api/v1/videos", Some("lots")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["code"], "bad_request");
+    }
+}
+
+mod full_text_fallback_tests {
+    use super::*;
+
+    #[ntex::test]
+    async fn test_fts_falls_back_to_like_without_an_index() {
+        // The test schema is built from the entities, so there is no `videos_fts` table
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        let resp = test::call_service(&app, TestRequest::get().uri("/api/v1/videos?search=Test%20Vid&fts=true").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: PaginatedVideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.total, 1);
+        assert_eq!(serde_json::json!(body.videos[0].id), video["id"]);
+    }
+}
+
+mod status_tests {
+    use super::*;
+    use ntex_api::entity::video::VideoStatus;
+
+    async fn create_with_status(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        status: &str,
+    ) -> StatusCode {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos")
+            .set_json(&serde_json::json!({
+                "title": "Test Video",
+                "youtube_id": next_youtube_id(),
+                "status": status,
+            }))
+            .to_request();
+        test::call_service(app, req).await.status()
+    }
+
+    #[ntex::test]
+    async fn test_new_video_defaults_to_draft() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        assert_eq!(video["status"], "draft");
+    }
+
+    #[ntex::test]
+    async fn test_unknown_status_is_rejected() {
+        let app = init_controller_service(setup_database().await).await;
+        assert_eq!(create_with_status(&app, "live").await, StatusCode::BAD_REQUEST);
+
+        let req = TestRequest::get().uri("/api/v1/videos?status=live").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[ntex::test]
+    async fn test_list_filters_by_status() {
+        let app = init_controller_service(setup_database().await).await;
+        create_test_video(&app).await;
+        assert_eq!(create_with_status(&app, "published").await, StatusCode::CREATED);
+        assert_eq!(create_with_status(&app, "archived").await, StatusCode::CREATED);
+
+        for status in ["draft", "published", "archived"] {
+            let req = TestRequest::get().uri(&format!("/api/v1/videos?status={}", status)).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+            let videos = body["videos"].as_array().unwrap();
+            assert_eq!(videos.len(), 1, "status={}", status);
+            assert_eq!(videos[0]["status"], status);
+        }
+    }
+
+    #[ntex::test]
+    async fn test_publish_transitions_draft_to_published_once() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+        let uri = format!("/api/v1/videos/{}/publish", video["id"]);
+
+        let resp = test::call_service(&app, TestRequest::post().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: VideoResponse = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        assert_eq!(body.status, VideoStatus::Published);
+
+        let resp = test::call_service(&app, TestRequest::post().uri(&uri).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let resp = test::call_service(&app, TestRequest::post().uri("/api/v1/videos/999999/publish").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+}
+
+mod position_tests {
+    use super::*;
+    use ntex_api::config::Config;
+
+    async fn move_to(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        id: &serde_json::Value,
+        position: i32,
+    ) -> StatusCode {
+        let req = TestRequest::patch()
+            .uri(&format!("/api/v1/videos/{}/position", id))
+            .set_json(&serde_json::json!({ "position": position }))
+            .to_request();
+        test::call_service(app, req).await.status()
+    }
+
+    /// Returns `(id, position)` of every live video in manual order
+    async fn order(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+    ) -> Vec<(serde_json::Value, serde_json::Value)> {
+        let req = TestRequest::get().uri("/api/v1/videos?order_by=position&order_direction=asc").to_request();
+        let resp = test::call_service(app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(&test::read_body(resp).await).unwrap();
+        body["videos"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|video| (video["id"].clone(), video["position"].clone()))
+            .collect()
+    }
+
+    fn expected(ids: &[&serde_json::Value]) -> Vec<(serde_json::Value, serde_json::Value)> {
+        ids.iter().zip(1..).map(|(id, position)| ((*id).clone(), serde_json::json!(position))).collect()
+    }
+
+    #[ntex::test]
+    async fn test_new_videos_are_appended() {
+        let app = init_controller_service(setup_database().await).await;
+        let a = create_test_video(&app).await["id"].clone();
+        let b = create_test_video(&app).await["id"].clone();
+
+        assert_eq!(order(&app).await, expected(&[&a, &b]));
+    }
+
+    #[ntex::test]
+    async fn test_moving_up_and_down_shifts_the_others() {
+        let app = init_controller_service(setup_database().await).await;
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            ids.push(create_test_video(&app).await["id"].clone());
+        }
+        let [a, b, c, d] = [&ids[0], &ids[1], &ids[2], &ids[3]];
+
+        assert_eq!(move_to(&app, d, 2).await, StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[a, d, b, c]));
+
+        assert_eq!(move_to(&app, a, 3).await, StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[d, b, a, c]));
+
+        // Past the end moves to the last place
+        assert_eq!(move_to(&app, d, 99).await, StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[b, a, c, d]));
+    }
+
+    #[ntex::test]
+    async fn test_move_rejects_bad_position_and_unknown_video() {
+        let app = init_controller_service(setup_database().await).await;
+        let video = create_test_video(&app).await;
+
+        assert_eq!(move_to(&app, &video["id"], 0).await, StatusCode::BAD_REQUEST);
+        assert_eq!(move_to(&app, &serde_json::json!(999999), 1).await, StatusCode::NOT_FOUND);
+    }
+
+    async fn swap(
+        app: &ntex::Pipeline<impl ntex::Service<ntex::http::Request, Response = ntex::web::WebResponse, Error = ntex::web::Error>>,
+        a: &serde_json::Value,
+        b: &serde_json::Value,
+    ) -> StatusCode {
+        let req = TestRequest::post()
+            .uri("/api/v1/videos/swap")
+            .set_json(&serde_json::json!({ "a": a, "b": b }))
+            .to_request();
+        test::call_service(app, req).await.status()
+    }
+
+    #[ntex::test]
+    async fn test_swap_exchanges_positions() {
+        let app = init_controller_service(setup_database().await).await;
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            ids.push(create_test_video(&app).await["id"].clone());
+        }
+        let [a, b, c] = [&ids[0], &ids[1], &ids[2]];
+
+        assert_eq!(swap(&app, a, c).await, StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[c, b, a]));
+    }
+
+    #[ntex::test]
+    async fn test_swap_with_missing_id_changes_nothing() {
+        let app = init_controller_service(setup_database().await).await;
+        let a = create_test_video(&app).await["id"].clone();
+        let b = create_test_video(&app).await["id"].clone();
+
+        assert_eq!(swap(&app, &a, &serde_json::json!(999999)).await, StatusCode::NOT_FOUND);
+        assert_eq!(swap(&app, &serde_json::json!(999999), &b).await, StatusCode::NOT_FOUND);
+        assert_eq!(order(&app).await, expected(&[&a, &b]));
+    }
+
+    #[ntex::test]
+    async fn test_restored_video_is_appended_and_positions_stay_contiguous() {
+        let config = Config {
+            admin_token: Some("s3cret".to_string()),
+            ..Config::default()
+        };
+        let app = init_controller_service_with_config(setup_database().await, config).await;
+        let mut ids = Vec::new();
+        for _ in 0..4 {
+            ids.push(create_test_video(&app).await["id"].clone());
+        }
+        let [a, b, c, d] = [&ids[0], &ids[1], &ids[2], &ids[3]];
+
+        let req = TestRequest::delete().uri(&format!("/api/v1/videos/{}", b)).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::NO_CONTENT);
+        assert_eq!(move_to(&app, d, 1).await, StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[d, a, c]));
+
+        // b's old position 2 is taken by now; it goes back at the end instead
+        let req = TestRequest::post()
+            .uri(&format!("/api/v1/videos/{}/restore", b))
+            .header(header::AUTHORIZATION, "Bearer s3cret")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[d, a, c, b]));
+
+        assert_eq!(move_to(&app, b, 1).await, StatusCode::OK);
+        assert_eq!(order(&app).await, expected(&[b, d, a, c]));
+    }
+}